@@ -0,0 +1,323 @@
+//! [VOPRF](crate::voprf) with key anonymity.
+//!
+//! Issuers with several live keys can partition clients by which key answered
+//! them. An [`AnonymousServer`] instead proves with an [`OrProof`] that the
+//! [`EvaluationElement`] was computed with *one of* a published set of
+//! [`PublicKey`]s without revealing which one.
+//!
+//! Clients blind with a regular [`VoprfClient`] and complete the evaluation
+//! with [`finalize()`]. The output is identical to
+//! [`VoprfClient::finalize()`] with the [`VoprfServer`] that holds the same
+//! [`KeyPair`], so clients must not learn which key that was through other
+//! means.
+//!
+//! [`VoprfServer`]: crate::voprf::VoprfServer
+
+use alloc::vec::Vec;
+use core::array;
+use core::fmt::{self, Debug, Formatter};
+
+use digest::Output;
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+use zeroize::ZeroizeOnDrop;
+
+use crate::cipher_suite::{CipherSuite, NonZeroScalar, ScalarLength};
+use crate::common::{BlindedElement, EvaluationElement, Proof, UnblindedElement};
+use crate::dleq::{self, Composites, Domain};
+use crate::error::{Error, Result};
+use crate::internal::{self, ElementWithRepr};
+use crate::key::{KeyPair, PublicKey};
+use crate::voprf::VoprfClient;
+
+/// VOPRF server proving evaluation under one of a set of [`PublicKey`]s.
+pub struct AnonymousServer<Cs: CipherSuite> {
+	/// The [`KeyPair`].
+	key_pair: KeyPair<Cs::Group>,
+	/// The published [`PublicKey`]s.
+	public_keys: Vec<PublicKey<Cs::Group>>,
+	/// The index of this servers [`PublicKey`] in `public_keys`.
+	index: usize,
+}
+
+/// Disjunctive [`Proof`] that an [`EvaluationElement`] was computed with one
+/// of a set of [`PublicKey`]s.
+pub struct OrProof<Cs: CipherSuite> {
+	/// One [`Proof`] per [`PublicKey`].
+	proofs: Vec<Proof<Cs>>,
+}
+
+/// Returned by [`AnonymousServer::blind_evaluate()`].
+pub struct AnonymousBlindEvaluateResult<Cs: CipherSuite> {
+	/// The [`EvaluationElement`].
+	pub evaluation_element: EvaluationElement<Cs>,
+	/// The [`OrProof`].
+	pub proof: OrProof<Cs>,
+}
+
+impl<Cs: CipherSuite> AnonymousServer<Cs> {
+	/// Creates a new [`AnonymousServer`] from the provided [`KeyPair`] and the
+	/// published `public_keys`.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::KeySet`] if `public_keys` is empty, exceeds a length of
+	/// [`u16::MAX`] or doesn't contain the [`PublicKey`] of the [`KeyPair`].
+	pub fn new(
+		key_pair: KeyPair<Cs::Group>,
+		public_keys: Vec<PublicKey<Cs::Group>>,
+	) -> Result<Self> {
+		if public_keys.len() > u16::MAX.into() {
+			return Err(Error::KeySet);
+		}
+
+		let index = public_keys
+			.iter()
+			.position(|public_key| public_key == key_pair.public_key())
+			.ok_or(Error::KeySet)?;
+
+		Ok(Self {
+			key_pair,
+			public_keys,
+			index,
+		})
+	}
+
+	/// Returns the [`KeyPair`].
+	#[must_use]
+	pub const fn key_pair(&self) -> &KeyPair<Cs::Group> {
+		&self.key_pair
+	}
+
+	/// Returns the published [`PublicKey`]s.
+	#[must_use]
+	pub fn public_keys(&self) -> &[PublicKey<Cs::Group>] {
+		&self.public_keys
+	}
+
+	/// Process the [`BlindedElement`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind_evaluate<R>(
+		&self,
+		rng: &mut R,
+		blinded_element: &BlindedElement<Cs>,
+	) -> Result<AnonymousBlindEvaluateResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let k = self.key_pair.secret_key().to_scalar();
+		let evaluation_element =
+			EvaluationElement::from(ElementWithRepr::new(k * blinded_element.as_element()));
+
+		let composites = composites(
+			Some(k),
+			&self.public_keys,
+			blinded_element,
+			&evaluation_element,
+		)
+		.map_err(Error::into_random::<R>)?;
+		let proofs = dleq::generate_or_proof(rng, k, self.index, composites, &self.public_keys)?;
+
+		Ok(AnonymousBlindEvaluateResult {
+			evaluation_element,
+			proof: OrProof { proofs },
+		})
+	}
+}
+
+impl<Cs: CipherSuite> OrProof<Cs> {
+	/// Serializes this [`OrProof`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.proofs.iter().flat_map(Proof::to_repr).collect()
+	}
+
+	/// Deserializes the provided `repr` to an [`OrProof`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let chunks = repr.chunks_exact(ScalarLength::<Cs>::USIZE * 2);
+
+		if repr.is_empty() || !chunks.remainder().is_empty() {
+			return Err(Error::FromRepr);
+		}
+
+		chunks
+			.map(Proof::from_repr)
+			.collect::<Result<_>>()
+			.map(|proofs| Self { proofs })
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for AnonymousServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			key_pair: self.key_pair.clone(),
+			public_keys: self.public_keys.clone(),
+			index: self.index,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for AnonymousServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("AnonymousServer")
+			.field("key_pair", &self.key_pair)
+			.field("public_keys", &self.public_keys)
+			.field("index", &self.index)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for AnonymousServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for AnonymousServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.key_pair.eq(&other.key_pair) && self.public_keys.eq(&other.public_keys)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for AnonymousServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for OrProof<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			proofs: self.proofs.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for OrProof<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("OrProof")
+			.field("proofs", &self.proofs)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for OrProof<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for OrProof<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.proofs.eq(&other.proofs)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for OrProof<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for AnonymousBlindEvaluateResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("AnonymousBlindEvaluateResult")
+			.field("evaluation_element", &self.evaluation_element)
+			.field("proof", &self.proof)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for AnonymousBlindEvaluateResult<Cs> {}
+
+/// Verifies the [`OrProof`] against the published `public_keys` and
+/// completes the evaluation without hashing the unblinded element.
+///
+/// # Errors
+///
+/// - [`Error::KeySet`] if `public_keys` is empty or exceeds a length of
+///   [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and
+///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+/// - [`Error::Proof`] if the [`OrProof`] is invalid.
+pub fn finalize_element<Cs: CipherSuite>(
+	client: &VoprfClient<Cs>,
+	public_keys: &[PublicKey<Cs::Group>],
+	evaluation_element: &EvaluationElement<Cs>,
+	proof: &OrProof<Cs>,
+) -> Result<UnblindedElement<Cs>> {
+	if public_keys.len() > u16::MAX.into() {
+		return Err(Error::KeySet);
+	}
+
+	let composites = composites(
+		None,
+		public_keys,
+		client.blinded_element(),
+		evaluation_element,
+	)?;
+	dleq::verify_or_proof(composites, public_keys, &proof.proofs)?;
+
+	let [unblinded_element] = internal::batch_finalize_element::<Cs, 1>(
+		[client.blind()],
+		array::from_ref(evaluation_element.as_element()).iter(),
+	);
+
+	Ok(unblinded_element.into())
+}
+
+/// Verifies the [`OrProof`] against the published `public_keys` and
+/// completes the evaluation.
+///
+/// The output is identical to the output of [`VoprfClient::finalize()`] with
+/// the server directly.
+///
+/// # Errors
+///
+/// - [`Error::KeySet`] if `public_keys` is empty or exceeds a length of
+///   [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and
+///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+/// - [`Error::Proof`] if the [`OrProof`] is invalid.
+/// - [`Error::InputLength`] if the provided `input` exceeds a length of
+///   [`u16::MAX`].
+pub fn finalize<Cs: CipherSuite>(
+	client: &VoprfClient<Cs>,
+	public_keys: &[PublicKey<Cs::Group>],
+	input: &[&[u8]],
+	evaluation_element: &EvaluationElement<Cs>,
+	proof: &OrProof<Cs>,
+) -> Result<Output<Cs::Hash>> {
+	finalize_element(client, public_keys, evaluation_element, proof)?.finalize(input, None)
+}
+
+/// Computes the [`Composites`] of the [`OrProof`], seeded with the first
+/// [`PublicKey`]. All branches share the same `M` and `Z`.
+///
+/// # Errors
+///
+/// - [`Error::KeySet`] if `public_keys` is empty.
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and
+///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+fn composites<Cs: CipherSuite>(
+	k: Option<NonZeroScalar<Cs>>,
+	public_keys: &[PublicKey<Cs::Group>],
+	blinded_element: &BlindedElement<Cs>,
+	evaluation_element: &EvaluationElement<Cs>,
+) -> Result<Composites<Cs>> {
+	let public_key = public_keys.first().ok_or(Error::KeySet)?;
+	let c = array::from_ref(blinded_element.as_ref()).iter();
+	let d = array::from_ref(evaluation_element.as_ref()).iter();
+
+	let generator = dleq::generator::<Cs>();
+	let domain = Domain::Custom {
+		dst: dleq::OR_PROOF_DST,
+		A: &generator,
+	};
+
+	dleq::compute_composites::<_, 1>(domain, k, public_key.as_ref(), c, d)
+}
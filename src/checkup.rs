@@ -0,0 +1,546 @@
+//! Compromised-credential checkup based on k-anonymity prefixes and OPRF.
+//!
+//! The server evaluates all breached credentials with
+//! [`CheckupServer::buckets()`] and groups the outputs into
+//! [`CheckupBuckets`] by a short prefix of the hash of each credential.
+//!
+//! 1. The client creates a [`CheckupRequest`] with
+//!    [`CheckupClient::request()`], containing the prefix of its credential and
+//!    a [`BlindedElement`], and sends it to the server.
+//! 2. The server responds with [`CheckupServer::respond()`], which evaluates
+//!    the [`BlindedElement`] and attaches the bucket of outputs matching the
+//!    prefix.
+//! 3. The client learns if its credential was breached with
+//!    [`CheckupClient::check()`].
+//!
+//! The server only learns the prefix of the credential, which is shared by
+//! roughly `breached credentials / 2^prefix_bits` other credentials, while the
+//! client learns nothing about other credentials in the bucket.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+
+use digest::{FixedOutput, Output, OutputSizeUser, Update};
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::{CipherSuite, ElementLength};
+use crate::common::{BlindedElement, EvaluationElement};
+use crate::error::{Error, Result};
+use crate::key::SecretKey;
+use crate::oprf::{OprfBlindResult, OprfClient, OprfServer};
+use crate::util::{I2ospLength, UpdateIter};
+
+/// Credential checkup client.
+pub struct CheckupClient<Cs: CipherSuite> {
+	/// The underlying [`OprfClient`].
+	client: OprfClient<Cs>,
+}
+
+/// Credential checkup server.
+pub struct CheckupServer<Cs: CipherSuite> {
+	/// The underlying [`OprfServer`].
+	server: OprfServer<Cs>,
+}
+
+/// Outputs of breached credentials grouped by prefix.
+pub struct CheckupBuckets<Cs: CipherSuite> {
+	/// Length of the prefixes in bits.
+	prefix_bits: u8,
+	/// Sorted outputs of each prefix.
+	buckets: BTreeMap<u32, Vec<Output<Cs::Hash>>>,
+}
+
+/// Returned from [`CheckupClient::request()`].
+pub struct CheckupRequestResult<Cs: CipherSuite> {
+	/// The [`CheckupClient`] to be used with [`CheckupClient::check()`].
+	pub client: CheckupClient<Cs>,
+	/// The [`CheckupRequest`] to be sent to the server.
+	pub request: CheckupRequest<Cs>,
+}
+
+/// Sent from the client to the server.
+pub struct CheckupRequest<Cs: CipherSuite> {
+	/// Prefix of the credential.
+	prefix: u32,
+	/// The [`BlindedElement`] of the credential.
+	blinded_element: BlindedElement<Cs>,
+}
+
+/// Sent from the server to the client in response to a [`CheckupRequest`].
+pub struct CheckupResponse<Cs: CipherSuite> {
+	/// The [`EvaluationElement`] of the credential.
+	evaluation_element: EvaluationElement<Cs>,
+	/// Outputs of all breached credentials with the same prefix.
+	bucket: Vec<Output<Cs::Hash>>,
+}
+
+impl<Cs: CipherSuite> CheckupClient<Cs> {
+	/// Creates a [`CheckupRequest`] for the provided `credential` with a
+	/// prefix of `prefix_bits` length.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Prefix`] if `prefix_bits` is zero or larger than 32.
+	/// - [`Error::InputLength`] if the `credential` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](crate::group::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the `credential` can never produce a valid
+	///   output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn request<R>(
+		rng: &mut R,
+		credential: &[&[u8]],
+		prefix_bits: u8,
+	) -> Result<CheckupRequestResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let prefix = prefix::<Cs>(credential, prefix_bits).map_err(Error::into_random::<R>)?;
+		let OprfBlindResult {
+			client,
+			blinded_element,
+		} = OprfClient::blind(rng, credential)?;
+
+		Ok(CheckupRequestResult {
+			client: Self { client },
+			request: CheckupRequest {
+				prefix,
+				blinded_element,
+			},
+		})
+	}
+
+	/// Returns [`true`] if the `credential` was found in the bucket of the
+	/// [`CheckupResponse`].
+	///
+	/// The `credential` must be the same as passed to
+	/// [`CheckupClient::request()`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::InputLength`] if the `credential` exceeds a length of
+	/// [`u16::MAX`].
+	pub fn check(&self, credential: &[&[u8]], response: &CheckupResponse<Cs>) -> Result<bool> {
+		let output = self
+			.client
+			.finalize(credential, &response.evaluation_element)?;
+
+		Ok(response.bucket.contains(&output))
+	}
+}
+
+impl<Cs: CipherSuite> CheckupServer<Cs> {
+	/// Creates a new [`CheckupServer`] by generating a random [`SecretKey`].
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn new<R>(rng: &mut R) -> Result<Self, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		Ok(Self {
+			server: OprfServer::new(rng)?,
+		})
+	}
+
+	/// Creates a new [`CheckupServer`] from the provided [`SecretKey`].
+	#[must_use]
+	pub const fn from_key(secret_key: SecretKey<Cs::Group>) -> Self {
+		Self {
+			server: OprfServer::from_key(secret_key),
+		}
+	}
+
+	/// Returns the [`SecretKey`].
+	#[must_use]
+	pub const fn secret_key(&self) -> &SecretKey<Cs::Group> {
+		self.server.secret_key()
+	}
+
+	/// Evaluates the provided breached `credentials` and groups them into
+	/// [`CheckupBuckets`] with prefixes of `prefix_bits` length.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Prefix`] if `prefix_bits` is zero or larger than 32.
+	/// - [`Error::InputLength`] if a credential exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](crate::group::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if a credential can never produce a valid
+	///   output.
+	pub fn buckets(&self, credentials: &[&[&[u8]]], prefix_bits: u8) -> Result<CheckupBuckets<Cs>> {
+		let mut buckets = CheckupBuckets::new(prefix_bits)?;
+
+		for credential in credentials {
+			self.insert(&mut buckets, credential)?;
+		}
+
+		Ok(buckets)
+	}
+
+	/// Evaluates the provided breached `credential` and inserts it into the
+	/// [`CheckupBuckets`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the `credential` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](crate::group::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the `credential` can never produce a valid
+	///   output.
+	pub fn insert(&self, buckets: &mut CheckupBuckets<Cs>, credential: &[&[u8]]) -> Result<()> {
+		let prefix = prefix::<Cs>(credential, buckets.prefix_bits)?;
+		let output = self.server.evaluate(credential)?;
+		let bucket = buckets.buckets.entry(prefix).or_default();
+
+		if let Err(index) = bucket.binary_search(&output) {
+			bucket.insert(index, output);
+		}
+
+		Ok(())
+	}
+
+	/// Processes the [`CheckupRequest`] of a [`CheckupClient`].
+	#[must_use]
+	pub fn respond(
+		&self,
+		request: &CheckupRequest<Cs>,
+		buckets: &CheckupBuckets<Cs>,
+	) -> CheckupResponse<Cs> {
+		CheckupResponse {
+			evaluation_element: self.server.blind_evaluate(&request.blinded_element),
+			bucket: buckets.bucket(request.prefix).to_vec(),
+		}
+	}
+}
+
+impl<Cs: CipherSuite> CheckupBuckets<Cs> {
+	/// Creates empty [`CheckupBuckets`] with prefixes of `prefix_bits` length.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Prefix`] if `prefix_bits` is zero or larger than 32.
+	pub fn new(prefix_bits: u8) -> Result<Self> {
+		check_prefix_bits(prefix_bits)?;
+
+		Ok(Self {
+			prefix_bits,
+			buckets: BTreeMap::new(),
+		})
+	}
+
+	/// Returns the length of the prefixes in bits.
+	#[must_use]
+	pub const fn prefix_bits(&self) -> u8 {
+		self.prefix_bits
+	}
+
+	/// Returns the sorted outputs of the provided `prefix`.
+	#[must_use]
+	pub fn bucket(&self, prefix: u32) -> &[Output<Cs::Hash>] {
+		self.buckets
+			.get(&prefix)
+			.map(Vec::as_slice)
+			.unwrap_or_default()
+	}
+}
+
+impl<Cs: CipherSuite> CheckupRequest<Cs> {
+	/// Returns the prefix of the credential.
+	#[must_use]
+	pub const fn prefix(&self) -> u32 {
+		self.prefix
+	}
+
+	/// Returns the [`BlindedElement`].
+	#[must_use]
+	pub const fn blinded_element(&self) -> &BlindedElement<Cs> {
+		&self.blinded_element
+	}
+
+	/// Deserializes the provided `repr` to a [`CheckupRequest`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let (prefix, blinded_element) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+
+		Ok(Self {
+			prefix: u32::from_be_bytes(*prefix),
+			blinded_element: BlindedElement::from_repr(blinded_element)?,
+		})
+	}
+
+	/// Serializes this [`CheckupRequest`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		[
+			self.prefix.to_be_bytes().as_slice(),
+			self.blinded_element.as_repr().as_slice(),
+		]
+		.concat()
+	}
+}
+
+impl<Cs: CipherSuite> CheckupResponse<Cs> {
+	/// Returns the [`EvaluationElement`].
+	#[must_use]
+	pub const fn evaluation_element(&self) -> &EvaluationElement<Cs> {
+		&self.evaluation_element
+	}
+
+	/// Returns the outputs of all breached credentials with the same prefix.
+	#[must_use]
+	pub const fn bucket(&self) -> &[Output<Cs::Hash>] {
+		self.bucket.as_slice()
+	}
+
+	/// Deserializes the provided `repr` to a [`CheckupResponse`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let (evaluation_element, bucket) = repr
+			.split_at_checked(ElementLength::<Cs>::USIZE)
+			.ok_or(Error::FromRepr)?;
+		let chunks = bucket.chunks_exact(<Cs::Hash as OutputSizeUser>::OutputSize::USIZE);
+
+		if !chunks.remainder().is_empty() {
+			return Err(Error::FromRepr);
+		}
+
+		Ok(Self {
+			evaluation_element: EvaluationElement::from_repr(evaluation_element)?,
+			bucket: chunks
+				.map(|output| output.try_into().map_err(|_| Error::FromRepr))
+				.collect::<Result<_>>()?,
+		})
+	}
+
+	/// Serializes this [`CheckupResponse`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.evaluation_element
+			.as_repr()
+			.iter()
+			.chain(self.bucket.iter().flatten())
+			.copied()
+			.collect()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for CheckupClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for CheckupClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("CheckupClient")
+			.field("client", &self.client)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for CheckupClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for CheckupClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client.eq(&other.client)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for CheckupClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for CheckupServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			server: self.server.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for CheckupServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("CheckupServer")
+			.field("server", &self.server)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for CheckupServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for CheckupServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.server.eq(&other.server)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for CheckupServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for CheckupBuckets<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			prefix_bits: self.prefix_bits,
+			buckets: self.buckets.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for CheckupBuckets<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("CheckupBuckets")
+			.field("prefix_bits", &self.prefix_bits)
+			.field("buckets", &self.buckets)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for CheckupBuckets<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for CheckupBuckets<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.prefix_bits.eq(&other.prefix_bits) && self.buckets.eq(&other.buckets)
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for CheckupRequestResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("CheckupRequestResult")
+			.field("client", &self.client)
+			.field("request", &self.request)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for CheckupRequestResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for CheckupRequest<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			prefix: self.prefix,
+			blinded_element: self.blinded_element.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for CheckupRequest<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("CheckupRequest")
+			.field("prefix", &self.prefix)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for CheckupRequest<Cs> {
+	fn drop(&mut self) {
+		self.prefix.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for CheckupRequest<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for CheckupRequest<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.prefix.eq(&other.prefix) && self.blinded_element.eq(&other.blinded_element)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for CheckupRequest<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for CheckupResponse<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			evaluation_element: self.evaluation_element.clone(),
+			bucket: self.bucket.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for CheckupResponse<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("CheckupResponse")
+			.field("evaluation_element", &self.evaluation_element)
+			.field("bucket", &self.bucket)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for CheckupResponse<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for CheckupResponse<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.evaluation_element.eq(&other.evaluation_element) && self.bucket.eq(&other.bucket)
+	}
+}
+
+/// Checks that `prefix_bits` is between 1 and 32.
+///
+/// # Errors
+///
+/// Returns [`Error::Prefix`] if `prefix_bits` is zero or larger than 32.
+const fn check_prefix_bits(prefix_bits: u8) -> Result<()> {
+	if prefix_bits == 0 || prefix_bits > 32 {
+		Err(Error::Prefix)
+	} else {
+		Ok(())
+	}
+}
+
+/// Computes the prefix of the provided `credential`.
+///
+/// # Errors
+///
+/// - [`Error::Prefix`] if `prefix_bits` is zero or larger than 32.
+/// - [`Error::InputLength`] if the `credential` exceeds a length of
+///   [`u16::MAX`].
+fn prefix<Cs: CipherSuite>(credential: &[&[u8]], prefix_bits: u8) -> Result<u32> {
+	check_prefix_bits(prefix_bits)?;
+
+	let hash = Cs::Hash::default()
+		.chain(credential.i2osp_length().ok_or(Error::InputLength)?)
+		.chain_iter(credential.iter().copied())
+		.chain(b"CheckupPrefix")
+		.finalize_fixed();
+	let prefix = hash
+		.first_chunk()
+		.copied()
+		.map(u32::from_be_bytes)
+		.expect("hash output must be at least 4 bytes");
+
+	Ok(prefix >> (32 - prefix_bits))
+}
@@ -2,12 +2,15 @@
 
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
+use core::array;
 use core::fmt::{self, Debug, Formatter};
+use core::iter;
 
 #[cfg(feature = "serde")]
 use ::serde::ser::SerializeStruct;
 #[cfg(feature = "serde")]
 use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+use digest::Output;
 use hybrid_array::Array;
 use hybrid_array::typenum::{Sum, Unsigned};
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -15,14 +18,18 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 use crate::cipher_suite::{
 	CipherSuite, ElementLength, NonIdentityElement, NonZeroScalar, Scalar, ScalarLength,
 };
+use crate::dleq::{self, Domain};
 use crate::error::{Error, Result};
 use crate::group::Group;
-use crate::internal::ElementWithRepr;
+use crate::internal::{self, ElementWithRepr, Info};
+use crate::key::PublicKey;
+use crate::poprf::PoprfClient;
 #[cfg(feature = "serde")]
 use crate::serde;
 
 /// Protocol mode. Only used in
-/// [`SecretKey::derive()`](crate::key::SecretKey::derive).
+/// [`SecretKey::derive()`](crate::key::SecretKey::derive) and
+/// [`KeyPair::prove_possession()`](crate::key::KeyPair::prove_possession).
 ///
 /// See [RFC 9497 § 3.1](https://www.rfc-editor.org/rfc/rfc9497.html#name-identifiers-for-protocol-va).
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -96,6 +103,14 @@ pub struct BlindedElement<Cs: CipherSuite>(ElementWithRepr<Cs::Group>);
 #[repr(transparent)]
 pub struct EvaluationElement<Cs: CipherSuite>(ElementWithRepr<Cs::Group>);
 
+/// Returned by [`*Client::finalize_element()`]. The unblinded
+/// [`EvaluationElement`] before it is hashed to an output by
+/// [`UnblindedElement::finalize()`].
+///
+/// [`*Client::finalize_element()`]: crate::oprf::OprfClient::finalize_element
+#[repr(transparent)]
+pub struct UnblindedElement<Cs: CipherSuite>(ElementWithRepr<Cs::Group>);
+
 /// Returned by [`*Server::blind_evaluate()`]. Sent to the client to be verified
 /// by [`*Client::finalize()`].
 ///
@@ -222,6 +237,43 @@ impl<Cs: CipherSuite> EvaluationElement<Cs> {
 	}
 }
 
+impl<Cs: CipherSuite> UnblindedElement<Cs> {
+	/// Hashes this [`UnblindedElement`] to the output `*Client::finalize()`
+	/// would have returned. `info` must be [`Some`] for POPRF and [`None`]
+	/// otherwise.
+	///
+	/// Corresponds to the last step of
+	/// [`Finalize()` in RFC 9497 § 3.3](https://www.rfc-editor.org/rfc/rfc9497.html#name-online-protocol).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the provided `input` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InfoLength`] if the provided `info` exceeds a length of
+	///   [`u16::MAX`].
+	pub fn finalize(&self, input: &[&[u8]], info: Option<&[u8]>) -> Result<Output<Cs::Hash>> {
+		let info = info.map(Info::new).transpose()?;
+		let [output] = internal::batch_finalize::<Cs, 1>(&[input], iter::once(&self.0), info)?;
+
+		Ok(output)
+	}
+
+	/// Deserializes the provided `repr` to a [`UnblindedElement`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		ElementWithRepr::from_repr(repr).map(Self)
+	}
+
+	/// Returns the representation of this [`UnblindedElement`].
+	#[must_use]
+	pub const fn as_repr(&self) -> &Array<u8, ElementLength<Cs>> {
+		self.0.as_repr()
+	}
+}
+
 impl<Cs: CipherSuite> Proof<Cs> {
 	/// Serializes this [`Proof`].
 	#[must_use]
@@ -250,6 +302,129 @@ impl<Cs: CipherSuite> Proof<Cs> {
 
 		Ok(Self { c, s })
 	}
+
+	/// Verifies this [`Proof`] without access to the clients blinds, e.g. by
+	/// an auditor observing the exchange.
+	///
+	/// Without `info` this verifies a [VOPRF](crate::voprf) [`Proof`] against
+	/// the [`PublicKey`]. With `info` this verifies a [POPRF](crate::poprf)
+	/// [`Proof`] against the tweaked key derived from the [`PublicKey`] and
+	/// `info`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InfoLength`] if the provided `info` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfo`] if the provided `info` can never produce a
+	///   valid output.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn verify(
+		&self,
+		public_key: &PublicKey<Cs::Group>,
+		blinded_element: &BlindedElement<Cs>,
+		evaluation_element: &EvaluationElement<Cs>,
+		info: Option<&[u8]>,
+	) -> Result<()> {
+		self.batch_verify(
+			public_key,
+			array::from_ref(blinded_element),
+			array::from_ref(evaluation_element),
+			info,
+		)
+	}
+
+	/// Batch verifies this combined [`Proof`] without access to the clients
+	/// blinds *without allocation*.
+	///
+	/// See [`verify()`](Self::verify).
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of items in `blinded_elements` and
+	///   `evaluation_elements` are zero or exceed a length of [`u16::MAX`].
+	/// - [`Error::InfoLength`] if the provided `info` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfo`] if the provided `info` can never produce a
+	///   valid output.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn batch_verify<const N: usize>(
+		&self,
+		public_key: &PublicKey<Cs::Group>,
+		blinded_elements: &[BlindedElement<Cs>; N],
+		evaluation_elements: &[EvaluationElement<Cs>; N],
+		info: Option<&[u8]>,
+	) -> Result<()> {
+		if N == 0 || N > u16::MAX.into() {
+			return Err(Error::Batch);
+		}
+
+		let (mode, key) = verification_key(public_key, info)?;
+
+		let b = blinded_elements.iter().map(BlindedElement::as_ref);
+		let e = evaluation_elements.iter().map(EvaluationElement::as_ref);
+
+		let composites = if mode == Mode::Poprf {
+			dleq::compute_composites::<_, N>(Domain::Rfc(mode), None, key.as_ref(), e, b)
+		} else {
+			dleq::compute_composites::<_, N>(Domain::Rfc(mode), None, key.as_ref(), b, e)
+		}?;
+		dleq::verify_proof(Domain::Rfc(mode), composites, key.as_ref(), self)
+	}
+
+	/// Batch verifies this combined [`Proof`] without access to the clients
+	/// blinds.
+	///
+	/// See [`verify()`](Self::verify).
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of items in `blinded_elements` and
+	///   `evaluation_elements` are zero, don't match or exceed a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InfoLength`] if the provided `info` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfo`] if the provided `info` can never produce a
+	///   valid output.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	#[cfg(feature = "alloc")]
+	pub fn batch_alloc_verify<'blinded_elements, 'evaluation_elements, Ib, Iee>(
+		&self,
+		public_key: &PublicKey<Cs::Group>,
+		blinded_elements: Ib,
+		evaluation_elements: Iee,
+		info: Option<&[u8]>,
+	) -> Result<()>
+	where
+		Ib: ExactSizeIterator<Item = &'blinded_elements BlindedElement<Cs>>,
+		Iee: ExactSizeIterator<Item = &'evaluation_elements EvaluationElement<Cs>>,
+	{
+		let length = blinded_elements.len();
+
+		if length == 0 || length != evaluation_elements.len() || length > u16::MAX.into() {
+			return Err(Error::Batch);
+		}
+
+		let (mode, key) = verification_key(public_key, info)?;
+
+		let b = blinded_elements.map(BlindedElement::as_ref);
+		let e = evaluation_elements.map(EvaluationElement::as_ref);
+
+		let composites = if mode == Mode::Poprf {
+			dleq::alloc_compute_composites(Domain::Rfc(mode), length, None, key.as_ref(), e, b)
+		} else {
+			dleq::alloc_compute_composites(Domain::Rfc(mode), length, None, key.as_ref(), b, e)
+		}?;
+		dleq::verify_proof(Domain::Rfc(mode), composites, key.as_ref(), self)
+	}
 }
 
 impl<Cs: CipherSuite> AsRef<ElementWithRepr<Cs::Group>> for BlindedElement<Cs> {
@@ -335,6 +510,12 @@ impl<'de, Cs: CipherSuite> Deserialize<'de> for EvaluationElement<Cs> {
 
 impl<Cs: CipherSuite> Eq for EvaluationElement<Cs> {}
 
+impl<Cs: CipherSuite> From<ElementWithRepr<Cs::Group>> for EvaluationElement<Cs> {
+	fn from(value: ElementWithRepr<Cs::Group>) -> Self {
+		Self(value)
+	}
+}
+
 #[cfg_attr(coverage_nightly, coverage(off))]
 impl<Cs: CipherSuite> PartialEq for EvaluationElement<Cs> {
 	fn eq(&self, other: &Self) -> bool {
@@ -354,6 +535,60 @@ impl<Cs: CipherSuite> Serialize for EvaluationElement<Cs> {
 
 impl<Cs: CipherSuite> ZeroizeOnDrop for EvaluationElement<Cs> {}
 
+impl<Cs: CipherSuite> AsRef<ElementWithRepr<Cs::Group>> for UnblindedElement<Cs> {
+	fn as_ref(&self) -> &ElementWithRepr<Cs::Group> {
+		&self.0
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for UnblindedElement<Cs> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for UnblindedElement<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("UnblindedElement").field(&self.0).finish()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Cs: CipherSuite> Deserialize<'de> for UnblindedElement<Cs> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		serde::newtype_struct(deserializer, "UnblindedElement").map(Self)
+	}
+}
+
+impl<Cs: CipherSuite> Eq for UnblindedElement<Cs> {}
+
+impl<Cs: CipherSuite> From<ElementWithRepr<Cs::Group>> for UnblindedElement<Cs> {
+	fn from(value: ElementWithRepr<Cs::Group>) -> Self {
+		Self(value)
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for UnblindedElement<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0.eq(&other.0)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<Cs: CipherSuite> Serialize for UnblindedElement<Cs> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_newtype_struct("UnblindedElement", &self.0)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for UnblindedElement<Cs> {}
+
 #[cfg_attr(coverage_nightly, coverage(off))]
 impl<Cs: CipherSuite> Clone for Proof<Cs> {
 	fn clone(&self) -> Self {
@@ -462,3 +697,25 @@ impl<Cs: CipherSuite> Debug for BatchAllocBlindEvaluateResult<Cs> {
 
 #[cfg(feature = "alloc")]
 impl<Cs: CipherSuite> ZeroizeOnDrop for BatchAllocBlindEvaluateResult<Cs> {}
+
+/// Returns the [`Mode`] and [`PublicKey`] to verify a [`Proof`] against.
+///
+/// # Errors
+///
+/// - [`Error::InfoLength`] if the provided `info` exceeds a length of
+///   [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::InvalidInfo`] if the provided `info` can never produce a valid
+///   output.
+fn verification_key<Cs: CipherSuite>(
+	public_key: &PublicKey<Cs::Group>,
+	info: Option<&[u8]>,
+) -> Result<(Mode, PublicKey<Cs::Group>)> {
+	match info {
+		Some(info) => PoprfClient::<Cs>::tweaked_key(public_key, Info::new(info)?)
+			.map(|key| (Mode::Poprf, key)),
+		None => Ok((Mode::Voprf, public_key.clone())),
+	}
+}
@@ -0,0 +1,840 @@
+//! Discrete logarithm equality (DLEQ) proofs.
+//!
+//! Proves that `log_A(B) == log_C(D)` for arbitrary bases `A` and `C`. Batches
+//! of `(C, D)` pairs are combined into a single [`Proof`] as described in
+//! [RFC 9497 § 2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2).
+//!
+//! Every proof is bound to a caller-supplied domain separation tag (DST),
+//! which must be unique to the protocol using it. [`Proof`]s generated with
+//! one DST never verify with another.
+//!
+//! The [`Proof`]s of [VOPRF](crate::voprf) and [POPRF](crate::poprf) are
+//! generated by this module as well, with `A` being the generator element and
+//! the RFC 9497 context string as DST.
+
+#![expect(non_snake_case, reason = "following the specification exactly")]
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::array;
+use core::iter;
+use core::ops::Deref;
+
+use digest::{FixedOutput, Output, OutputSizeUser, Update};
+#[cfg(feature = "alloc")]
+use hybrid_array::Array;
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+
+use crate::cipher_suite::{CipherSuite, Element, NonIdentityElement, NonZeroScalar, Scalar};
+use crate::common::{Mode, Proof};
+use crate::error::{Error, Result};
+use crate::group::{CipherSuiteExt, Group};
+use crate::internal::{self, ElementWithRepr};
+#[cfg(feature = "alloc")]
+use crate::key::PublicKey;
+use crate::key::SecretKey;
+use crate::util::{Concat, I2osp, I2ospLength, UpdateIter};
+
+/// DST of disjunctive [`Proof`]s, see [`generate_or_proof()`].
+#[cfg(feature = "alloc")]
+pub(crate) const OR_PROOF_DST: &[u8] = b"OPRF-OrProof";
+
+/// Domain separation of a [`Proof`].
+pub(crate) enum Domain<'dst, Cs: CipherSuite> {
+	/// [RFC 9497](https://www.rfc-editor.org/rfc/rfc9497.html) with the
+	/// context string of the provided [`Mode`]. `A` is always the generator
+	/// element.
+	Rfc(Mode),
+	/// Caller-supplied DST and base `A`.
+	Custom {
+		/// The DST.
+		dst: &'dst [u8],
+		/// `A`.
+		A: &'dst ElementWithRepr<Cs::Group>,
+	},
+}
+
+/// Returned by [`compute_composites()`].
+pub(crate) struct Composites<Cs: CipherSuite> {
+	/// `M`. Might be half the expected value to facilitate batch serialization.
+	M: Element<Cs>,
+	/// `Z`. Might be half the expected value to facilitate batch serialization.
+	Z: Element<Cs>,
+}
+
+impl<Cs: CipherSuite> Domain<'_, Cs> {
+	/// Returns `A`.
+	fn A(self) -> Element<Cs> {
+		match self {
+			Self::Rfc(_) => Cs::Group::element_generator(),
+			Self::Custom { A, .. } => (*A.as_element()).into(),
+		}
+	}
+
+	/// Returns `scalar * A`.
+	fn mul_A(self, scalar: &Scalar<Cs>) -> Element<Cs> {
+		match self {
+			Self::Rfc(_) => Cs::Group::scalar_mul_by_generator(scalar),
+			Self::Custom { .. } => *scalar * &self.A(),
+		}
+	}
+
+	/// Hashes the provided `input` to a [`Scalar`] with this [`Domain`]s DST.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+	/// are incompatible.
+	fn hash_to_scalar(self, input: &[&[u8]]) -> Result<Scalar<Cs>> {
+		match self {
+			Self::Rfc(mode) => Cs::hash_to_scalar(mode, input, None),
+			Self::Custom { dst, .. } => Cs::Group::hash_to_scalar::<Cs::ExpandMsg>(input, &[dst])
+				.map_err(|_| Error::InvalidCipherSuite),
+		}
+	}
+
+	/// Computes the `seed` used by [`compute_composites()`].
+	fn seed(self, B: &ElementWithRepr<Cs::Group>) -> Output<Cs::Hash> {
+		let Bm = B.as_repr();
+
+		match self {
+			Self::Rfc(mode) => {
+				let seed_dst =
+					[b"Seed-".as_slice()].concat(internal::create_context_string::<Cs>(mode));
+
+				Cs::Hash::default()
+					.chain(Cs::I2OSP_ELEMENT_LEN)
+					.chain(Bm)
+					.chain(seed_dst.i2osp_length().expect("`Cs::Id` too long"))
+					.chain_iter(seed_dst.into_iter())
+					.finalize_fixed()
+			}
+			Self::Custom { dst, A } => {
+				let seed_dst = [b"Seed-".as_slice(), dst];
+
+				Cs::Hash::default()
+					.chain(Cs::I2OSP_ELEMENT_LEN)
+					.chain(A.as_repr())
+					.chain(Cs::I2OSP_ELEMENT_LEN)
+					.chain(Bm)
+					.chain(seed_dst.i2osp_length().expect("`dst` must be checked"))
+					.chain_iter(seed_dst.into_iter())
+					.finalize_fixed()
+			}
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for Domain<'_, Cs> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<Cs: CipherSuite> Copy for Domain<'_, Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for Composites<Cs> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<Cs: CipherSuite> Copy for Composites<Cs> {}
+
+/// Generates a [`Proof`] that `log_a(b) == log_c(d)`, where the `secret_key`
+/// is the discrete logarithm.
+///
+/// # Errors
+///
+/// - [`Error::Dst`] if `dst` is empty or exceeds a length of 255.
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Random`] if the provided `rng` fails.
+pub fn prove<Cs, R>(
+	rng: &mut R,
+	dst: &[u8],
+	secret_key: &SecretKey<Cs::Group>,
+	a: &NonIdentityElement<Cs>,
+	b: &NonIdentityElement<Cs>,
+	c: &NonIdentityElement<Cs>,
+	d: &NonIdentityElement<Cs>,
+) -> Result<Proof<Cs>, Error<R::Error>>
+where
+	Cs: CipherSuite,
+	R: ?Sized + TryCryptoRng,
+{
+	batch_prove::<Cs, R, 1>(
+		rng,
+		dst,
+		secret_key,
+		a,
+		b,
+		array::from_ref(c),
+		array::from_ref(d),
+	)
+}
+
+/// Generates a single [`Proof`] that `log_a(b) == log_c(d)` for every pair of
+/// `c` and `d` *without allocation*.
+///
+/// # Errors
+///
+/// - [`Error::Dst`] if `dst` is empty or exceeds a length of 255.
+/// - [`Error::Batch`] if `N` is zero or exceeds [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Random`] if the provided `rng` fails.
+pub fn batch_prove<Cs, R, const N: usize>(
+	rng: &mut R,
+	dst: &[u8],
+	secret_key: &SecretKey<Cs::Group>,
+	a: &NonIdentityElement<Cs>,
+	b: &NonIdentityElement<Cs>,
+	c: &[NonIdentityElement<Cs>; N],
+	d: &[NonIdentityElement<Cs>; N],
+) -> Result<Proof<Cs>, Error<R::Error>>
+where
+	Cs: CipherSuite,
+	R: ?Sized + TryCryptoRng,
+{
+	check_dst(dst).map_err(Error::into_random::<R>)?;
+
+	if N == 0 || N > u16::MAX.into() {
+		return Err(Error::Batch);
+	}
+
+	let a = ElementWithRepr::new(*a);
+	let b = ElementWithRepr::new(*b);
+	let c = c.map(ElementWithRepr::new);
+	let d = d.map(ElementWithRepr::new);
+	let domain = Domain::Custom { dst, A: &a };
+	let k = secret_key.to_scalar();
+
+	let composites = compute_composites::<_, N>(domain, Some(k), &b, c.iter(), d.iter())
+		.map_err(Error::into_random::<R>)?;
+	generate_proof(domain, rng, k, composites, &b)
+}
+
+/// Generates a single [`Proof`] that `log_a(b) == log_c(d)` for every pair of
+/// `c` and `d`.
+///
+/// # Errors
+///
+/// - [`Error::Dst`] if `dst` is empty or exceeds a length of 255.
+/// - [`Error::Batch`] if the number of items in `c` and `d` don't match, are
+///   zero or exceed a length of [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Random`] if the provided `rng` fails.
+#[cfg(feature = "alloc")]
+pub fn batch_alloc_prove<Cs, R>(
+	rng: &mut R,
+	dst: &[u8],
+	secret_key: &SecretKey<Cs::Group>,
+	a: &NonIdentityElement<Cs>,
+	b: &NonIdentityElement<Cs>,
+	c: &[NonIdentityElement<Cs>],
+	d: &[NonIdentityElement<Cs>],
+) -> Result<Proof<Cs>, Error<R::Error>>
+where
+	Cs: CipherSuite,
+	R: ?Sized + TryCryptoRng,
+{
+	check_dst(dst).map_err(Error::into_random::<R>)?;
+
+	let length = c.len();
+
+	if length == 0 || length > u16::MAX.into() || length != d.len() {
+		return Err(Error::Batch);
+	}
+
+	let a = ElementWithRepr::new(*a);
+	let b = ElementWithRepr::new(*b);
+	let c: Vec<_> = c.iter().copied().map(ElementWithRepr::new).collect();
+	let d: Vec<_> = d.iter().copied().map(ElementWithRepr::new).collect();
+	let domain = Domain::Custom { dst, A: &a };
+	let k = secret_key.to_scalar();
+
+	let composites = alloc_compute_composites(domain, length, Some(k), &b, c.iter(), d.iter())
+		.map_err(Error::into_random::<R>)?;
+	generate_proof(domain, rng, k, composites, &b)
+}
+
+/// Verifies a [`Proof`] that `log_a(b) == log_c(d)`.
+///
+/// # Errors
+///
+/// - [`Error::Dst`] if `dst` is empty or exceeds a length of 255.
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Proof`] if the [`Proof`] is invalid.
+pub fn verify<Cs: CipherSuite>(
+	dst: &[u8],
+	a: &NonIdentityElement<Cs>,
+	b: &NonIdentityElement<Cs>,
+	c: &NonIdentityElement<Cs>,
+	d: &NonIdentityElement<Cs>,
+	proof: &Proof<Cs>,
+) -> Result<()> {
+	batch_verify::<Cs, 1>(dst, a, b, array::from_ref(c), array::from_ref(d), proof)
+}
+
+/// Verifies a [`Proof`] that `log_a(b) == log_c(d)` for every pair of `c` and
+/// `d` *without allocation*.
+///
+/// # Errors
+///
+/// - [`Error::Dst`] if `dst` is empty or exceeds a length of 255.
+/// - [`Error::Batch`] if `N` is zero or exceeds [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Proof`] if the [`Proof`] is invalid.
+pub fn batch_verify<Cs: CipherSuite, const N: usize>(
+	dst: &[u8],
+	a: &NonIdentityElement<Cs>,
+	b: &NonIdentityElement<Cs>,
+	c: &[NonIdentityElement<Cs>; N],
+	d: &[NonIdentityElement<Cs>; N],
+	proof: &Proof<Cs>,
+) -> Result<()> {
+	check_dst(dst)?;
+
+	if N == 0 || N > u16::MAX.into() {
+		return Err(Error::Batch);
+	}
+
+	let a = ElementWithRepr::new(*a);
+	let b = ElementWithRepr::new(*b);
+	let c = c.map(ElementWithRepr::new);
+	let d = d.map(ElementWithRepr::new);
+	let domain = Domain::Custom { dst, A: &a };
+
+	let composites = compute_composites::<_, N>(domain, None, &b, c.iter(), d.iter())?;
+	verify_proof(domain, composites, &b, proof)
+}
+
+/// Verifies a [`Proof`] that `log_a(b) == log_c(d)` for every pair of `c` and
+/// `d`.
+///
+/// # Errors
+///
+/// - [`Error::Dst`] if `dst` is empty or exceeds a length of 255.
+/// - [`Error::Batch`] if the number of items in `c` and `d` don't match, are
+///   zero or exceed a length of [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Proof`] if the [`Proof`] is invalid.
+#[cfg(feature = "alloc")]
+pub fn batch_alloc_verify<Cs: CipherSuite>(
+	dst: &[u8],
+	a: &NonIdentityElement<Cs>,
+	b: &NonIdentityElement<Cs>,
+	c: &[NonIdentityElement<Cs>],
+	d: &[NonIdentityElement<Cs>],
+	proof: &Proof<Cs>,
+) -> Result<()> {
+	check_dst(dst)?;
+
+	let length = c.len();
+
+	if length == 0 || length > u16::MAX.into() || length != d.len() {
+		return Err(Error::Batch);
+	}
+
+	let a = ElementWithRepr::new(*a);
+	let b = ElementWithRepr::new(*b);
+	let c: Vec<_> = c.iter().copied().map(ElementWithRepr::new).collect();
+	let d: Vec<_> = d.iter().copied().map(ElementWithRepr::new).collect();
+	let domain = Domain::Custom { dst, A: &a };
+
+	let composites = alloc_compute_composites(domain, length, None, &b, c.iter(), d.iter())?;
+	verify_proof(domain, composites, &b, proof)
+}
+
+/// Checks that the provided `dst` is not empty and does not exceed a length of
+/// 255.
+///
+/// # Errors
+///
+/// Returns [`Error::Dst`] if `dst` is empty or exceeds a length of 255.
+fn check_dst(dst: &[u8]) -> Result<()> {
+	if dst.is_empty() || dst.len() > u8::MAX.into() {
+		Err(Error::Dst)
+	} else {
+		Ok(())
+	}
+}
+
+/// Returns the generator element as `A` of a [`Domain::Custom`].
+pub(crate) fn generator<Cs: CipherSuite>() -> ElementWithRepr<Cs::Group> {
+	let Ok(generator) = Cs::Group::element_generator().try_into() else {
+		unreachable!("generator is never the identity element")
+	};
+
+	ElementWithRepr::new(generator)
+}
+
+/// Corresponds to
+/// [`GenerateProof()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-3).
+///
+/// `A` is determined by the [`Domain`].
+/// `C` and `D` are used to generate [`Composites`].
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Random`] if the provided `rng` fails.
+pub(crate) fn generate_proof<Cs, R>(
+	domain: Domain<'_, Cs>,
+	rng: &mut R,
+	k: NonZeroScalar<Cs>,
+	composites: Composites<Cs>,
+	B: &ElementWithRepr<Cs::Group>,
+) -> Result<Proof<Cs>, Error<R::Error>>
+where
+	Cs: CipherSuite,
+	R: ?Sized + TryCryptoRng,
+{
+	let Composites::<Cs> { M, Z } = composites;
+
+	let r = Cs::Group::scalar_random(rng).map_err(Error::Random)?.into();
+	let t2 = domain.mul_A(&Cs::Group::scalar_maybe_halve(&r));
+	let t3 = r * &M;
+
+	let c = compute_c::<Cs>(domain, B, M, Z, t2, t3).map_err(Error::into_random::<R>)?;
+	let s = r - &(c * k.deref());
+
+	Ok(Proof { c, s })
+}
+
+/// Corresponds to
+/// [`VerifyProof()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-2).
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Proof`] if the [`Proof`] is invalid.
+pub(crate) fn verify_proof<Cs>(
+	domain: Domain<'_, Cs>,
+	composites: Composites<Cs>,
+	B: &ElementWithRepr<Cs::Group>,
+	proof: &Proof<Cs>,
+) -> Result<()>
+where
+	Cs: CipherSuite,
+{
+	let Composites::<Cs> { M, Z } = composites;
+
+	let expected_c = recompute_c(domain, B, M, Z, proof)?;
+
+	if expected_c == proof.c {
+		Ok(())
+	} else {
+		Err(Error::Proof)
+	}
+}
+
+/// Generates a disjunctive [`Proof`] that `k` is the [`SecretKey`] of one of
+/// the provided `Bs` without revealing which one.
+///
+/// Every `B` gets its own [`Proof`], the one at `index` is generated with `k`
+/// and the others are simulated. Their `c`s sum up to the challenge computed
+/// by [`compute_or_c()`] over all of them.
+///
+/// Uses [`OR_PROOF_DST`] with the generator element as `A`, the
+/// [`Composites`] must be computed with the same [`Domain`].
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Random`] if the provided `rng` fails.
+#[cfg(feature = "alloc")]
+pub(crate) fn generate_or_proof<Cs, R>(
+	rng: &mut R,
+	k: NonZeroScalar<Cs>,
+	index: usize,
+	composites: Composites<Cs>,
+	Bs: &[PublicKey<Cs::Group>],
+) -> Result<Vec<Proof<Cs>>, Error<R::Error>>
+where
+	Cs: CipherSuite,
+	R: ?Sized + TryCryptoRng,
+{
+	debug_assert!(index < Bs.len(), "found out-of-bounds index");
+
+	let Composites::<Cs> { M, Z } = composites;
+	let generator = generator::<Cs>();
+	let domain = Domain::Custom {
+		dst: OR_PROOF_DST,
+		A: &generator,
+	};
+
+	let r = Cs::Group::scalar_random(rng).map_err(Error::Random)?.into();
+	let mut proofs = Vec::with_capacity(Bs.len());
+	let mut challenges = Vec::with_capacity(Bs.len());
+
+	for (j, B) in Bs.iter().enumerate() {
+		if j == index {
+			let t2 = domain.mul_A(&Cs::Group::scalar_maybe_halve(&r));
+			let t3 = r * &M;
+
+			challenges.push(
+				compute_c::<Cs>(domain, B.as_ref(), M, Z, t2, t3)
+					.map_err(Error::into_random::<R>)?,
+			);
+			proofs.push(Proof {
+				c: Scalar::<Cs>::default(),
+				s: Scalar::<Cs>::default(),
+			});
+		} else {
+			let proof = Proof {
+				c: Cs::Group::scalar_random(rng).map_err(Error::Random)?.into(),
+				s: Cs::Group::scalar_random(rng).map_err(Error::Random)?.into(),
+			};
+
+			challenges.push(
+				recompute_c(domain, B.as_ref(), M, Z, &proof).map_err(Error::into_random::<R>)?,
+			);
+			proofs.push(proof);
+		}
+	}
+
+	let c = compute_or_c::<Cs>(&challenges).map_err(Error::into_random::<R>)?;
+	// The `c` at `index` is still zero.
+	let c = proofs.iter().fold(c, |c, proof| c - &proof.c);
+
+	let proof = proofs.get_mut(index).expect("`index` must be in bounds");
+	proof.c = c;
+	proof.s = r - &(c * k.deref());
+
+	Ok(proofs)
+}
+
+/// Verifies a disjunctive [`Proof`] generated by [`generate_or_proof()`].
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::Proof`] if the [`Proof`] is invalid.
+#[cfg(feature = "alloc")]
+pub(crate) fn verify_or_proof<Cs>(
+	composites: Composites<Cs>,
+	Bs: &[PublicKey<Cs::Group>],
+	proofs: &[Proof<Cs>],
+) -> Result<()>
+where
+	Cs: CipherSuite,
+{
+	if Bs.len() != proofs.len() {
+		return Err(Error::Proof);
+	}
+
+	let Composites::<Cs> { M, Z } = composites;
+	let generator = generator::<Cs>();
+	let domain = Domain::Custom {
+		dst: OR_PROOF_DST,
+		A: &generator,
+	};
+
+	let challenges = Bs
+		.iter()
+		.zip(proofs)
+		.map(|(B, proof)| recompute_c(domain, B.as_ref(), M, Z, proof))
+		.collect::<Result<Vec<_>>>()?;
+	let expected_c = compute_or_c::<Cs>(&challenges)?;
+	let c = proofs
+		.iter()
+		.fold(Scalar::<Cs>::default(), |c, proof| c + &proof.c);
+
+	if expected_c == c {
+		Ok(())
+	} else {
+		Err(Error::Proof)
+	}
+}
+
+/// Recomputes `c` from the provided [`Proof`] as done by
+/// [`VerifyProof()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-2).
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+/// are incompatible.
+fn recompute_c<Cs: CipherSuite>(
+	domain: Domain<'_, Cs>,
+	B: &ElementWithRepr<Cs::Group>,
+	M: Element<Cs>,
+	Z: Element<Cs>,
+	proof: &Proof<Cs>,
+) -> Result<Scalar<Cs>> {
+	let Proof { c, s } = proof;
+
+	let t2 = Cs::Group::lincomb(&[
+		(domain.A(), Cs::Group::scalar_maybe_halve(s)),
+		((*B.as_element()).into(), Cs::Group::scalar_maybe_halve(c)),
+	]);
+	let t3 = Cs::Group::lincomb(&[(M, *s), (Z, *c)]);
+
+	compute_c::<Cs>(domain, B, M, Z, t2, t3)
+}
+
+/// Combines the `c`s of every branch of a disjunctive [`Proof`] into a single
+/// challenge.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+/// are incompatible.
+#[cfg(feature = "alloc")]
+fn compute_or_c<Cs: CipherSuite>(challenges: &[Scalar<Cs>]) -> Result<Scalar<Cs>> {
+	let reprs: Vec<_> = challenges.iter().map(Cs::Group::scalar_to_repr).collect();
+	let input: Vec<&[u8]> = reprs.iter().map(Array::as_slice).collect();
+
+	Cs::Group::hash_to_scalar::<Cs::ExpandMsg>(&input, &[OR_PROOF_DST, b"-Challenge"])
+		.map_err(|_| Error::InvalidCipherSuite)
+}
+
+/// Shared code between
+/// [`GenerateProof()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-3)
+/// and
+/// [`VerifyProof()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-2).
+///
+/// With a [`Domain::Custom`] `A` is prepended to the transcript.
+///
+/// The provided [`Element`]s may be halved for the purpose of batch
+/// serialization.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+/// are incompatible.
+fn compute_c<Cs: CipherSuite>(
+	domain: Domain<'_, Cs>,
+	B: &ElementWithRepr<Cs::Group>,
+	M: Element<Cs>,
+	Z: Element<Cs>,
+	t2: Element<Cs>,
+	t3: Element<Cs>,
+) -> Result<Scalar<Cs>> {
+	let Bm = B.as_repr();
+	let [a0, a1, a2, a3] = Cs::Group::element_batch_maybe_double_to_repr(&[M, Z, t2, t3]);
+
+	match domain {
+		Domain::Rfc(_) => domain.hash_to_scalar(&[
+			&Cs::I2OSP_ELEMENT_LEN,
+			Bm,
+			&Cs::I2OSP_ELEMENT_LEN,
+			&a0,
+			&Cs::I2OSP_ELEMENT_LEN,
+			&a1,
+			&Cs::I2OSP_ELEMENT_LEN,
+			&a2,
+			&Cs::I2OSP_ELEMENT_LEN,
+			&a3,
+			b"Challenge",
+		]),
+		Domain::Custom { A, .. } => domain.hash_to_scalar(&[
+			&Cs::I2OSP_ELEMENT_LEN,
+			A.as_repr(),
+			&Cs::I2OSP_ELEMENT_LEN,
+			Bm,
+			&Cs::I2OSP_ELEMENT_LEN,
+			&a0,
+			&Cs::I2OSP_ELEMENT_LEN,
+			&a1,
+			&Cs::I2OSP_ELEMENT_LEN,
+			&a2,
+			&Cs::I2OSP_ELEMENT_LEN,
+			&a3,
+			b"Challenge",
+		]),
+	}
+}
+
+/// Corresponds to
+/// [`ComputeComposites()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-5)
+/// and
+/// [`ComputeCompositesFast()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-4).
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+/// are incompatible.
+pub(crate) fn compute_composites<'items, Cs, const N: usize>(
+	domain: Domain<'_, Cs>,
+	k: Option<NonZeroScalar<Cs>>,
+	B: &ElementWithRepr<Cs::Group>,
+	C: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
+	D: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
+) -> Result<Composites<Cs>>
+where
+	Cs: CipherSuite,
+{
+	debug_assert_ne!(N, 0, "found zero item length");
+	debug_assert_eq!(N, C.len(), "found unequal item length");
+	debug_assert_eq!(N, D.len(), "found unequal item length");
+	debug_assert!(N <= u16::MAX.into(), "found overflowing item length");
+
+	let mut Ms = [(Element::<Cs>::default(), Scalar::<Cs>::default()); N];
+	let mut Zs = k
+		.is_none()
+		.then(|| [(Element::<Cs>::default(), Scalar::<Cs>::default()); N]);
+
+	internal_compute_composites::<Cs>(
+		domain,
+		N,
+		B,
+		C,
+		D,
+		&mut Ms,
+		Zs.as_mut().map(<[_; N]>::as_mut_slice),
+	)?;
+
+	// We skip the initial addition to the identity point, which is a no-op.
+	let M = Cs::Group::lincomb(&Ms);
+	let Z = k.map_or_else(
+		|| Cs::Group::lincomb(&Zs.expect("`Zs` must be present if `k` is not")),
+		|k| k.into() * &M,
+	);
+
+	Ok(Composites { M, Z })
+}
+
+/// Corresponds to
+/// [`ComputeComposites()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-5)
+/// and
+/// [`ComputeCompositesFast()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-4).
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+/// are incompatible.
+#[cfg(feature = "alloc")]
+pub(crate) fn alloc_compute_composites<'items, Cs>(
+	domain: Domain<'_, Cs>,
+	length: usize,
+	k: Option<NonZeroScalar<Cs>>,
+	B: &ElementWithRepr<Cs::Group>,
+	C: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
+	D: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
+) -> Result<Composites<Cs>>
+where
+	Cs: CipherSuite,
+{
+	debug_assert_ne!(length, 0, "found zero item length");
+	debug_assert_eq!(length, C.len(), "found unequal item length");
+	debug_assert_eq!(length, D.len(), "found unequal item length");
+	debug_assert!(length <= u16::MAX.into(), "found overflowing item length");
+
+	let mut Ms = vec![(Element::<Cs>::default(), Scalar::<Cs>::default()); length];
+	let mut Zs = k
+		.is_none()
+		.then(|| vec![(Element::<Cs>::default(), Scalar::<Cs>::default()); length]);
+
+	internal_compute_composites::<Cs>(domain, length, B, C, D, &mut Ms, Zs.as_deref_mut())?;
+
+	let M = Cs::Group::alloc_lincomb(&Ms);
+	let Z = k.map_or_else(
+		|| Cs::Group::alloc_lincomb(&Zs.expect("`Zs` must be present if `k` is not")),
+		|k| k.into() * &M,
+	);
+
+	Ok(Composites { M, Z })
+}
+
+/// Corresponds to
+/// [`ComputeComposites()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-5)
+/// and
+/// [`ComputeCompositesFast()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-4).
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+/// are incompatible.
+fn internal_compute_composites<'items, Cs>(
+	domain: Domain<'_, Cs>,
+	length: usize,
+	B: &ElementWithRepr<Cs::Group>,
+	C: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
+	D: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
+	Ms: &mut [(Element<Cs>, Scalar<Cs>)],
+	Zs: Option<&mut [(Element<Cs>, Scalar<Cs>)]>,
+) -> Result<()>
+where
+	Cs: CipherSuite,
+{
+	debug_assert_ne!(length, 0, "found zero item length");
+	debug_assert_eq!(length, C.len(), "found unequal item length");
+	debug_assert_eq!(length, D.len(), "found unequal item length");
+	debug_assert_eq!(length, Ms.len(), "found unequal item length");
+
+	#[expect(
+		clippy::debug_assert_with_mut_call,
+		reason = "`len()` must not have side-effects"
+	)]
+	if let Some(Zs) = &Zs {
+		debug_assert_eq!(length, Zs.len(), "found unequal item length");
+	}
+
+	debug_assert!(length <= u16::MAX.into(), "found overflowing item length");
+
+	let seed = domain.seed(B);
+
+	for (i, ((Ci, M), (Di, Z))) in (0..=u16::MAX).zip(
+		(C.zip(Ms)).zip(
+			D.zip(
+				Zs.into_iter()
+					.flatten()
+					.map(Some)
+					.chain(iter::repeat_with(|| None)),
+			),
+		),
+	) {
+		let mut di = domain.hash_to_scalar(&[
+			&<Cs::Hash as OutputSizeUser>::OutputSize::U16.i2osp(),
+			&seed,
+			&i.i2osp(),
+			&Cs::I2OSP_ELEMENT_LEN,
+			Ci.as_repr(),
+			&Cs::I2OSP_ELEMENT_LEN,
+			Di.as_repr(),
+			b"Composite",
+		])?;
+
+		di = Cs::Group::scalar_maybe_halve(&di);
+
+		*M = ((*Ci.as_element()).into(), di);
+
+		if let Some(Z) = Z {
+			*Z = ((*Di.as_element()).into(), di);
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,385 @@
+//! Server-aided derivation of deduplication keys, following
+//! [DupLESS](https://eprint.iacr.org/2013/429).
+//!
+//! Convergent encryption derives the key of a file from its content, so equal
+//! files encrypt to equal ciphertexts and can be deduplicated by the storage.
+//! Deriving the key from the content alone allows anyone to confirm guesses of
+//! predictable files offline. DupLESS instead derives the content key through
+//! a [VOPRF](crate::voprf) with a key server, which never learns the content
+//! and can limit the rate of derivations per client.
+//!
+//! 1. The client blinds the content of one or more files with
+//!    [`DuplessClient::blind()`] and sends the [`BlindedElement`]s to the key
+//!    server.
+//! 2. The key server counts the request against the [`RateLimiter`] and
+//!    evaluates the [`BlindedElement`]s with [`DuplessServer::evaluate()`].
+//! 3. The client verifies the combined [`Proof`] against the key servers
+//!    pinned [`PublicKey`] and derives the content keys with
+//!    [`DuplessClient::finalize()`].
+//!
+//! Verifying the [`Proof`] ensures that the key server evaluated every file
+//! with the same [`SecretKey`](crate::key::SecretKey), so a malicious key
+//! server can't serve inconsistent keys that break deduplication or tag
+//! clients.
+//!
+//! This module doesn't authenticate the `client_id`, which is up to the
+//! caller.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+
+use digest::{FixedOutput, Output, Update};
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::CipherSuite;
+use crate::common::{BatchAllocBlindEvaluateResult, BlindedElement, EvaluationElement, Proof};
+use crate::error::{Error, Result};
+use crate::key::{KeyPair, PublicKey};
+use crate::voprf::{VoprfBatchAllocBlindResult, VoprfClient, VoprfServer};
+
+/// Label to frame the content hash used as VOPRF input.
+const CONTENT_LABEL: &[u8] = b"DupLESS-Content-";
+/// Label to derive the content key.
+const KEY_LABEL: &[u8] = b"DupLESS-Key-";
+
+/// DupLESS client.
+pub struct DuplessClient<Cs: CipherSuite> {
+	/// The [`VoprfClient`]s each corresponding to a file in order.
+	clients: Vec<VoprfClient<Cs>>,
+	/// The content hashes each corresponding to a file in order.
+	content_hashes: Vec<Output<Cs::Hash>>,
+}
+
+/// DupLESS key server.
+pub struct DuplessServer<Cs: CipherSuite> {
+	/// The underlying [`VoprfServer`].
+	server: VoprfServer<Cs>,
+}
+
+/// Returned from [`DuplessClient::blind()`].
+pub struct DuplessBlindResult<Cs: CipherSuite> {
+	/// The [`DuplessClient`] to be used with [`DuplessClient::finalize()`].
+	pub client: DuplessClient<Cs>,
+	/// The [`BlindedElement`]s each corresponding to a file in order, to be
+	/// sent to the key server.
+	pub blinded_elements: Vec<BlindedElement<Cs>>,
+}
+
+/// Limits the number of content keys the key server derives per client.
+pub trait RateLimiter {
+	/// Records a request of `count` content keys by the provided `client_id`.
+	/// Returns `false` if the request exceeds the limit of the client.
+	fn allow(&mut self, client_id: &[u8], count: usize) -> bool;
+}
+
+/// In-memory [`RateLimiter`] allowing a fixed number of content keys per
+/// client until it is [`reset()`](Self::reset).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemoryRateLimiter {
+	/// The number of content keys allowed per client.
+	limit: usize,
+	/// The number of content keys derived per client.
+	counts: BTreeMap<Vec<u8>, usize>,
+}
+
+impl<Cs: CipherSuite> DuplessClient<Cs> {
+	/// Blinds the content of the provided files.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of `contents` is zero or exceeds
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if a file can never produce a valid content
+	///   key.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind<R>(
+		rng: &mut R,
+		contents: &[&[u8]],
+	) -> Result<DuplessBlindResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		if contents.is_empty() || contents.len() > u16::MAX.into() {
+			return Err(Error::Batch);
+		}
+
+		let content_hashes: Vec<_> = contents
+			.iter()
+			.map(|content| content_hash::<Cs>(content))
+			.collect();
+		let inputs: Vec<_> = content_hashes
+			.iter()
+			.map(|hash| [hash.as_slice()])
+			.collect();
+
+		let VoprfBatchAllocBlindResult {
+			clients,
+			blinded_elements,
+		} = VoprfClient::batch_alloc_blind(rng, inputs.iter().map(<[_; 1]>::as_slice))?;
+
+		Ok(DuplessBlindResult {
+			client: Self {
+				clients,
+				content_hashes,
+			},
+			blinded_elements,
+		})
+	}
+
+	/// Verifies the combined [`Proof`] against the key servers [`PublicKey`]
+	/// and derives the content keys, each corresponding to a file passed to
+	/// [`DuplessClient::blind()`] in order.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of `evaluation_elements` doesn't match
+	///   the number of files.
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn finalize(
+		&self,
+		public_key: &PublicKey<Cs::Group>,
+		evaluation_elements: &[EvaluationElement<Cs>],
+		proof: &Proof<Cs>,
+	) -> Result<Vec<Output<Cs::Hash>>> {
+		let inputs: Vec<_> = self
+			.content_hashes
+			.iter()
+			.map(|hash| [hash.as_slice()])
+			.collect();
+
+		let outputs = VoprfClient::batch_alloc_finalize(
+			self.clients.iter(),
+			public_key,
+			inputs.iter().map(<[_; 1]>::as_slice),
+			evaluation_elements.iter(),
+			proof,
+		)?;
+
+		Ok(outputs
+			.iter()
+			.map(|output| {
+				Cs::Hash::default()
+					.chain(output)
+					.chain(KEY_LABEL)
+					.chain(&*Cs::ID)
+					.finalize_fixed()
+			})
+			.collect())
+	}
+}
+
+impl<Cs: CipherSuite> DuplessServer<Cs> {
+	/// Creates a new [`DuplessServer`] by generating a random
+	/// [`SecretKey`](crate::key::SecretKey).
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn new<R>(rng: &mut R) -> Result<Self, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		Ok(Self {
+			server: VoprfServer::new(rng)?,
+		})
+	}
+
+	/// Creates a new [`DuplessServer`] from the provided [`KeyPair`].
+	#[must_use]
+	pub const fn from_key_pair(key_pair: KeyPair<Cs::Group>) -> Self {
+		Self {
+			server: VoprfServer::from_key_pair(key_pair),
+		}
+	}
+
+	/// Returns the [`KeyPair`].
+	#[must_use]
+	pub const fn key_pair(&self) -> &KeyPair<Cs::Group> {
+		self.server.key_pair()
+	}
+
+	/// Returns the [`PublicKey`] to be pinned by clients.
+	#[must_use]
+	pub const fn public_key(&self) -> &PublicKey<Cs::Group> {
+		self.server.public_key()
+	}
+
+	/// Counts the request of the provided `client_id` against the
+	/// [`RateLimiter`] and evaluates the [`BlindedElement`]s with a combined
+	/// [`Proof`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of `blinded_elements` is zero or
+	///   exceeds [`u16::MAX`].
+	/// - [`Error::RateLimit`] if the [`RateLimiter`] rejects the request.
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn evaluate<R, L>(
+		&self,
+		rng: &mut R,
+		limiter: &mut L,
+		client_id: &[u8],
+		blinded_elements: &[BlindedElement<Cs>],
+	) -> Result<BatchAllocBlindEvaluateResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+		L: ?Sized + RateLimiter,
+	{
+		if blinded_elements.is_empty() || blinded_elements.len() > u16::MAX.into() {
+			return Err(Error::Batch);
+		}
+
+		if !limiter.allow(client_id, blinded_elements.len()) {
+			return Err(Error::RateLimit);
+		}
+
+		self.server
+			.batch_alloc_blind_evaluate(rng, blinded_elements.iter())
+	}
+}
+
+impl MemoryRateLimiter {
+	/// Creates a [`MemoryRateLimiter`] allowing `limit` content keys per
+	/// client.
+	#[must_use]
+	pub const fn new(limit: usize) -> Self {
+		Self {
+			limit,
+			counts: BTreeMap::new(),
+		}
+	}
+
+	/// Returns the number of content keys allowed per client.
+	#[must_use]
+	pub const fn limit(&self) -> usize {
+		self.limit
+	}
+
+	/// Returns the number of content keys the provided `client_id` can still
+	/// request.
+	#[must_use]
+	pub fn remaining(&self, client_id: &[u8]) -> usize {
+		self.limit
+			.saturating_sub(self.counts.get(client_id).copied().unwrap_or_default())
+	}
+
+	/// Starts a new period by forgetting the requests of all clients.
+	pub fn reset(&mut self) {
+		self.counts.clear();
+	}
+}
+
+impl RateLimiter for MemoryRateLimiter {
+	fn allow(&mut self, client_id: &[u8], count: usize) -> bool {
+		if count > self.remaining(client_id) {
+			return false;
+		}
+
+		let used = self.counts.entry(client_id.to_vec()).or_default();
+		*used = used.saturating_add(count);
+
+		true
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for DuplessClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			clients: self.clients.clone(),
+			content_hashes: self.content_hashes.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for DuplessClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DuplessClient")
+			.field("clients", &self.clients)
+			.field("content_hashes", &self.content_hashes)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for DuplessClient<Cs> {
+	fn drop(&mut self) {
+		for content_hash in &mut self.content_hashes {
+			content_hash.zeroize();
+		}
+	}
+}
+
+impl<Cs: CipherSuite> Eq for DuplessClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for DuplessClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.clients.eq(&other.clients) && self.content_hashes.eq(&other.content_hashes)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for DuplessClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for DuplessServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			server: self.server.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for DuplessServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DuplessServer")
+			.field("server", &self.server)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for DuplessServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for DuplessServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.server.eq(&other.server)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for DuplessServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for DuplessBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DuplessBlindResult")
+			.field("client", &self.client)
+			.field("blinded_elements", &self.blinded_elements)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for DuplessBlindResult<Cs> {}
+
+/// Hashes the `content` of a file into the VOPRF input.
+fn content_hash<Cs: CipherSuite>(content: &[u8]) -> Output<Cs::Hash> {
+	Cs::Hash::default()
+		.chain(CONTENT_LABEL)
+		.chain(&*Cs::ID)
+		.chain(content)
+		.finalize_fixed()
+}
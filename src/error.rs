@@ -45,6 +45,40 @@ pub enum Error<E = Infallible> {
 	InvalidInfoDanger,
 	/// The provided `repr` can't be deserialized into the output type.
 	FromRepr,
+	/// The threshold parameters are invalid or the provided shares are
+	/// insufficient, duplicated or don't combine to a valid result.
+	Threshold,
+	/// The token or token request is invalid or was issued under a different
+	/// key.
+	Token,
+	/// The Privacy Pass token was already redeemed.
+	DoubleSpend,
+	/// The OPAQUE envelope can't be recovered, e.g. because of a wrong
+	/// password.
+	Envelope,
+	/// The MAC of the OPAQUE peer is invalid.
+	Authentication,
+	/// The cuckoo filter parameters are invalid or it has no capacity left.
+	Filter,
+	/// The credential checkup prefix length is zero or larger than 32 bits.
+	Prefix,
+	/// The key set is empty, larger than [`u16::MAX`] or doesn't contain the
+	/// servers [`PublicKey`](crate::key::PublicKey).
+	KeySet,
+	/// The domain separation tag is empty or exceeds a length of 255.
+	Dst,
+	/// The password is wrong, e.g. the recovered PPSS secret doesn't match its
+	/// commitment or the vault tag doesn't match.
+	Password,
+	/// The vault user is unknown, its enrollment wasn't confirmed or the
+	/// enrollment secret doesn't match.
+	Vault,
+	/// The vault user is already enrolled and confirmed.
+	AlreadyEnrolled,
+	/// All vault attempts were used and the key of the user was wiped.
+	AttemptLimit,
+	/// The key server rate limit of the client was exceeded.
+	RateLimit,
 	/// The provided RNG failed.
 	Random(E),
 }
@@ -87,6 +121,20 @@ impl Error {
 			Self::InvalidInfo => Error::InvalidInfo,
 			Self::InvalidInfoDanger => Error::InvalidInfoDanger,
 			Self::FromRepr => Error::FromRepr,
+			Self::Threshold => Error::Threshold,
+			Self::Token => Error::Token,
+			Self::DoubleSpend => Error::DoubleSpend,
+			Self::Envelope => Error::Envelope,
+			Self::Authentication => Error::Authentication,
+			Self::Filter => Error::Filter,
+			Self::Prefix => Error::Prefix,
+			Self::KeySet => Error::KeySet,
+			Self::Dst => Error::Dst,
+			Self::Password => Error::Password,
+			Self::Vault => Error::Vault,
+			Self::AlreadyEnrolled => Error::AlreadyEnrolled,
+			Self::AttemptLimit => Error::AttemptLimit,
+			Self::RateLimit => Error::RateLimit,
 		}
 	}
 }
@@ -110,6 +158,36 @@ impl<E: Display> Display for Error<E> {
 				 know it and it should be replaced"
 			}
 			Self::FromRepr => "the provided `repr` can't be deserialized into the output type",
+			Self::Threshold => {
+				"the threshold parameters are invalid or the provided shares are insufficient, \
+				 duplicated or don't combine to a valid result"
+			}
+			Self::Token => {
+				"the token or token request is invalid or was issued under a different key"
+			}
+			Self::DoubleSpend => "the Privacy Pass token was already redeemed",
+			Self::Envelope => {
+				"the OPAQUE envelope can't be recovered, e.g. because of a wrong password"
+			}
+			Self::Authentication => "the MAC of the OPAQUE peer is invalid",
+			Self::Filter => "the cuckoo filter parameters are invalid or it has no capacity left",
+			Self::Prefix => "the credential checkup prefix length is zero or larger than 32 bits",
+			Self::KeySet => {
+				"the key set is empty, larger than `u16::MAX` or doesn't contain the servers \
+				 `PublicKey`"
+			}
+			Self::Dst => "the domain separation tag is empty or exceeds a length of 255",
+			Self::Password => {
+				"the password is wrong, e.g. the recovered PPSS secret doesn't match its commitment \
+				 or the vault tag doesn't match"
+			}
+			Self::Vault => {
+				"the vault user is unknown, its enrollment wasn't confirmed or the enrollment secret \
+				 doesn't match"
+			}
+			Self::AlreadyEnrolled => "the vault user is already enrolled and confirmed",
+			Self::AttemptLimit => "all vault attempts were used and the key of the user was wiped",
+			Self::RateLimit => "the key server rate limit of the client was exceeded",
 			Self::Random(error) => return error.fmt(formatter),
 		})
 	}
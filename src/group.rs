@@ -195,6 +195,13 @@ pub trait Group {
 		repr: &Array<u8, Self::ScalarLength>,
 	) -> Result<Self::Scalar, InternalError>;
 
+	/// Converts the provided integer to a [`Scalar`](Group::Scalar).
+	///
+	/// This is used to evaluate and interpolate polynomials at participant
+	/// indices, e.g. in [`threshold`](crate::threshold).
+	#[must_use]
+	fn scalar_from_u64(value: u64) -> Self::Scalar;
+
 	/// Returns the identity element.
 	///
 	/// Corresponds to [`Identity()` in RFC 9497 § 2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.1-4.4).
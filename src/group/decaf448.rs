@@ -104,6 +104,10 @@ impl Group for Decaf448 {
 			.ok_or(InternalError)
 	}
 
+	fn scalar_from_u64(value: u64) -> Self::Scalar {
+		Scalar::<Self>::from(value)
+	}
+
 	fn element_identity() -> Self::Element {
 		ProjectivePoint::<Self>::IDENTITY
 	}
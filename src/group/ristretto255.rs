@@ -139,6 +139,10 @@ impl Group for Ristretto255 {
 			.ok_or(InternalError)
 	}
 
+	fn scalar_from_u64(value: u64) -> Self::Scalar {
+		Scalar::from(value)
+	}
+
 	fn element_identity() -> Self::Element {
 		RistrettoPoint::identity()
 	}
@@ -2,31 +2,25 @@
 
 #![expect(non_snake_case, reason = "following the specification exactly")]
 
-#[cfg(feature = "alloc")]
-use alloc::vec;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::fmt::{self, Debug, Formatter};
-use core::iter;
 use core::ops::Deref;
 
 #[cfg(feature = "serde")]
 use ::serde::de::Error as _;
 #[cfg(feature = "serde")]
 use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
-use digest::{FixedOutput, Output, OutputSizeUser, Update};
-use hybrid_array::typenum::Unsigned;
+use digest::{FixedOutput, Output, Update};
 use hybrid_array::{Array, ArrayN, ArraySize, AssocArraySize};
 use rand_core::TryCryptoRng;
 use zeroize::Zeroize;
 
-use crate::cipher_suite::{
-	CipherSuite, Element, ElementLength, NonIdentityElement, NonZeroScalar, Scalar,
-};
-use crate::common::{BlindedElement, Mode, Proof};
+use crate::cipher_suite::{CipherSuite, Element, ElementLength, NonIdentityElement, NonZeroScalar};
+use crate::common::{BlindedElement, Mode};
 use crate::error::{Error, Result};
 use crate::group::{CipherSuiteExt, Group};
-use crate::util::{CollectArray, Concat, I2osp, I2ospLength, UpdateIter};
+use crate::util::{CollectArray, I2ospLength, UpdateIter};
 
 /// Returned by [`batch_blind()`].
 pub(crate) struct BlindResult<Cs: CipherSuite, const N: usize> {
@@ -58,14 +52,6 @@ pub(crate) struct ElementWithRepr<G: Group> {
 	repr: Array<u8, G::ElementLength>,
 }
 
-/// Returned by [`compute_composites()`].
-pub(crate) struct Composites<Cs: CipherSuite> {
-	/// `M`. Might be half the expected value to facilitate batch serialization.
-	M: Element<Cs>,
-	/// `Z`. Might be half the expected value to facilitate batch serialization.
-	Z: Element<Cs>,
-}
-
 impl<'info> Info<'info> {
 	/// Creates a new [`Info`].
 	///
@@ -228,309 +214,6 @@ impl<G: Group> Serialize for ElementWithRepr<G> {
 	}
 }
 
-#[cfg_attr(coverage_nightly, coverage(off))]
-impl<Cs: CipherSuite> Clone for Composites<Cs> {
-	fn clone(&self) -> Self {
-		*self
-	}
-}
-
-impl<Cs: CipherSuite> Copy for Composites<Cs> {}
-
-/// Corresponds to
-/// [`GenerateProof()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-3).
-///
-/// `A` is always the generator element.
-/// `C` and `D` are used to generate [`Composites`].
-///
-/// # Errors
-///
-/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
-///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
-///   are incompatible.
-/// - [`Error::Random`] if the provided `rng` fails.
-pub(crate) fn generate_proof<Cs, R>(
-	mode: Mode,
-	rng: &mut R,
-	k: NonZeroScalar<Cs>,
-	composites: Composites<Cs>,
-	B: &ElementWithRepr<Cs::Group>,
-) -> Result<Proof<Cs>, Error<R::Error>>
-where
-	Cs: CipherSuite,
-	R: ?Sized + TryCryptoRng,
-{
-	let Composites::<Cs> { M, Z } = composites;
-
-	let r = Cs::Group::scalar_random(rng).map_err(Error::Random)?.into();
-	// `A` is always the generator element.
-	let t2 = Cs::Group::scalar_mul_by_generator(&Cs::Group::scalar_maybe_halve(&r));
-	let t3 = r * &M;
-
-	let c = compute_c::<Cs>(mode, B, M, Z, t2, t3).map_err(Error::into_random::<R>)?;
-	let s = r - &(c * k.deref());
-
-	Ok(Proof { c, s })
-}
-
-/// Corresponds to
-/// [`VerifyProof()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-2).
-///
-/// # Errors
-///
-/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
-///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
-///   are incompatible.
-/// - [`Error::Proof`] if the [`Proof`] is invalid.
-pub(crate) fn verify_proof<Cs>(
-	mode: Mode,
-	composites: Composites<Cs>,
-	B: &ElementWithRepr<Cs::Group>,
-	proof: &Proof<Cs>,
-) -> Result<()>
-where
-	Cs: CipherSuite,
-{
-	let Composites::<Cs> { M, Z } = composites;
-	let Proof { c, s } = proof;
-
-	let t2 = Cs::Group::lincomb(&[
-		(
-			Cs::Group::element_generator(),
-			Cs::Group::scalar_maybe_halve(s),
-		),
-		(B.element.into(), Cs::Group::scalar_maybe_halve(c)),
-	]);
-	let t3 = Cs::Group::lincomb(&[(M, *s), (Z, *c)]);
-
-	let expected_c = compute_c::<Cs>(mode, B, M, Z, t2, t3)?;
-
-	if &expected_c == c {
-		Ok(())
-	} else {
-		Err(Error::Proof)
-	}
-}
-
-/// Shared code between
-/// [`GenerateProof()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-3)
-/// and
-/// [`VerifyProof()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-2).
-///
-/// The provided [`Element`]s may be halved for the purpose of batch
-/// serialization.
-///
-/// # Errors
-///
-/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
-/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
-/// are incompatible.
-fn compute_c<Cs: CipherSuite>(
-	mode: Mode,
-	B: &ElementWithRepr<Cs::Group>,
-	M: Element<Cs>,
-	Z: Element<Cs>,
-	t2: Element<Cs>,
-	t3: Element<Cs>,
-) -> Result<Scalar<Cs>> {
-	let Bm = &B.repr;
-	let [a0, a1, a2, a3] = Cs::Group::element_batch_maybe_double_to_repr(&[M, Z, t2, t3]);
-
-	Cs::hash_to_scalar(
-		mode,
-		&[
-			&Cs::I2OSP_ELEMENT_LEN,
-			Bm,
-			&Cs::I2OSP_ELEMENT_LEN,
-			&a0,
-			&Cs::I2OSP_ELEMENT_LEN,
-			&a1,
-			&Cs::I2OSP_ELEMENT_LEN,
-			&a2,
-			&Cs::I2OSP_ELEMENT_LEN,
-			&a3,
-			b"Challenge",
-		],
-		None,
-	)
-}
-
-/// Corresponds to
-/// [`ComputeComposites()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-5)
-/// and
-/// [`ComputeCompositesFast()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-4).
-///
-/// # Errors
-///
-/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
-/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
-/// are incompatible.
-pub(crate) fn compute_composites<'items, Cs, const N: usize>(
-	mode: Mode,
-	k: Option<NonZeroScalar<Cs>>,
-	B: &ElementWithRepr<Cs::Group>,
-	C: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
-	D: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
-) -> Result<Composites<Cs>>
-where
-	Cs: CipherSuite,
-{
-	debug_assert_ne!(N, 0, "found zero item length");
-	debug_assert_eq!(N, C.len(), "found unequal item length");
-	debug_assert_eq!(N, D.len(), "found unequal item length");
-	debug_assert!(N <= u16::MAX.into(), "found overflowing item length");
-
-	let mut Ms = [(Element::<Cs>::default(), Scalar::<Cs>::default()); N];
-	let mut Zs = k
-		.is_none()
-		.then(|| [(Element::<Cs>::default(), Scalar::<Cs>::default()); N]);
-
-	internal_compute_composites::<Cs>(
-		mode,
-		N,
-		B,
-		C,
-		D,
-		&mut Ms,
-		Zs.as_mut().map(<[_; N]>::as_mut_slice),
-	)?;
-
-	// We skip the initial addition to the identity point, which is a no-op.
-	let M = Cs::Group::lincomb(&Ms);
-	let Z = k.map_or_else(
-		|| Cs::Group::lincomb(&Zs.expect("`Zs` must be present if `k` is not")),
-		|k| k.into() * &M,
-	);
-
-	Ok(Composites { M, Z })
-}
-
-/// Corresponds to
-/// [`ComputeComposites()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-5)
-/// and
-/// [`ComputeCompositesFast()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-4).
-///
-/// # Errors
-///
-/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
-/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
-/// are incompatible.
-#[cfg(feature = "alloc")]
-pub(crate) fn alloc_compute_composites<'items, Cs>(
-	mode: Mode,
-	length: usize,
-	k: Option<NonZeroScalar<Cs>>,
-	B: &ElementWithRepr<Cs::Group>,
-	C: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
-	D: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
-) -> Result<Composites<Cs>>
-where
-	Cs: CipherSuite,
-{
-	debug_assert_ne!(length, 0, "found zero item length");
-	debug_assert_eq!(length, C.len(), "found unequal item length");
-	debug_assert_eq!(length, D.len(), "found unequal item length");
-	debug_assert!(length <= u16::MAX.into(), "found overflowing item length");
-
-	let mut Ms = vec![(Element::<Cs>::default(), Scalar::<Cs>::default()); length];
-	let mut Zs = k
-		.is_none()
-		.then(|| vec![(Element::<Cs>::default(), Scalar::<Cs>::default()); length]);
-
-	internal_compute_composites::<Cs>(mode, length, B, C, D, &mut Ms, Zs.as_deref_mut())?;
-
-	let M = Cs::Group::alloc_lincomb(&Ms);
-	let Z = k.map_or_else(
-		|| Cs::Group::alloc_lincomb(&Zs.expect("`Zs` must be present if `k` is not")),
-		|k| k.into() * &M,
-	);
-
-	Ok(Composites { M, Z })
-}
-
-/// Corresponds to
-/// [`ComputeComposites()` in RFC 9497 § 2.2.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.1-5)
-/// and
-/// [`ComputeCompositesFast()` in RFC 9497 § 2.2.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-2.2.2-4).
-///
-/// # Errors
-///
-/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
-/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
-/// are incompatible.
-fn internal_compute_composites<'items, Cs>(
-	mode: Mode,
-	length: usize,
-	B: &ElementWithRepr<Cs::Group>,
-	C: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
-	D: impl ExactSizeIterator<Item = &'items ElementWithRepr<Cs::Group>>,
-	Ms: &mut [(Element<Cs>, Scalar<Cs>)],
-	Zs: Option<&mut [(Element<Cs>, Scalar<Cs>)]>,
-) -> Result<()>
-where
-	Cs: CipherSuite,
-{
-	debug_assert_ne!(length, 0, "found zero item length");
-	debug_assert_eq!(length, C.len(), "found unequal item length");
-	debug_assert_eq!(length, D.len(), "found unequal item length");
-	debug_assert_eq!(length, Ms.len(), "found unequal item length");
-
-	#[expect(
-		clippy::debug_assert_with_mut_call,
-		reason = "`len()` must not have side-effects"
-	)]
-	if let Some(Zs) = &Zs {
-		debug_assert_eq!(length, Zs.len(), "found unequal item length");
-	}
-
-	debug_assert!(length <= u16::MAX.into(), "found overflowing item length");
-
-	let Bm = &B.repr;
-	let seed_dst = [b"Seed-".as_slice()].concat(create_context_string::<Cs>(mode));
-	let seed = Cs::Hash::default()
-		.chain(Cs::I2OSP_ELEMENT_LEN)
-		.chain(Bm)
-		.chain(seed_dst.i2osp_length().expect("`Cs::Id` too long"))
-		.chain_iter(seed_dst.into_iter())
-		.finalize_fixed();
-
-	for (i, ((Ci, M), (Di, Z))) in (0..=u16::MAX).zip(
-		(C.zip(Ms)).zip(
-			D.zip(
-				Zs.into_iter()
-					.flatten()
-					.map(Some)
-					.chain(iter::repeat_with(|| None)),
-			),
-		),
-	) {
-		let mut di = Cs::hash_to_scalar(
-			mode,
-			&[
-				&<Cs::Hash as OutputSizeUser>::OutputSize::U16.i2osp(),
-				&seed,
-				&i.i2osp(),
-				&Cs::I2OSP_ELEMENT_LEN,
-				&Ci.repr,
-				&Cs::I2OSP_ELEMENT_LEN,
-				&Di.repr,
-				b"Composite",
-			],
-			None,
-		)?;
-
-		di = Cs::Group::scalar_maybe_halve(&di);
-
-		*M = (Ci.element.into(), di);
-
-		if let Some(Z) = Z {
-			*Z = (Di.element.into(), di);
-		}
-	}
-
-	Ok(())
-}
-
 /// Corresponds to
 /// [`CreateContextString()` in RFC 9497 § 3.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-3.1-5).
 pub(crate) fn create_context_string<Cs: CipherSuite>(mode: Mode) -> [&'static [u8]; 4] {
@@ -641,7 +324,44 @@ where
 	})
 }
 
-/// Corresponds to
+/// Unblinds the provided `evaluation_elements`. Corresponds to the first part
+/// of [`Finalize()` in RFC 9497 § 3.3.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-3.3.1-7).
+#[expect(single_use_lifetimes, reason = "false-positive")]
+pub(crate) fn batch_finalize_element<'evaluation_elements, Cs, const N: usize>(
+	blinds: [NonZeroScalar<Cs>; N],
+	evaluation_elements: impl ExactSizeIterator<Item = &'evaluation_elements NonIdentityElement<Cs>>,
+) -> [ElementWithRepr<Cs::Group>; N]
+where
+	Cs: CipherSuite,
+{
+	debug_assert_eq!(N, evaluation_elements.len(), "found unequal item length");
+
+	let inverted_blinds = Cs::Group::scalar_batch_invert(blinds);
+	ElementWithRepr::new_batch(evaluation_elements.copied().zip(inverted_blinds))
+}
+
+/// Unblinds the provided `evaluation_elements`. Corresponds to the first part
+/// of [`Finalize()` in RFC 9497 § 3.3.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-3.3.1-7).
+#[cfg(feature = "alloc")]
+#[expect(single_use_lifetimes, reason = "false-positive")]
+pub(crate) fn batch_alloc_finalize_element<'evaluation_elements, Cs>(
+	blinds: Vec<NonZeroScalar<Cs>>,
+	evaluation_elements: impl ExactSizeIterator<Item = &'evaluation_elements NonIdentityElement<Cs>>,
+) -> Vec<ElementWithRepr<Cs::Group>>
+where
+	Cs: CipherSuite,
+{
+	debug_assert_eq!(
+		blinds.len(),
+		evaluation_elements.len(),
+		"found unequal item length"
+	);
+
+	let inverted_blinds = Cs::Group::scalar_batch_alloc_invert(blinds);
+	ElementWithRepr::new_batch_alloc(evaluation_elements.copied().zip(inverted_blinds))
+}
+
+/// Hashes the provided `unblinded_elements`. Corresponds to the last part of
 /// [`Finalize()` in RFC 9497 § 3.3.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-3.3.1-7).
 ///
 /// # Errors
@@ -649,10 +369,9 @@ where
 /// Returns [`Error::InputLength`] if a provided input exceeds a length of
 /// [`u16::MAX`].
 #[expect(single_use_lifetimes, reason = "false-positive")]
-pub(crate) fn batch_finalize<'evaluation_elements, Cs, const N: usize>(
+pub(crate) fn batch_finalize<'unblinded_elements, Cs, const N: usize>(
 	inputs: &[&[&[u8]]; N],
-	blinds: [NonZeroScalar<Cs>; N],
-	evaluation_elements: impl ExactSizeIterator<Item = &'evaluation_elements NonIdentityElement<Cs>>,
+	unblinded_elements: impl ExactSizeIterator<Item = &'unblinded_elements ElementWithRepr<Cs::Group>>,
 	info: Option<Info<'_>>,
 ) -> Result<[Output<Cs::Hash>; N]>
 where
@@ -660,19 +379,7 @@ where
 		AssocArraySize<Size: ArraySize<ArrayType<Output<Cs::Hash>> = [Output<Cs::Hash>; N]>>,
 	Cs: CipherSuite,
 {
-	debug_assert_eq!(N, evaluation_elements.len(), "found unequal item length");
-
-	let inverted_blinds = Cs::Group::scalar_batch_invert(blinds);
-	let n = inverted_blinds
-		.into_iter()
-		.zip(evaluation_elements)
-		.map(|(inverted_blind, evaluation_element)| {
-			maybe_halve::<Cs::Group>(&inverted_blind, N) * evaluation_element.deref()
-		})
-		.collect_array::<N>();
-	let unblinded_elements = batch_maybe_double_to_repr::<Cs::Group, N>(&n);
-
-	let mut outputs = internal_finalize::<Cs>(inputs.iter().copied(), &unblinded_elements, info);
+	let mut outputs = internal_finalize::<Cs>(inputs.iter().copied(), unblinded_elements, info);
 	// Using `Iterator::collect()` can panic!
 	let outputs = ArrayN::<_, N>::try_from_fn(|_| {
 		outputs
@@ -683,7 +390,7 @@ where
 	Ok(outputs.0)
 }
 
-/// Corresponds to
+/// Hashes the provided `unblinded_elements`. Corresponds to the last part of
 /// [`Finalize()` in RFC 9497 § 3.3.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-3.3.1-7).
 ///
 /// # Errors
@@ -692,35 +399,15 @@ where
 /// [`u16::MAX`].
 #[cfg(feature = "alloc")]
 #[expect(single_use_lifetimes, reason = "false-positive")]
-pub(crate) fn batch_alloc_finalize<'inputs, 'evaluation_elements, Cs>(
-	length: usize,
+pub(crate) fn batch_alloc_finalize<'inputs, 'unblinded_elements, Cs>(
 	inputs: impl ExactSizeIterator<Item = &'inputs [&'inputs [u8]]>,
-	blinds: Vec<NonZeroScalar<Cs>>,
-	evaluation_elements: impl ExactSizeIterator<Item = &'evaluation_elements NonIdentityElement<Cs>>,
+	unblinded_elements: impl ExactSizeIterator<Item = &'unblinded_elements ElementWithRepr<Cs::Group>>,
 	info: Option<Info<'_>>,
 ) -> Result<Vec<Output<Cs::Hash>>>
 where
 	Cs: CipherSuite,
 {
-	debug_assert_eq!(length, inputs.len(), "found unequal item length");
-	debug_assert_eq!(length, blinds.len(), "found unequal item length");
-	debug_assert_eq!(
-		length,
-		evaluation_elements.len(),
-		"found unequal item length"
-	);
-
-	let inverted_blinds = Cs::Group::scalar_batch_alloc_invert(blinds);
-	let n: Vec<_> = inverted_blinds
-		.into_iter()
-		.zip(evaluation_elements)
-		.map(|(inverted_blind, evaluation_element)| {
-			non_zero_maybe_halve::<Cs::Group>(&inverted_blind, length) * evaluation_element
-		})
-		.collect();
-	let unblinded_elements = non_identity_batch_alloc_maybe_double_to_repr::<Cs::Group>(&n);
-
-	internal_finalize::<Cs>(inputs, &unblinded_elements, info).collect()
+	internal_finalize::<Cs>(inputs, unblinded_elements, info).collect()
 }
 
 /// Corresponds to
@@ -730,9 +417,10 @@ where
 ///
 /// Returns [`Error::InputLength`] if a provided input exceeds a length of
 /// [`u16::MAX`].
-fn internal_finalize<'inputs, Cs: CipherSuite>(
+#[expect(single_use_lifetimes, reason = "false-positive")]
+fn internal_finalize<'inputs, 'unblinded_elements, Cs: CipherSuite>(
 	inputs: impl ExactSizeIterator<Item = &'inputs [&'inputs [u8]]>,
-	unblinded_elements: &[Array<u8, ElementLength<Cs>>],
+	unblinded_elements: impl ExactSizeIterator<Item = &'unblinded_elements ElementWithRepr<Cs::Group>>,
 	info: Option<Info<'_>>,
 ) -> impl Iterator<Item = Result<Output<Cs::Hash>>> {
 	debug_assert_eq!(
@@ -755,7 +443,7 @@ fn internal_finalize<'inputs, Cs: CipherSuite>(
 
 			Ok(hash
 				.chain(Cs::I2OSP_ELEMENT_LEN)
-				.chain(unblinded_element)
+				.chain(unblinded_element.as_repr())
 				.chain(b"Finalize")
 				.finalize_fixed())
 		})
@@ -872,6 +560,20 @@ fn internal_evaluate<Cs: CipherSuite>(
 		})
 }
 
+/// Multiplies two [`NonZeroScalar`](Group::NonZeroScalar)s.
+pub(crate) fn non_zero_scalar_mul<G: Group>(
+	left: G::NonZeroScalar,
+	right: G::NonZeroScalar,
+) -> G::NonZeroScalar {
+	let left: G::Scalar = left.into();
+
+	let Ok(product) = (left * &*right).try_into() else {
+		unreachable!("the product of non-zero scalars is non-zero")
+	};
+
+	product
+}
+
 /// Only redirects to [`Group::non_zero_scalar_maybe_halve()`] if we intend to
 /// serialize multiple scalars.
 fn non_zero_maybe_halve<G: Group>(scalar: &G::NonZeroScalar, length: usize) -> G::NonZeroScalar {
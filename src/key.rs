@@ -1,16 +1,20 @@
 //! [`KeyPair`] and corresponding types.
 
 use core::fmt::{self, Debug, Formatter};
+use core::ops::Deref;
 use core::slice;
 
+#[cfg(feature = "serde")]
+use ::serde::ser::SerializeStruct;
 #[cfg(feature = "serde")]
 use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
 use hybrid_array::Array;
+use hybrid_array::typenum::Sum;
 use rand_core::TryCryptoRng;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::cipher_suite::CipherSuite;
-use crate::common::Mode;
+use crate::cipher_suite::{CipherSuite, Element, Scalar, ScalarLength};
+use crate::common::{Mode, Proof};
 use crate::error::{Error, Result};
 use crate::group::{CipherSuiteExt, Group};
 use crate::internal::ElementWithRepr;
@@ -18,6 +22,9 @@ use crate::internal::ElementWithRepr;
 use crate::serde;
 use crate::util::{Concat, I2ospLength};
 
+/// DST prefix of a [`PossessionProof`], followed by the context string.
+const POSSESSION_DST: &[u8] = b"PossessionProof-";
+
 /// Holds a [`SecretKey`] and its [`PublicKey`].
 pub struct KeyPair<G: Group> {
 	/// [`SecretKey`].
@@ -87,6 +94,40 @@ impl<G: Group> KeyPair<G> {
 		SecretKey::from_repr(repr).map(Self::from_secret_key)
 	}
 
+	/// Proves knowledge of the [`SecretKey`] corresponding to this
+	/// [`KeyPair`]s [`PublicKey`], e.g. before registering it in a key
+	/// directory.
+	///
+	/// The `context` is bound to the [`PossessionProof`] and has to be passed
+	/// to [`PublicKey::verify_possession()`] unchanged.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InfoLength`] if `context` exceeds a length of [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn prove_possession<Cs, R>(
+		&self,
+		rng: &mut R,
+		mode: Mode,
+		context: &[u8],
+	) -> Result<PossessionProof<Cs>, Error<R::Error>>
+	where
+		Cs: CipherSuite<Group = G>,
+		R: ?Sized + TryCryptoRng,
+	{
+		let r = G::scalar_random(rng).map_err(Error::Random)?.into();
+		let t = G::scalar_mul_by_generator(&G::scalar_maybe_halve(&r));
+
+		let c = possession_c::<Cs>(mode, &self.public_key, t, context)
+			.map_err(Error::into_random::<R>)?;
+		let s = r - &(c * self.secret_key.0.deref());
+
+		Ok(PossessionProof { c, s })
+	}
+
 	/// Returns the [`SecretKey`].
 	#[must_use]
 	pub const fn secret_key(&self) -> &SecretKey<G> {
@@ -266,6 +307,66 @@ impl<G: Group> PublicKey<G> {
 	pub const fn as_repr(&self) -> &Array<u8, G::ElementLength> {
 		self.0.as_repr()
 	}
+
+	/// Verifies that the holder of this [`PublicKey`] knows the corresponding
+	/// [`SecretKey`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::InfoLength`] if `context` exceeds a length of [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Proof`] if the [`PossessionProof`] is invalid.
+	pub fn verify_possession<Cs: CipherSuite<Group = G>>(
+		&self,
+		mode: Mode,
+		proof: &PossessionProof<Cs>,
+		context: &[u8],
+	) -> Result<()> {
+		let PossessionProof { c, s } = proof;
+
+		let t = G::lincomb(&[
+			(G::element_generator(), G::scalar_maybe_halve(s)),
+			((*self.as_element()).into(), G::scalar_maybe_halve(c)),
+		]);
+		let expected_c = possession_c::<Cs>(mode, self, t, context)?;
+
+		if &expected_c == c {
+			Ok(())
+		} else {
+			Err(Error::Proof)
+		}
+	}
+}
+
+/// Returned by [`KeyPair::prove_possession()`]. Verified by
+/// [`PublicKey::verify_possession()`].
+pub struct PossessionProof<Cs: CipherSuite> {
+	/// `c`.
+	c: Scalar<Cs>,
+	/// `s`.
+	s: Scalar<Cs>,
+}
+
+impl<Cs: CipherSuite> PossessionProof<Cs> {
+	/// Serializes this [`PossessionProof`].
+	#[must_use]
+	pub fn to_repr(&self) -> Array<u8, Sum<ScalarLength<Cs>, ScalarLength<Cs>>> {
+		Cs::Group::scalar_to_repr(&self.c).concat(Cs::Group::scalar_to_repr(&self.s))
+	}
+
+	/// Deserializes the provided `repr` to a [`PossessionProof`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		Proof::<Cs>::from_repr(repr).map(|proof| Self {
+			c: proof.c,
+			s: proof.s,
+		})
+	}
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -430,3 +531,105 @@ impl<G: Group> Serialize for PublicKey<G> {
 }
 
 impl<G: Group> ZeroizeOnDrop for PublicKey<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PossessionProof<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			c: self.c,
+			s: self.s,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PossessionProof<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PossessionProof")
+			.field("c", &self.c)
+			.field("s", &self.s)
+			.finish()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Cs> Deserialize<'de> for PossessionProof<Cs>
+where
+	Cs: CipherSuite,
+	Scalar<Cs>: Deserialize<'de>,
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		serde::struct_2(deserializer, "PossessionProof", &["c", "s"]).map(|(c, s)| Self { c, s })
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for PossessionProof<Cs> {
+	fn drop(&mut self) {
+		self.c.zeroize();
+		self.s.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PossessionProof<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PossessionProof<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.c.eq(&other.c) && self.s.eq(&other.s)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<Cs> Serialize for PossessionProof<Cs>
+where
+	Cs: CipherSuite,
+	Scalar<Cs>: Serialize,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut state = serializer.serialize_struct("PossessionProof", 2)?;
+		state.serialize_field("c", &self.c)?;
+		state.serialize_field("s", &self.s)?;
+		state.end()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PossessionProof<Cs> {}
+
+/// Computes the challenge of a [`PossessionProof`] over the [`PublicKey`], the
+/// commitment `t` and the `context`.
+///
+/// # Errors
+///
+/// - [`Error::InfoLength`] if `context` exceeds a length of [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+fn possession_c<Cs: CipherSuite>(
+	mode: Mode,
+	public_key: &PublicKey<Cs::Group>,
+	t: Element<Cs>,
+	context: &[u8],
+) -> Result<Scalar<Cs>> {
+	let [t] = Cs::Group::element_batch_maybe_double_to_repr(&[t]);
+
+	Cs::hash_to_scalar(
+		mode,
+		&[
+			&Cs::I2OSP_ELEMENT_LEN,
+			public_key.as_repr(),
+			&Cs::I2OSP_ELEMENT_LEN,
+			&t,
+			&context.i2osp_length().ok_or(Error::InfoLength)?,
+			context,
+			b"Challenge",
+		],
+		Some(POSSESSION_DST),
+	)
+}
@@ -11,20 +11,43 @@ extern crate alloc;
 #[cfg(test)]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+pub mod anonymous;
+#[cfg(feature = "alloc")]
+pub mod checkup;
 pub mod cipher_suite;
 pub mod common;
+pub mod dleq;
+#[cfg(feature = "alloc")]
+pub mod dupless;
 pub mod error;
 pub mod group;
 mod internal;
 pub mod key;
+#[cfg(feature = "opaque")]
+pub mod opaque;
 pub mod oprf;
 pub mod poprf;
+#[cfg(feature = "alloc")]
+pub mod ppss;
+#[cfg(feature = "privacy-pass")]
+pub mod privacy_pass;
+pub mod private_metadata;
+#[cfg(feature = "alloc")]
+pub mod psi;
+pub mod pythia;
+pub mod relay;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "alloc")]
+pub mod threshold;
+pub mod updatable;
 mod util;
+#[cfg(feature = "vault")]
+pub mod vault;
 pub mod voprf;
 
-pub use common::{BlindedElement, EvaluationElement, Proof};
+pub use common::{BlindedElement, EvaluationElement, Proof, UnblindedElement};
 #[cfg(feature = "decaf448")]
 pub use ed448_goldilocks::Decaf448;
 pub use error::{Error, Result};
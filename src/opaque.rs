@@ -0,0 +1,1793 @@
+//! [OPAQUE](https://www.rfc-editor.org/rfc/rfc9807.html) augmented PAKE.
+//!
+//! Implements OPAQUE-3DH on top of [`OprfClient`] and [`OprfServer`]. The
+//! [`Group`] of the OPRF [`CipherSuite`] is used for the 3DH key exchange and its
+//! [`Hash`](CipherSuite::Hash) for HKDF, HMAC and the transcript hash.
+//!
+//! # Registration
+//!
+//! 1. The client starts with [`ClientRegistration::start()`] and sends the
+//!    [`RegistrationRequest`] to the server.
+//! 2. The server responds with [`OpaqueServer::registration()`] and sends the
+//!    [`RegistrationResponse`] back to the client.
+//! 3. The client completes the registration with
+//!    [`ClientRegistration::finish()`] and sends the [`RegistrationRecord`] to
+//!    the server, which stores it under the credential identifier.
+//!
+//! # Login
+//!
+//! 1. The client starts with [`ClientLogin::start()`] and sends the [`Ke1`] to
+//!    the server.
+//! 2. The server responds with [`OpaqueServer::login()`] and sends the [`Ke2`]
+//!    back to the client.
+//! 3. The client authenticates the server with [`ClientLogin::finish()`] and
+//!    sends the [`Ke3`] to the server.
+//! 4. The server authenticates the client with [`ServerLogin::finish()`].
+//!
+//! To prevent client enumeration, servers should run [`OpaqueServer::login()`]
+//! with a [`RegistrationRecord::fake()`] for unknown credential identifiers.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter;
+
+use digest::block_api::BlockSizeUser;
+use digest::{FixedOutput, HashMarker, KeyInit, Mac, Output, OutputSizeUser, Update};
+use hkdf::{SimpleHkdf, SimpleHkdfExtract};
+use hmac::SimpleHmac;
+use hybrid_array::Array;
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::{CipherSuite, ElementLength};
+use crate::common::{BlindedElement, EvaluationElement, Mode};
+use crate::error::{Error, Result};
+use crate::group::Group;
+use crate::key::{KeyPair, PublicKey, SecretKey};
+use crate::oprf::{OprfBlindResult, OprfClient, OprfServer};
+use crate::util::{I2osp, I2ospLength};
+
+/// `Nn`: length of nonces.
+const NONCE_LENGTH: usize = 32;
+/// `Nseed`: length of seeds.
+const SEED_LENGTH: usize = 32;
+
+/// Typedef to the [`Hash`](CipherSuite::Hash) of the OPRF [`CipherSuite`].
+type Hash<Cs> = <<Cs as OpaqueCipherSuite>::Oprf as CipherSuite>::Hash;
+/// Typedef to the [`Group`] of the OPRF [`CipherSuite`].
+type OpaqueGroup<Cs> = <<Cs as OpaqueCipherSuite>::Oprf as CipherSuite>::Group;
+
+/// OPAQUE configuration.
+///
+/// See [RFC 9807 § 7](https://www.rfc-editor.org/rfc/rfc9807.html#section-7).
+pub trait OpaqueCipherSuite: 'static {
+	/// The OPRF [`CipherSuite`]. Its [`Group`] is also used for the key
+	/// exchange and its [`Hash`](CipherSuite::Hash) for the KDF, MAC and hash.
+	///
+	/// [`KeyPair::derive()`] takes a 32 byte seed, so this is only
+	/// interoperable with RFC 9807 for [`Group`]s with 32 byte scalars.
+	type Oprf: CipherSuite<Hash: BlockSizeUser + Clone + HashMarker>;
+
+	/// The key stretching function.
+	type Ksf: Ksf;
+}
+
+/// Key stretching function.
+///
+/// See [RFC 9807 § 4](https://www.rfc-editor.org/rfc/rfc9807.html#section-4-2.5.1).
+pub trait Ksf {
+	/// Stretches `input` into `output` of the same length.
+	fn stretch(input: &[u8], output: &mut [u8]);
+}
+
+/// The identity [`Ksf`], which doesn't stretch its input.
+///
+/// # ⚠️ Warning
+///
+/// This offers no protection against offline dictionary attacks. It is only
+/// meant for testing and for passwords with high entropy.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IdentityKsf;
+
+/// Optional identities of the client and server. Default to their public keys.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Identifiers<'identifiers> {
+	/// The client identity.
+	pub client: Option<&'identifiers [u8]>,
+	/// The server identity.
+	pub server: Option<&'identifiers [u8]>,
+}
+
+/// OPAQUE server.
+pub struct OpaqueServer<Cs: OpaqueCipherSuite> {
+	/// The servers long-term [`KeyPair`].
+	key_pair: KeyPair<OpaqueGroup<Cs>>,
+	/// `oprf_seed` to derive per-credential OPRF keys from.
+	oprf_seed: Output<Hash<Cs>>,
+}
+
+/// Server state during login.
+pub struct ServerLogin<Cs: OpaqueCipherSuite> {
+	/// `expected_client_mac`.
+	expected_client_mac: Output<Hash<Cs>>,
+	/// `session_key`.
+	session_key: Output<Hash<Cs>>,
+}
+
+/// Returned from [`OpaqueServer::login()`].
+pub struct ServerLoginResult<Cs: OpaqueCipherSuite> {
+	/// The [`ServerLogin`] to be used with [`ServerLogin::finish()`].
+	pub server: ServerLogin<Cs>,
+	/// The [`Ke2`] to be sent to the client.
+	pub ke2: Ke2<Cs>,
+}
+
+/// Client state during registration.
+pub struct ClientRegistration<Cs: OpaqueCipherSuite> {
+	/// The underlying [`OprfClient`].
+	client: OprfClient<Cs::Oprf>,
+}
+
+/// Returned from [`ClientRegistration::start()`].
+pub struct ClientRegistrationStartResult<Cs: OpaqueCipherSuite> {
+	/// The [`ClientRegistration`] to be used with
+	/// [`ClientRegistration::finish()`].
+	pub client: ClientRegistration<Cs>,
+	/// The [`RegistrationRequest`] to be sent to the server.
+	pub registration_request: RegistrationRequest<Cs>,
+}
+
+/// Returned from [`ClientRegistration::finish()`].
+pub struct ClientRegistrationFinishResult<Cs: OpaqueCipherSuite> {
+	/// The [`RegistrationRecord`] to be sent to the server.
+	pub registration_record: RegistrationRecord<Cs>,
+	/// `export_key`.
+	pub export_key: Output<Hash<Cs>>,
+}
+
+/// Client state during login.
+pub struct ClientLogin<Cs: OpaqueCipherSuite> {
+	/// The underlying [`OprfClient`].
+	client: OprfClient<Cs::Oprf>,
+	/// `client_secret`.
+	client_secret: SecretKey<OpaqueGroup<Cs>>,
+	/// The sent [`Ke1`].
+	ke1: Ke1<Cs>,
+}
+
+/// Returned from [`ClientLogin::start()`].
+pub struct ClientLoginStartResult<Cs: OpaqueCipherSuite> {
+	/// The [`ClientLogin`] to be used with [`ClientLogin::finish()`].
+	pub client: ClientLogin<Cs>,
+	/// The [`Ke1`] to be sent to the server.
+	pub ke1: Ke1<Cs>,
+}
+
+/// Returned from [`ClientLogin::finish()`].
+pub struct ClientLoginFinishResult<Cs: OpaqueCipherSuite> {
+	/// The [`Ke3`] to be sent to the server.
+	pub ke3: Ke3<Cs>,
+	/// `session_key`.
+	pub session_key: Output<Hash<Cs>>,
+	/// `export_key`.
+	pub export_key: Output<Hash<Cs>>,
+	/// The servers [`PublicKey`] recovered from the envelope.
+	pub server_public_key: PublicKey<OpaqueGroup<Cs>>,
+}
+
+/// Sent from the client to the server to start the registration.
+///
+/// Corresponds to
+/// [`RegistrationRequest` in RFC 9807 § 5.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-5.1).
+pub struct RegistrationRequest<Cs: OpaqueCipherSuite> {
+	/// `blinded_message`.
+	blinded_element: BlindedElement<Cs::Oprf>,
+}
+
+/// Sent from the server to the client in response to a
+/// [`RegistrationRequest`].
+///
+/// Corresponds to
+/// [`RegistrationResponse` in RFC 9807 § 5.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-5.1).
+pub struct RegistrationResponse<Cs: OpaqueCipherSuite> {
+	/// `evaluated_message`.
+	evaluation_element: EvaluationElement<Cs::Oprf>,
+	/// `server_public_key`.
+	server_public_key: PublicKey<OpaqueGroup<Cs>>,
+}
+
+/// Sent from the client to the server to complete the registration. Stored by
+/// the server under the credential identifier.
+///
+/// Corresponds to
+/// [`RegistrationRecord` in RFC 9807 § 5.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-5.1).
+pub struct RegistrationRecord<Cs: OpaqueCipherSuite> {
+	/// `client_public_key`.
+	client_public_key: PublicKey<OpaqueGroup<Cs>>,
+	/// `masking_key`.
+	masking_key: Output<Hash<Cs>>,
+	/// `envelope.envelope_nonce`.
+	envelope_nonce: [u8; NONCE_LENGTH],
+	/// `envelope.auth_tag`.
+	auth_tag: Output<Hash<Cs>>,
+}
+
+/// First login message, sent from the client to the server.
+///
+/// Corresponds to
+/// [`KE1` in RFC 9807 § 6.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.1).
+pub struct Ke1<Cs: OpaqueCipherSuite> {
+	/// `credential_request.blinded_message`.
+	blinded_element: BlindedElement<Cs::Oprf>,
+	/// `auth_request.client_nonce`.
+	client_nonce: [u8; NONCE_LENGTH],
+	/// `auth_request.client_public_keyshare`.
+	client_public_keyshare: PublicKey<OpaqueGroup<Cs>>,
+}
+
+/// Second login message, sent from the server to the client.
+///
+/// Corresponds to
+/// [`KE2` in RFC 9807 § 6.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.1).
+pub struct Ke2<Cs: OpaqueCipherSuite> {
+	/// `credential_response.evaluated_message`.
+	evaluation_element: EvaluationElement<Cs::Oprf>,
+	/// `credential_response.masking_nonce`.
+	masking_nonce: [u8; NONCE_LENGTH],
+	/// `credential_response.masked_response`.
+	masked_response: Vec<u8>,
+	/// `auth_response.server_nonce`.
+	server_nonce: [u8; NONCE_LENGTH],
+	/// `auth_response.server_public_keyshare`.
+	server_public_keyshare: PublicKey<OpaqueGroup<Cs>>,
+	/// `auth_response.server_mac`.
+	server_mac: Output<Hash<Cs>>,
+}
+
+/// Third login message, sent from the client to the server.
+///
+/// Corresponds to
+/// [`KE3` in RFC 9807 § 6.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.1).
+pub struct Ke3<Cs: OpaqueCipherSuite> {
+	/// `client_mac`.
+	client_mac: Output<Hash<Cs>>,
+}
+
+/// `CleartextCredentials` as per
+/// [RFC 9807 § 4](https://www.rfc-editor.org/rfc/rfc9807.html#section-4-4).
+struct CleartextCredentials<'credentials> {
+	/// `server_public_key`.
+	server_public_key: &'credentials [u8],
+	/// `server_identity`.
+	server_identity: &'credentials [u8],
+	/// Length of `server_identity`.
+	server_identity_length: [u8; 2],
+	/// `client_identity`.
+	client_identity: &'credentials [u8],
+	/// Length of `client_identity`.
+	client_identity_length: [u8; 2],
+}
+
+/// Keys derived from the `randomized_password` and `envelope_nonce`.
+struct EnvelopeKeys<Cs: OpaqueCipherSuite> {
+	/// `auth_key`.
+	auth_key: Output<Hash<Cs>>,
+	/// `export_key`.
+	export_key: Output<Hash<Cs>>,
+	/// The clients long-term [`KeyPair`].
+	key_pair: KeyPair<OpaqueGroup<Cs>>,
+}
+
+/// Keys derived from the 3DH key exchange.
+struct SessionKeys<Cs: OpaqueCipherSuite> {
+	/// `Km2`.
+	server_mac_key: Output<Hash<Cs>>,
+	/// `Km3`.
+	client_mac_key: Output<Hash<Cs>>,
+	/// `session_key`.
+	session_key: Output<Hash<Cs>>,
+}
+
+impl Ksf for IdentityKsf {
+	fn stretch(input: &[u8], output: &mut [u8]) {
+		output.copy_from_slice(input);
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> OpaqueServer<Cs> {
+	/// Generates a random [`KeyPair`] and `oprf_seed`.
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn new<R>(rng: &mut R) -> Result<Self, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let key_pair = KeyPair::generate(rng)?;
+		let mut oprf_seed = Output::<Hash<Cs>>::default();
+		rng.try_fill_bytes(&mut oprf_seed)?;
+
+		Ok(Self {
+			key_pair,
+			oprf_seed,
+		})
+	}
+
+	/// Creates a new [`OpaqueServer`] from the provided [`KeyPair`] and
+	/// `oprf_seed`.
+	#[must_use]
+	pub const fn from_key_pair(
+		key_pair: KeyPair<OpaqueGroup<Cs>>,
+		oprf_seed: Output<Hash<Cs>>,
+	) -> Self {
+		Self {
+			key_pair,
+			oprf_seed,
+		}
+	}
+
+	/// Returns the [`KeyPair`].
+	#[must_use]
+	pub const fn key_pair(&self) -> &KeyPair<OpaqueGroup<Cs>> {
+		&self.key_pair
+	}
+
+	/// Returns the `oprf_seed`.
+	///
+	/// # ⚠️ Warning
+	///
+	/// This value is key material.
+	///
+	/// Please treat it with the care it deserves!
+	#[must_use]
+	pub const fn oprf_seed(&self) -> &Output<Hash<Cs>> {
+		&self.oprf_seed
+	}
+
+	/// Processes the [`RegistrationRequest`] for the provided
+	/// `credential_identifier`.
+	///
+	/// Corresponds to
+	/// [`CreateRegistrationResponse()` in RFC 9807 § 5.2.2](https://www.rfc-editor.org/rfc/rfc9807.html#section-5.2.2).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::DeriveKeyPair`] if no OPRF key can be derived for the
+	///   `credential_identifier`.
+	pub fn registration(
+		&self,
+		registration_request: &RegistrationRequest<Cs>,
+		credential_identifier: &[u8],
+	) -> Result<RegistrationResponse<Cs>> {
+		let server = OprfServer::from_key(oprf_key::<Cs>(&self.oprf_seed, credential_identifier)?);
+
+		Ok(RegistrationResponse {
+			evaluation_element: server.blind_evaluate(&registration_request.blinded_element),
+			server_public_key: self.key_pair.public_key().clone(),
+		})
+	}
+
+	/// Processes the [`Ke1`] with the [`RegistrationRecord`] stored for the
+	/// provided `credential_identifier`.
+	///
+	/// Corresponds to
+	/// [`GenerateKE2()` in RFC 9807 § 6.2.2](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.2.2).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if an identity or the `context` exceeds a
+	///   length of [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::DeriveKeyPair`] if no OPRF key can be derived for the
+	///   `credential_identifier`.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn login<R>(
+		&self,
+		rng: &mut R,
+		registration_record: &RegistrationRecord<Cs>,
+		credential_identifier: &[u8],
+		ke1: &Ke1<Cs>,
+		identifiers: Identifiers<'_>,
+		context: &[u8],
+	) -> Result<ServerLoginResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let server = OprfServer::from_key(
+			oprf_key::<Cs>(&self.oprf_seed, credential_identifier)
+				.map_err(Error::into_random::<R>)?,
+		);
+
+		let mut masking_nonce = [0; NONCE_LENGTH];
+		rng.try_fill_bytes(&mut masking_nonce)
+			.map_err(Error::Random)?;
+		let mut masked_response =
+			credential_response_pad::<Cs>(&registration_record.masking_key, &masking_nonce);
+		let response = self
+			.key_pair
+			.public_key()
+			.as_repr()
+			.iter()
+			.chain(&registration_record.envelope_nonce)
+			.chain(&registration_record.auth_tag);
+
+		for (byte, response) in masked_response.iter_mut().zip(response) {
+			*byte ^= response;
+		}
+
+		let mut server_nonce = [0; NONCE_LENGTH];
+		rng.try_fill_bytes(&mut server_nonce)
+			.map_err(Error::Random)?;
+		let mut server_keyshare_seed = [0; SEED_LENGTH];
+		rng.try_fill_bytes(&mut server_keyshare_seed)
+			.map_err(Error::Random)?;
+		let server_keyshare = derive_diffie_hellman_key_pair::<Cs>(&server_keyshare_seed);
+		server_keyshare_seed.zeroize();
+		let server_keyshare = server_keyshare.map_err(Error::into_random::<R>)?;
+
+		let mut ke2 = Ke2 {
+			evaluation_element: server.blind_evaluate(&ke1.blinded_element),
+			masking_nonce,
+			masked_response,
+			server_nonce,
+			server_public_keyshare: server_keyshare.public_key().clone(),
+			server_mac: Output::<Hash<Cs>>::default(),
+		};
+
+		let credentials = CleartextCredentials::new(
+			self.key_pair.public_key().as_repr(),
+			registration_record.client_public_key.as_repr(),
+			identifiers,
+		)
+		.map_err(Error::into_random::<R>)?;
+		let mut transcript =
+			preamble::<Cs>(context, &credentials, ke1, &ke2).map_err(Error::into_random::<R>)?;
+
+		let keys = SessionKeys::<Cs>::new(
+			[
+				diffie_hellman(server_keyshare.secret_key(), &ke1.client_public_keyshare),
+				diffie_hellman(self.key_pair.secret_key(), &ke1.client_public_keyshare),
+				diffie_hellman(
+					server_keyshare.secret_key(),
+					&registration_record.client_public_key,
+				),
+			],
+			&transcript.clone().finalize_fixed(),
+		);
+
+		ke2.server_mac = keys.server_mac(&transcript);
+		Update::update(&mut transcript, &ke2.server_mac);
+
+		Ok(ServerLoginResult {
+			server: ServerLogin {
+				expected_client_mac: keys.client_mac(transcript),
+				session_key: keys.session_key.clone(),
+			},
+			ke2,
+		})
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ServerLogin<Cs> {
+	/// Authenticates the client with the [`Ke3`] and returns the
+	/// `session_key`.
+	///
+	/// Corresponds to
+	/// [`ServerFinish()` in RFC 9807 § 6.2.2](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.2.2).
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Authentication`] if the client MAC is invalid.
+	pub fn finish(&self, ke3: &Ke3<Cs>) -> Result<Output<Hash<Cs>>> {
+		if bool::from(
+			self.expected_client_mac
+				.as_slice()
+				.ct_eq(ke3.client_mac.as_slice()),
+		) {
+			Ok(self.session_key.clone())
+		} else {
+			Err(Error::Authentication)
+		}
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ClientRegistration<Cs> {
+	/// Starts the registration of the provided `password`.
+	///
+	/// Corresponds to
+	/// [`CreateRegistrationRequest()` in RFC 9807 § 5.2.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-5.2.1).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the `password` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the `password` can never produce a valid
+	///   output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn start<R>(
+		rng: &mut R,
+		password: &[u8],
+	) -> Result<ClientRegistrationStartResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let OprfBlindResult {
+			client,
+			blinded_element,
+		} = OprfClient::blind(rng, &[password])?;
+
+		Ok(ClientRegistrationStartResult {
+			client: Self { client },
+			registration_request: RegistrationRequest { blinded_element },
+		})
+	}
+
+	/// Completes the registration with the [`RegistrationResponse`].
+	///
+	/// Corresponds to
+	/// [`FinalizeRegistrationRequest()` in RFC 9807 § 5.2.3](https://www.rfc-editor.org/rfc/rfc9807.html#section-5.2.3).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the `password` or an identity exceeds a
+	///   length of [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::DeriveKeyPair`] if no client [`KeyPair`] can be derived.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn finish<R>(
+		&self,
+		rng: &mut R,
+		password: &[u8],
+		registration_response: &RegistrationResponse<Cs>,
+		identifiers: Identifiers<'_>,
+	) -> Result<ClientRegistrationFinishResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let mut envelope_nonce = [0; NONCE_LENGTH];
+		rng.try_fill_bytes(&mut envelope_nonce)
+			.map_err(Error::Random)?;
+
+		let mut randomized_password = randomized_password::<Cs>(
+			&self.client,
+			password,
+			&registration_response.evaluation_element,
+		)
+		.map_err(Error::into_random::<R>)?;
+
+		let mut masking_key = Output::<Hash<Cs>>::default();
+		expand::<Cs>(&randomized_password, &[b"MaskingKey"], &mut masking_key);
+		let keys = EnvelopeKeys::<Cs>::new(&randomized_password, &envelope_nonce);
+		randomized_password.zeroize();
+		let keys = keys.map_err(Error::into_random::<R>)?;
+
+		let credentials = CleartextCredentials::new(
+			registration_response.server_public_key.as_repr(),
+			keys.key_pair.public_key().as_repr(),
+			identifiers,
+		)
+		.map_err(Error::into_random::<R>)?;
+		let auth_tag = keys.auth_tag(&envelope_nonce, &credentials);
+
+		Ok(ClientRegistrationFinishResult {
+			registration_record: RegistrationRecord {
+				client_public_key: keys.key_pair.public_key().clone(),
+				masking_key,
+				envelope_nonce,
+				auth_tag,
+			},
+			export_key: keys.export_key.clone(),
+		})
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ClientLogin<Cs> {
+	/// Starts the login with the provided `password`.
+	///
+	/// Corresponds to
+	/// [`GenerateKE1()` in RFC 9807 § 6.2.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.2.1).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the `password` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the `password` can never produce a valid
+	///   output.
+	/// - [`Error::DeriveKeyPair`] if no client keyshare can be derived.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn start<R>(
+		rng: &mut R,
+		password: &[u8],
+	) -> Result<ClientLoginStartResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let OprfBlindResult {
+			client,
+			blinded_element,
+		} = OprfClient::blind(rng, &[password])?;
+
+		let mut client_nonce = [0; NONCE_LENGTH];
+		rng.try_fill_bytes(&mut client_nonce)
+			.map_err(Error::Random)?;
+		let mut client_keyshare_seed = [0; SEED_LENGTH];
+		rng.try_fill_bytes(&mut client_keyshare_seed)
+			.map_err(Error::Random)?;
+		let client_keyshare = derive_diffie_hellman_key_pair::<Cs>(&client_keyshare_seed);
+		client_keyshare_seed.zeroize();
+		let (client_secret, client_public_keyshare) = client_keyshare
+			.map_err(Error::into_random::<R>)?
+			.into_keys();
+
+		let ke1 = Ke1 {
+			blinded_element,
+			client_nonce,
+			client_public_keyshare,
+		};
+
+		Ok(ClientLoginStartResult {
+			client: Self {
+				client,
+				client_secret,
+				ke1: ke1.clone(),
+			},
+			ke1,
+		})
+	}
+
+	/// Authenticates the server with the [`Ke2`] and completes the login.
+	///
+	/// Corresponds to
+	/// [`GenerateKE3()` in RFC 9807 § 6.2.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.2.1).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the `password`, an identity or the
+	///   `context` exceeds a length of [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Envelope`] if the envelope can't be recovered, e.g. because
+	///   of a wrong `password`.
+	/// - [`Error::Authentication`] if the server MAC is invalid.
+	pub fn finish(
+		&self,
+		password: &[u8],
+		ke2: &Ke2<Cs>,
+		identifiers: Identifiers<'_>,
+		context: &[u8],
+	) -> Result<ClientLoginFinishResult<Cs>> {
+		let mut randomized_password =
+			randomized_password::<Cs>(&self.client, password, &ke2.evaluation_element)?;
+
+		let mut masking_key = Output::<Hash<Cs>>::default();
+		expand::<Cs>(&randomized_password, &[b"MaskingKey"], &mut masking_key);
+		let mut response = credential_response_pad::<Cs>(&masking_key, &ke2.masking_nonce);
+		masking_key.zeroize();
+
+		for (byte, masked) in response.iter_mut().zip(&ke2.masked_response) {
+			*byte ^= masked;
+		}
+
+		let (server_public_key, envelope) = response
+			.split_at_checked(ElementLength::<Cs::Oprf>::USIZE)
+			.ok_or(Error::Envelope)?;
+		let server_public_key =
+			PublicKey::from_repr(server_public_key).map_err(|_| Error::Envelope)?;
+		let (envelope_nonce, auth_tag) = envelope
+			.split_first_chunk::<NONCE_LENGTH>()
+			.ok_or(Error::Envelope)?;
+
+		let keys = EnvelopeKeys::<Cs>::new(&randomized_password, envelope_nonce);
+		randomized_password.zeroize();
+		let keys = keys.map_err(|_| Error::Envelope)?;
+
+		let credentials = CleartextCredentials::new(
+			server_public_key.as_repr(),
+			keys.key_pair.public_key().as_repr(),
+			identifiers,
+		)?;
+
+		if !bool::from(
+			keys.auth_tag(envelope_nonce, &credentials)
+				.as_slice()
+				.ct_eq(auth_tag),
+		) {
+			return Err(Error::Envelope);
+		}
+
+		let mut transcript = preamble::<Cs>(context, &credentials, &self.ke1, ke2)?;
+
+		let session_keys = SessionKeys::<Cs>::new(
+			[
+				diffie_hellman(&self.client_secret, &ke2.server_public_keyshare),
+				diffie_hellman(&self.client_secret, &server_public_key),
+				diffie_hellman(keys.key_pair.secret_key(), &ke2.server_public_keyshare),
+			],
+			&transcript.clone().finalize_fixed(),
+		);
+
+		if !bool::from(
+			session_keys
+				.server_mac(&transcript)
+				.as_slice()
+				.ct_eq(ke2.server_mac.as_slice()),
+		) {
+			return Err(Error::Authentication);
+		}
+
+		Update::update(&mut transcript, &ke2.server_mac);
+
+		Ok(ClientLoginFinishResult {
+			ke3: Ke3 {
+				client_mac: session_keys.client_mac(transcript),
+			},
+			session_key: session_keys.session_key.clone(),
+			export_key: keys.export_key.clone(),
+			server_public_key,
+		})
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> RegistrationRequest<Cs> {
+	/// Returns the [`BlindedElement`].
+	#[must_use]
+	pub const fn blinded_element(&self) -> &BlindedElement<Cs::Oprf> {
+		&self.blinded_element
+	}
+
+	/// Deserializes the provided `repr` to a [`RegistrationRequest`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		Ok(Self {
+			blinded_element: BlindedElement::from_repr(repr)?,
+		})
+	}
+
+	/// Serializes this [`RegistrationRequest`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.blinded_element.as_repr().to_vec()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> RegistrationResponse<Cs> {
+	/// Returns the [`EvaluationElement`].
+	#[must_use]
+	pub const fn evaluation_element(&self) -> &EvaluationElement<Cs::Oprf> {
+		&self.evaluation_element
+	}
+
+	/// Returns the servers [`PublicKey`].
+	#[must_use]
+	pub const fn server_public_key(&self) -> &PublicKey<OpaqueGroup<Cs>> {
+		&self.server_public_key
+	}
+
+	/// Deserializes the provided `repr` to a [`RegistrationResponse`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(mut repr: &[u8]) -> Result<Self> {
+		let evaluation_element =
+			EvaluationElement::from_repr(take(&mut repr, element_length::<Cs>())?)?;
+		let server_public_key = PublicKey::from_repr(repr)?;
+
+		Ok(Self {
+			evaluation_element,
+			server_public_key,
+		})
+	}
+
+	/// Serializes this [`RegistrationResponse`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		[
+			self.evaluation_element.as_repr().as_slice(),
+			self.server_public_key.as_repr(),
+		]
+		.concat()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> RegistrationRecord<Cs> {
+	/// Creates a fake [`RegistrationRecord`] to respond to login attempts for
+	/// unknown credential identifiers.
+	///
+	/// See [RFC 9807 § 10.9](https://www.rfc-editor.org/rfc/rfc9807.html#section-10.9).
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn fake<R>(rng: &mut R) -> Result<Self, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let (_, client_public_key) = KeyPair::generate(rng)?.into_keys();
+		let mut masking_key = Output::<Hash<Cs>>::default();
+		rng.try_fill_bytes(&mut masking_key)?;
+
+		Ok(Self {
+			client_public_key,
+			masking_key,
+			envelope_nonce: [0; NONCE_LENGTH],
+			auth_tag: Output::<Hash<Cs>>::default(),
+		})
+	}
+
+	/// Returns the clients [`PublicKey`].
+	#[must_use]
+	pub const fn client_public_key(&self) -> &PublicKey<OpaqueGroup<Cs>> {
+		&self.client_public_key
+	}
+
+	/// Deserializes the provided `repr` to a [`RegistrationRecord`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(mut repr: &[u8]) -> Result<Self> {
+		let client_public_key = PublicKey::from_repr(take(&mut repr, element_length::<Cs>())?)?;
+		let masking_key = output_from_repr::<Cs>(take(&mut repr, hash_length::<Cs>())?)?;
+		let envelope_nonce = nonce_from_repr(&mut repr)?;
+		let auth_tag = output_from_repr::<Cs>(repr)?;
+
+		Ok(Self {
+			client_public_key,
+			masking_key,
+			envelope_nonce,
+			auth_tag,
+		})
+	}
+
+	/// Serializes this [`RegistrationRecord`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		[
+			self.client_public_key.as_repr().as_slice(),
+			&self.masking_key,
+			&self.envelope_nonce,
+			&self.auth_tag,
+		]
+		.concat()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Ke1<Cs> {
+	/// Deserializes the provided `repr` to a [`Ke1`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(mut repr: &[u8]) -> Result<Self> {
+		let blinded_element = BlindedElement::from_repr(take(&mut repr, element_length::<Cs>())?)?;
+		let client_nonce = nonce_from_repr(&mut repr)?;
+		let client_public_keyshare = PublicKey::from_repr(repr)?;
+
+		Ok(Self {
+			blinded_element,
+			client_nonce,
+			client_public_keyshare,
+		})
+	}
+
+	/// Serializes this [`Ke1`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.parts().concat()
+	}
+
+	/// Returns all serialized fields in order.
+	fn parts(&self) -> [&[u8]; 3] {
+		[
+			self.blinded_element.as_repr(),
+			&self.client_nonce,
+			self.client_public_keyshare.as_repr(),
+		]
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Ke2<Cs> {
+	/// Deserializes the provided `repr` to a [`Ke2`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(mut repr: &[u8]) -> Result<Self> {
+		let evaluation_element =
+			EvaluationElement::from_repr(take(&mut repr, element_length::<Cs>())?)?;
+		let masking_nonce = nonce_from_repr(&mut repr)?;
+		let masked_response = take(&mut repr, masked_response_length::<Cs>())?.to_vec();
+		let server_nonce = nonce_from_repr(&mut repr)?;
+		let server_public_keyshare =
+			PublicKey::from_repr(take(&mut repr, element_length::<Cs>())?)?;
+		let server_mac = output_from_repr::<Cs>(repr)?;
+
+		Ok(Self {
+			evaluation_element,
+			masking_nonce,
+			masked_response,
+			server_nonce,
+			server_public_keyshare,
+			server_mac,
+		})
+	}
+
+	/// Serializes this [`Ke2`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.parts()
+			.into_iter()
+			.chain(iter::once(self.server_mac.as_slice()))
+			.collect::<Vec<_>>()
+			.concat()
+	}
+
+	/// Returns all serialized fields in order except `server_mac`.
+	fn parts(&self) -> [&[u8]; 5] {
+		[
+			self.evaluation_element.as_repr(),
+			&self.masking_nonce,
+			&self.masked_response,
+			&self.server_nonce,
+			self.server_public_keyshare.as_repr(),
+		]
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Ke3<Cs> {
+	/// Deserializes the provided `repr` to a [`Ke3`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		Ok(Self {
+			client_mac: output_from_repr::<Cs>(repr)?,
+		})
+	}
+
+	/// Serializes this [`Ke3`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.client_mac.to_vec()
+	}
+}
+
+impl<'credentials> CleartextCredentials<'credentials> {
+	/// Creates the `CleartextCredentials`, identities default to the
+	/// corresponding public keys.
+	///
+	/// Corresponds to
+	/// [`CreateCleartextCredentials()` in RFC 9807 § 4](https://www.rfc-editor.org/rfc/rfc9807.html#section-4-4).
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::InputLength`] if an identity exceeds a length of
+	/// [`u16::MAX`].
+	fn new(
+		server_public_key: &'credentials [u8],
+		client_public_key: &'credentials [u8],
+		identifiers: Identifiers<'credentials>,
+	) -> Result<Self> {
+		let server_identity = identifiers.server.unwrap_or(server_public_key);
+		let client_identity = identifiers.client.unwrap_or(client_public_key);
+
+		Ok(Self {
+			server_public_key,
+			server_identity,
+			server_identity_length: server_identity.i2osp_length().ok_or(Error::InputLength)?,
+			client_identity,
+			client_identity_length: client_identity.i2osp_length().ok_or(Error::InputLength)?,
+		})
+	}
+
+	/// Returns the serialization of these `CleartextCredentials`.
+	const fn parts(&self) -> [&[u8]; 5] {
+		[
+			self.server_public_key,
+			&self.server_identity_length,
+			self.server_identity,
+			&self.client_identity_length,
+			self.client_identity,
+		]
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> EnvelopeKeys<Cs> {
+	/// Derives all keys of the envelope.
+	///
+	/// Corresponds to the common part of
+	/// [`Store()` and `Recover()` in RFC 9807 § 4.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-4.1).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::DeriveKeyPair`] if no client [`KeyPair`] can be derived.
+	fn new(randomized_password: &[u8], envelope_nonce: &[u8; NONCE_LENGTH]) -> Result<Self> {
+		let mut auth_key = Output::<Hash<Cs>>::default();
+		expand::<Cs>(
+			randomized_password,
+			&[envelope_nonce, b"AuthKey"],
+			&mut auth_key,
+		);
+		let mut export_key = Output::<Hash<Cs>>::default();
+		expand::<Cs>(
+			randomized_password,
+			&[envelope_nonce, b"ExportKey"],
+			&mut export_key,
+		);
+		let mut seed = [0; SEED_LENGTH];
+		expand::<Cs>(
+			randomized_password,
+			&[envelope_nonce, b"PrivateKey"],
+			&mut seed,
+		);
+		let key_pair = derive_diffie_hellman_key_pair::<Cs>(&seed);
+		seed.zeroize();
+
+		Ok(Self {
+			auth_key,
+			export_key,
+			key_pair: key_pair?,
+		})
+	}
+
+	/// Computes the `auth_tag` of the envelope.
+	fn auth_tag(
+		&self,
+		envelope_nonce: &[u8; NONCE_LENGTH],
+		credentials: &CleartextCredentials<'_>,
+	) -> Output<Hash<Cs>> {
+		let mut mac = hmac::<Cs>(&self.auth_key);
+		Mac::update(&mut mac, envelope_nonce);
+
+		for part in credentials.parts() {
+			Mac::update(&mut mac, part);
+		}
+
+		mac.finalize().into_bytes()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> SessionKeys<Cs> {
+	/// Derives all keys from the 3DH shared secrets and the preamble hash.
+	///
+	/// Corresponds to
+	/// [`DeriveKeys()` in RFC 9807 § 6.4.2](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.4.2).
+	fn new(
+		mut ikm: [Array<u8, ElementLength<Cs::Oprf>>; 3],
+		preamble_hash: &Output<Hash<Cs>>,
+	) -> Self {
+		let mut extract = SimpleHkdfExtract::<Hash<Cs>>::new(None);
+
+		for ikm in &ikm {
+			extract.input_ikm(ikm);
+		}
+
+		ikm.iter_mut().for_each(Zeroize::zeroize);
+		let (mut prk, _) = extract.finalize();
+
+		let mut handshake_secret = derive_secret::<Cs>(&prk, b"HandshakeSecret", preamble_hash);
+		let session_key = derive_secret::<Cs>(&prk, b"SessionKey", preamble_hash);
+		prk.zeroize();
+		let server_mac_key = derive_secret::<Cs>(&handshake_secret, b"ServerMAC", &[]);
+		let client_mac_key = derive_secret::<Cs>(&handshake_secret, b"ClientMAC", &[]);
+		handshake_secret.zeroize();
+
+		Self {
+			server_mac_key,
+			client_mac_key,
+			session_key,
+		}
+	}
+
+	/// Computes the `server_mac` over the preamble `transcript`.
+	fn server_mac(&self, transcript: &Hash<Cs>) -> Output<Hash<Cs>> {
+		let mut mac = hmac::<Cs>(&self.server_mac_key);
+		Mac::update(&mut mac, &transcript.clone().finalize_fixed());
+		mac.finalize().into_bytes()
+	}
+
+	/// Computes the `client_mac` over the preamble and `server_mac`
+	/// `transcript`.
+	fn client_mac(&self, transcript: Hash<Cs>) -> Output<Hash<Cs>> {
+		let mut mac = hmac::<Cs>(&self.client_mac_key);
+		Mac::update(&mut mac, &transcript.finalize_fixed());
+		mac.finalize().into_bytes()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for OpaqueServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			key_pair: self.key_pair.clone(),
+			oprf_seed: self.oprf_seed.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for OpaqueServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("OpaqueServer")
+			.field("key_pair", &self.key_pair)
+			.field("oprf_seed", &self.oprf_seed)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Drop for OpaqueServer<Cs> {
+	fn drop(&mut self) {
+		self.oprf_seed.zeroize();
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for OpaqueServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for OpaqueServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.key_pair.eq(&other.key_pair) && self.oprf_seed.eq(&other.oprf_seed)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for OpaqueServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for ServerLogin<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			expected_client_mac: self.expected_client_mac.clone(),
+			session_key: self.session_key.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for ServerLogin<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ServerLogin")
+			.field("expected_client_mac", &self.expected_client_mac)
+			.field("session_key", &self.session_key)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Drop for ServerLogin<Cs> {
+	fn drop(&mut self) {
+		self.expected_client_mac.zeroize();
+		self.session_key.zeroize();
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for ServerLogin<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for ServerLogin<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.expected_client_mac.eq(&other.expected_client_mac)
+			&& self.session_key.eq(&other.session_key)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for ServerLogin<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for ServerLoginResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ServerLoginResult")
+			.field("server", &self.server)
+			.field("ke2", &self.ke2)
+			.finish()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for ServerLoginResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for ClientRegistration<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for ClientRegistration<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ClientRegistration")
+			.field("client", &self.client)
+			.finish()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for ClientRegistration<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for ClientRegistration<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client.eq(&other.client)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for ClientRegistration<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for ClientRegistrationStartResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ClientRegistrationStartResult")
+			.field("client", &self.client)
+			.field("registration_request", &self.registration_request)
+			.finish()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for ClientRegistrationStartResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for ClientRegistrationFinishResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ClientRegistrationFinishResult")
+			.field("registration_record", &self.registration_record)
+			.field("export_key", &self.export_key)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for ClientLogin<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			client_secret: self.client_secret.clone(),
+			ke1: self.ke1.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for ClientLogin<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ClientLogin")
+			.field("client", &self.client)
+			.field("client_secret", &self.client_secret)
+			.field("ke1", &self.ke1)
+			.finish()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for ClientLogin<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for ClientLogin<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client.eq(&other.client)
+			&& self.client_secret.eq(&other.client_secret)
+			&& self.ke1.eq(&other.ke1)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for ClientLogin<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for ClientLoginStartResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ClientLoginStartResult")
+			.field("client", &self.client)
+			.field("ke1", &self.ke1)
+			.finish()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for ClientLoginStartResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for ClientLoginFinishResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ClientLoginFinishResult")
+			.field("ke3", &self.ke3)
+			.field("session_key", &self.session_key)
+			.field("export_key", &self.export_key)
+			.field("server_public_key", &self.server_public_key)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for RegistrationRequest<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			blinded_element: self.blinded_element.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for RegistrationRequest<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RegistrationRequest")
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for RegistrationRequest<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for RegistrationRequest<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.blinded_element.eq(&other.blinded_element)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for RegistrationRequest<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for RegistrationResponse<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			evaluation_element: self.evaluation_element.clone(),
+			server_public_key: self.server_public_key.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for RegistrationResponse<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RegistrationResponse")
+			.field("evaluation_element", &self.evaluation_element)
+			.field("server_public_key", &self.server_public_key)
+			.finish()
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for RegistrationResponse<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for RegistrationResponse<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.evaluation_element.eq(&other.evaluation_element)
+			&& self.server_public_key.eq(&other.server_public_key)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for RegistrationResponse<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for RegistrationRecord<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client_public_key: self.client_public_key.clone(),
+			masking_key: self.masking_key.clone(),
+			envelope_nonce: self.envelope_nonce,
+			auth_tag: self.auth_tag.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for RegistrationRecord<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RegistrationRecord")
+			.field("client_public_key", &self.client_public_key)
+			.field("masking_key", &self.masking_key)
+			.field("envelope_nonce", &self.envelope_nonce)
+			.field("auth_tag", &self.auth_tag)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Drop for RegistrationRecord<Cs> {
+	fn drop(&mut self) {
+		self.masking_key.zeroize();
+		self.envelope_nonce.zeroize();
+		self.auth_tag.zeroize();
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for RegistrationRecord<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for RegistrationRecord<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client_public_key.eq(&other.client_public_key)
+			&& self.masking_key.eq(&other.masking_key)
+			&& self.envelope_nonce.eq(&other.envelope_nonce)
+			&& self.auth_tag.eq(&other.auth_tag)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for RegistrationRecord<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for Ke1<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			blinded_element: self.blinded_element.clone(),
+			client_nonce: self.client_nonce,
+			client_public_keyshare: self.client_public_keyshare.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for Ke1<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Ke1")
+			.field("blinded_element", &self.blinded_element)
+			.field("client_nonce", &self.client_nonce)
+			.field("client_public_keyshare", &self.client_public_keyshare)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Drop for Ke1<Cs> {
+	fn drop(&mut self) {
+		self.client_nonce.zeroize();
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for Ke1<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for Ke1<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.blinded_element.eq(&other.blinded_element)
+			&& self.client_nonce.eq(&other.client_nonce)
+			&& self
+				.client_public_keyshare
+				.eq(&other.client_public_keyshare)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for Ke1<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for Ke2<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			evaluation_element: self.evaluation_element.clone(),
+			masking_nonce: self.masking_nonce,
+			masked_response: self.masked_response.clone(),
+			server_nonce: self.server_nonce,
+			server_public_keyshare: self.server_public_keyshare.clone(),
+			server_mac: self.server_mac.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for Ke2<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Ke2")
+			.field("evaluation_element", &self.evaluation_element)
+			.field("masking_nonce", &self.masking_nonce)
+			.field("masked_response", &self.masked_response)
+			.field("server_nonce", &self.server_nonce)
+			.field("server_public_keyshare", &self.server_public_keyshare)
+			.field("server_mac", &self.server_mac)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Drop for Ke2<Cs> {
+	fn drop(&mut self) {
+		self.masking_nonce.zeroize();
+		self.masked_response.zeroize();
+		self.server_nonce.zeroize();
+		self.server_mac.zeroize();
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for Ke2<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for Ke2<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.evaluation_element.eq(&other.evaluation_element)
+			&& self.masking_nonce.eq(&other.masking_nonce)
+			&& self.masked_response.eq(&other.masked_response)
+			&& self.server_nonce.eq(&other.server_nonce)
+			&& self
+				.server_public_keyshare
+				.eq(&other.server_public_keyshare)
+			&& self.server_mac.eq(&other.server_mac)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for Ke2<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Clone for Ke3<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client_mac: self.client_mac.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Debug for Ke3<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Ke3")
+			.field("client_mac", &self.client_mac)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Drop for Ke3<Cs> {
+	fn drop(&mut self) {
+		self.client_mac.zeroize();
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> Eq for Ke3<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> PartialEq for Ke3<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client_mac.eq(&other.client_mac)
+	}
+}
+
+impl<Cs: OpaqueCipherSuite> ZeroizeOnDrop for Ke3<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Drop for EnvelopeKeys<Cs> {
+	fn drop(&mut self) {
+		self.auth_key.zeroize();
+		self.export_key.zeroize();
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: OpaqueCipherSuite> Drop for SessionKeys<Cs> {
+	fn drop(&mut self) {
+		self.server_mac_key.zeroize();
+		self.client_mac_key.zeroize();
+		self.session_key.zeroize();
+	}
+}
+
+/// Derives the OPRF key for the provided `credential_identifier`.
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+/// - [`Error::DeriveKeyPair`] if no OPRF key can be derived.
+fn oprf_key<Cs: OpaqueCipherSuite>(
+	oprf_seed: &[u8],
+	credential_identifier: &[u8],
+) -> Result<SecretKey<OpaqueGroup<Cs>>> {
+	let mut seed = [0; SEED_LENGTH];
+	expand::<Cs>(oprf_seed, &[credential_identifier, b"OprfKey"], &mut seed);
+	let secret_key = SecretKey::derive::<Cs::Oprf>(Mode::Oprf, &seed, b"OPAQUE-DeriveKeyPair");
+	seed.zeroize();
+
+	secret_key
+}
+
+/// Corresponds to
+/// [`DeriveDiffieHellmanKeyPair()` in RFC 9807 § 6.4.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.4.1).
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+/// - [`Error::DeriveKeyPair`] if no [`KeyPair`] can be derived.
+fn derive_diffie_hellman_key_pair<Cs: OpaqueCipherSuite>(
+	seed: &[u8; SEED_LENGTH],
+) -> Result<KeyPair<OpaqueGroup<Cs>>> {
+	KeyPair::derive::<Cs::Oprf>(Mode::Oprf, seed, b"OPAQUE-DeriveDiffieHellmanKeyPair")
+}
+
+/// Corresponds to
+/// [`DiffieHellman()` in RFC 9807 § 6.4.1](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.4.1).
+fn diffie_hellman<G: Group>(
+	secret_key: &SecretKey<G>,
+	public_key: &PublicKey<G>,
+) -> Array<u8, G::ElementLength> {
+	G::element_to_repr(&(secret_key.to_scalar() * public_key.as_element()))
+}
+
+/// Computes the `randomized_password` from the OPRF evaluation.
+///
+/// # Errors
+///
+/// Returns [`Error::InputLength`] if the `password` exceeds a length of
+/// [`u16::MAX`].
+fn randomized_password<Cs: OpaqueCipherSuite>(
+	client: &OprfClient<Cs::Oprf>,
+	password: &[u8],
+	evaluation_element: &EvaluationElement<Cs::Oprf>,
+) -> Result<Output<Hash<Cs>>> {
+	let mut oprf_output = client.finalize(&[password], evaluation_element)?;
+	let mut stretched_oprf_output = Output::<Hash<Cs>>::default();
+	Cs::Ksf::stretch(&oprf_output, &mut stretched_oprf_output);
+
+	let mut extract = SimpleHkdfExtract::<Hash<Cs>>::new(None);
+	extract.input_ikm(&oprf_output);
+	extract.input_ikm(&stretched_oprf_output);
+	oprf_output.zeroize();
+	stretched_oprf_output.zeroize();
+
+	Ok(extract.finalize().0)
+}
+
+/// Computes the `credential_response_pad`.
+fn credential_response_pad<Cs: OpaqueCipherSuite>(
+	masking_key: &[u8],
+	masking_nonce: &[u8; NONCE_LENGTH],
+) -> Vec<u8> {
+	let mut pad = vec![0; masked_response_length::<Cs>()];
+	expand::<Cs>(
+		masking_key,
+		&[masking_nonce, b"CredentialResponsePad"],
+		&mut pad,
+	);
+	pad
+}
+
+/// Hashes the preamble.
+///
+/// Corresponds to
+/// [`Preamble()` in RFC 9807 § 6.4.2](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.4.2).
+///
+/// # Errors
+///
+/// Returns [`Error::InputLength`] if the `context` exceeds a length of
+/// [`u16::MAX`].
+fn preamble<Cs: OpaqueCipherSuite>(
+	context: &[u8],
+	credentials: &CleartextCredentials<'_>,
+	ke1: &Ke1<Cs>,
+	ke2: &Ke2<Cs>,
+) -> Result<Hash<Cs>> {
+	let context_length = context.i2osp_length().ok_or(Error::InputLength)?;
+	let mut transcript = Hash::<Cs>::default();
+
+	for part in [
+		b"OPAQUEv1-".as_slice(),
+		&context_length,
+		context,
+		&credentials.client_identity_length,
+		credentials.client_identity,
+	]
+	.into_iter()
+	.chain(ke1.parts())
+	.chain([
+		credentials.server_identity_length.as_slice(),
+		credentials.server_identity,
+	])
+	.chain(ke2.parts())
+	{
+		Update::update(&mut transcript, part);
+	}
+
+	Ok(transcript)
+}
+
+/// Corresponds to
+/// [`Derive-Secret()` in RFC 9807 § 6.4.2](https://www.rfc-editor.org/rfc/rfc9807.html#section-6.4.2).
+fn derive_secret<Cs: OpaqueCipherSuite>(
+	secret: &[u8],
+	label: &[u8],
+	transcript_hash: &[u8],
+) -> Output<Hash<Cs>> {
+	/// Prefix of all labels.
+	const LABEL_PREFIX: &[u8] = b"OPAQUE-";
+
+	let mut output = Output::<Hash<Cs>>::default();
+	let length = u16::try_from(output.len())
+		.expect("`Nx` must fit into `u16`")
+		.i2osp();
+	let label_length =
+		u8::try_from(LABEL_PREFIX.len() + label.len()).expect("labels must fit into `u8`");
+	let context_length =
+		u8::try_from(transcript_hash.len()).expect("transcript hash must fit into `u8`");
+
+	expand::<Cs>(
+		secret,
+		&[
+			&length,
+			&[label_length],
+			LABEL_PREFIX,
+			label,
+			&[context_length],
+			transcript_hash,
+		],
+		&mut output,
+	);
+
+	output
+}
+
+/// HKDF-Expand.
+fn expand<Cs: OpaqueCipherSuite>(prk: &[u8], info: &[&[u8]], okm: &mut [u8]) {
+	SimpleHkdf::<Hash<Cs>>::from_prk(prk)
+		.expect("`prk` must be at least `Nh` long")
+		.expand_multi_info(info, okm)
+		.expect("`okm` must be at most `255 * Nh` long");
+}
+
+/// Creates HMAC with the provided `key`.
+fn hmac<Cs: OpaqueCipherSuite>(key: &[u8]) -> SimpleHmac<Hash<Cs>> {
+	<SimpleHmac<Hash<Cs>> as KeyInit>::new_from_slice(key).expect("HMAC takes keys of any length")
+}
+
+/// Splits off the first `length` bytes of `repr`.
+///
+/// # Errors
+///
+/// Returns [`Error::FromRepr`] if `repr` is too short.
+fn take<'repr>(repr: &mut &'repr [u8], length: usize) -> Result<&'repr [u8]> {
+	let (head, tail) = repr.split_at_checked(length).ok_or(Error::FromRepr)?;
+	*repr = tail;
+
+	Ok(head)
+}
+
+/// Splits off a nonce from `repr`.
+///
+/// # Errors
+///
+/// Returns [`Error::FromRepr`] if `repr` is too short.
+fn nonce_from_repr(repr: &mut &[u8]) -> Result<[u8; NONCE_LENGTH]> {
+	let (nonce, tail) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+	*repr = tail;
+
+	Ok(*nonce)
+}
+
+/// Deserializes a hash output.
+///
+/// # Errors
+///
+/// Returns [`Error::FromRepr`] if `repr` has the wrong length.
+fn output_from_repr<Cs: OpaqueCipherSuite>(repr: &[u8]) -> Result<Output<Hash<Cs>>> {
+	repr.try_into().map_err(|_| Error::FromRepr)
+}
+
+/// `Noe` and `Npk`.
+const fn element_length<Cs: OpaqueCipherSuite>() -> usize {
+	ElementLength::<Cs::Oprf>::USIZE
+}
+
+/// `Nh`, `Nm` and `Nx`.
+const fn hash_length<Cs: OpaqueCipherSuite>() -> usize {
+	<Hash<Cs> as OutputSizeUser>::OutputSize::USIZE
+}
+
+/// Length of `masked_response`.
+const fn masked_response_length<Cs: OpaqueCipherSuite>() -> usize {
+	element_length::<Cs>() + NONCE_LENGTH + hash_length::<Cs>()
+}
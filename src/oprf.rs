@@ -14,7 +14,7 @@ use rand_core::TryCryptoRng;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::cipher_suite::{CipherSuite, Element, NonIdentityElement, NonZeroScalar};
-use crate::common::{BlindedElement, EvaluationElement, Mode};
+use crate::common::{BlindedElement, EvaluationElement, Mode, UnblindedElement};
 use crate::error::{Error, Result};
 #[cfg(feature = "alloc")]
 use crate::internal::AllocBlindResult;
@@ -165,6 +165,21 @@ impl<Cs: CipherSuite> OprfClient<Cs> {
 		Ok(output)
 	}
 
+	/// Completes the evaluation without hashing the [`UnblindedElement`] to an
+	/// output. [`UnblindedElement::finalize()`] with [`None`] produces the
+	/// same output as [`finalize()`](Self::finalize).
+	#[must_use]
+	pub fn finalize_element(
+		&self,
+		evaluation_element: &EvaluationElement<Cs>,
+	) -> UnblindedElement<Cs> {
+		let [unblinded_element] = Self::batch_finalize_element(
+			array::from_ref(self),
+			array::from_ref(evaluation_element),
+		);
+		unblinded_element
+	}
+
 	/// Batch completes evaluations *without allocation*.
 	///
 	/// It is expected that a part of the computation is as efficient as
@@ -183,12 +198,31 @@ impl<Cs: CipherSuite> OprfClient<Cs> {
 		[Output<Cs::Hash>; N]:
 			AssocArraySize<Size: ArraySize<ArrayType<Output<Cs::Hash>> = [Output<Cs::Hash>; N]>>,
 	{
+		let unblinded_elements = Self::batch_finalize_element(clients, evaluation_elements);
+
+		internal::batch_finalize::<Cs, N>(
+			inputs,
+			unblinded_elements.iter().map(UnblindedElement::as_ref),
+			None,
+		)
+	}
+
+	/// Batch completes evaluations without hashing the [`UnblindedElement`]s
+	/// *without allocation*.
+	///
+	/// See [`finalize_element()`](Self::finalize_element).
+	#[must_use]
+	pub fn batch_finalize_element<const N: usize>(
+		clients: &[Self; N],
+		evaluation_elements: &[EvaluationElement<Cs>; N],
+	) -> [UnblindedElement<Cs>; N] {
 		let blinds = clients.iter().map(|client| client.blind).collect_array();
 		let evaluation_elements = evaluation_elements
 			.iter()
 			.map(EvaluationElement::as_element);
 
-		internal::batch_finalize::<Cs, N>(inputs, blinds, evaluation_elements, None)
+		internal::batch_finalize_element::<Cs, N>(blinds, evaluation_elements)
+			.map(UnblindedElement::from)
 	}
 
 	/// Batch completes evaluations.
@@ -219,10 +253,45 @@ impl<Cs: CipherSuite> OprfClient<Cs> {
 			return Err(Error::Batch);
 		}
 
+		let unblinded_elements = Self::batch_alloc_finalize_element(clients, evaluation_elements)?;
+
+		internal::batch_alloc_finalize::<Cs>(
+			inputs,
+			unblinded_elements.iter().map(UnblindedElement::as_ref),
+			None,
+		)
+	}
+
+	/// Batch completes evaluations without hashing the [`UnblindedElement`]s.
+	///
+	/// See [`finalize_element()`](Self::finalize_element).
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Batch`] if the number of items in `clients` and
+	/// `evaluation_elements` don't match.
+	#[cfg(feature = "alloc")]
+	pub fn batch_alloc_finalize_element<'clients, 'evaluation_elements, Ic, Iee>(
+		clients: Ic,
+		evaluation_elements: Iee,
+	) -> Result<Vec<UnblindedElement<Cs>>>
+	where
+		Ic: ExactSizeIterator<Item = &'clients Self>,
+		Iee: ExactSizeIterator<Item = &'evaluation_elements EvaluationElement<Cs>>,
+	{
+		if clients.len() != evaluation_elements.len() {
+			return Err(Error::Batch);
+		}
+
 		let blinds = clients.map(|client| client.blind).collect();
 		let evaluation_elements = evaluation_elements.map(EvaluationElement::as_element);
 
-		internal::batch_alloc_finalize::<Cs>(length, inputs, blinds, evaluation_elements, None)
+		Ok(
+			internal::batch_alloc_finalize_element::<Cs>(blinds, evaluation_elements)
+				.into_iter()
+				.map(UnblindedElement::from)
+				.collect(),
+		)
 	}
 }
 
@@ -20,7 +20,9 @@ use crate::cipher_suite::{CipherSuite, Element, NonIdentityElement, NonZeroScala
 use crate::common::BatchAllocBlindEvaluateResult;
 use crate::common::{
 	BatchBlindEvaluateResult, BlindEvaluateResult, BlindedElement, EvaluationElement, Mode, Proof,
+	UnblindedElement,
 };
+use crate::dleq::{self, Domain};
 use crate::error::{Error, Result};
 use crate::group::{CipherSuiteExt, Group};
 #[cfg(feature = "alloc")]
@@ -201,6 +203,37 @@ impl<Cs: CipherSuite> PoprfClient<Cs> {
 		Ok(output)
 	}
 
+	/// Completes the evaluation without hashing the [`UnblindedElement`] to an
+	/// output. [`UnblindedElement::finalize()`] with the same `info` produces
+	/// the same output as [`finalize()`](Self::finalize).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InfoLength`] if the provided `info` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfo`] if the provided `info` can never produce a
+	///   valid output.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn finalize_element(
+		&self,
+		public_key: &PublicKey<Cs::Group>,
+		evaluation_element: &EvaluationElement<Cs>,
+		proof: &Proof<Cs>,
+		info: &[u8],
+	) -> Result<UnblindedElement<Cs>> {
+		let [unblinded_element] = Self::batch_finalize_element(
+			array::from_ref(self),
+			public_key,
+			array::from_ref(evaluation_element),
+			proof,
+			info,
+		)?;
+		Ok(unblinded_element)
+	}
+
 	/// Batch completes evaluations with a combined [`Proof`] *without
 	/// allocation*.
 	///
@@ -233,6 +266,40 @@ impl<Cs: CipherSuite> PoprfClient<Cs> {
 		[Output<Cs::Hash>; N]:
 			AssocArraySize<Size: ArraySize<ArrayType<Output<Cs::Hash>> = [Output<Cs::Hash>; N]>>,
 	{
+		let unblinded_elements =
+			Self::batch_finalize_element(clients, public_key, evaluation_elements, proof, info)?;
+
+		internal::batch_finalize::<Cs, N>(
+			inputs,
+			unblinded_elements.iter().map(UnblindedElement::as_ref),
+			Some(Info::new(info)?),
+		)
+	}
+
+	/// Batch completes evaluations with a combined [`Proof`] without hashing
+	/// the [`UnblindedElement`]s *without allocation*.
+	///
+	/// See [`finalize_element()`](Self::finalize_element).
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of items in `clients` and
+	///   `evaluation_elements` are zero or exceed a length of [`u16::MAX`].
+	/// - [`Error::InfoLength`] if the provided `info` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfo`] if the provided `info` can never produce a
+	///   valid output.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn batch_finalize_element<const N: usize>(
+		clients: &[Self; N],
+		public_key: &PublicKey<Cs::Group>,
+		evaluation_elements: &[EvaluationElement<Cs>; N],
+		proof: &Proof<Cs>,
+		info: &[u8],
+	) -> Result<[UnblindedElement<Cs>; N]> {
 		if N == 0 || N > u16::MAX.into() {
 			return Err(Error::Batch);
 		}
@@ -243,16 +310,29 @@ impl<Cs: CipherSuite> PoprfClient<Cs> {
 		let c = evaluation_elements.iter().map(EvaluationElement::as_ref);
 		let d = clients.iter().map(|client| client.blinded_element.as_ref());
 
-		let composites =
-			internal::compute_composites::<_, N>(Mode::Poprf, None, tweaked_key.as_ref(), c, d)?;
-		internal::verify_proof(Mode::Poprf, composites, tweaked_key.as_ref(), proof)?;
+		let composites = dleq::compute_composites::<_, N>(
+			Domain::Rfc(Mode::Poprf),
+			None,
+			tweaked_key.as_ref(),
+			c,
+			d,
+		)?;
+		dleq::verify_proof(
+			Domain::Rfc(Mode::Poprf),
+			composites,
+			tweaked_key.as_ref(),
+			proof,
+		)?;
 
 		let blinds = clients.iter().map(|client| client.blind).collect_array();
 		let evaluation_elements = evaluation_elements
 			.iter()
 			.map(EvaluationElement::as_element);
 
-		internal::batch_finalize::<Cs, N>(inputs, blinds, evaluation_elements, Some(info))
+		Ok(
+			internal::batch_finalize_element::<Cs, N>(blinds, evaluation_elements)
+				.map(UnblindedElement::from),
+		)
 	}
 
 	/// Batch completes evaluations with a combined [`Proof`].
@@ -287,14 +367,59 @@ impl<Cs: CipherSuite> PoprfClient<Cs> {
 		Ic: ExactSizeIterator<Item = &'clients Self>,
 		Ii: ExactSizeIterator<Item = &'inputs [&'inputs [u8]]>,
 		Iee: ExactSizeIterator<Item = &'evaluation_elements EvaluationElement<Cs>>,
+	{
+		if clients.len() != inputs.len() {
+			return Err(Error::Batch);
+		}
+
+		let unblinded_elements = Self::batch_alloc_finalize_element(
+			clients,
+			public_key,
+			evaluation_elements,
+			proof,
+			info,
+		)?;
+
+		internal::batch_alloc_finalize::<Cs>(
+			inputs,
+			unblinded_elements.iter().map(UnblindedElement::as_ref),
+			Some(Info::new(info)?),
+		)
+	}
+
+	/// Batch completes evaluations with a combined [`Proof`] without hashing
+	/// the [`UnblindedElement`]s.
+	///
+	/// See [`finalize_element()`](Self::finalize_element).
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of items in `clients` and
+	///   `evaluation_elements` are zero, don't match or exceed a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InfoLength`] if the provided `info` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfo`] if the provided `info` can never produce a
+	///   valid output.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	#[cfg(feature = "alloc")]
+	pub fn batch_alloc_finalize_element<'clients, 'evaluation_elements, Ic, Iee>(
+		clients: Ic,
+		public_key: &PublicKey<Cs::Group>,
+		evaluation_elements: Iee,
+		proof: &Proof<Cs>,
+		info: &[u8],
+	) -> Result<Vec<UnblindedElement<Cs>>>
+	where
+		Ic: ExactSizeIterator<Item = &'clients Self>,
+		Iee: ExactSizeIterator<Item = &'evaluation_elements EvaluationElement<Cs>>,
 	{
 		let length = clients.len();
 
-		if length == 0
-			|| length != inputs.len()
-			|| length != evaluation_elements.len()
-			|| length > u16::MAX.into()
-		{
+		if length == 0 || length != evaluation_elements.len() || length > u16::MAX.into() {
 			return Err(Error::Batch);
 		}
 
@@ -306,24 +431,28 @@ impl<Cs: CipherSuite> PoprfClient<Cs> {
 			.map(|client| (client.blinded_element.as_ref(), client.blind))
 			.unzip();
 
-		let composites = internal::alloc_compute_composites(
-			Mode::Poprf,
+		let composites = dleq::alloc_compute_composites(
+			Domain::Rfc(Mode::Poprf),
 			length,
 			None,
 			tweaked_key.as_ref(),
 			c.iter().copied(),
 			d.iter().copied(),
 		)?;
-		internal::verify_proof(Mode::Poprf, composites, tweaked_key.as_ref(), proof)?;
+		dleq::verify_proof(
+			Domain::Rfc(Mode::Poprf),
+			composites,
+			tweaked_key.as_ref(),
+			proof,
+		)?;
 
 		let evaluation_elements = c.into_iter().map(ElementWithRepr::as_element);
 
-		internal::batch_alloc_finalize::<Cs>(
-			length,
-			inputs,
-			blinds,
-			evaluation_elements,
-			Some(info),
+		Ok(
+			internal::batch_alloc_finalize_element::<Cs>(blinds, evaluation_elements)
+				.into_iter()
+				.map(UnblindedElement::from)
+				.collect(),
 		)
 	}
 
@@ -334,7 +463,7 @@ impl<Cs: CipherSuite> PoprfClient<Cs> {
 	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
 	/// - [`Error::InvalidInfo`] if the provided `info` can never produce a
 	///   valid output.
-	fn tweaked_key(
+	pub(crate) fn tweaked_key(
 		public_key: &PublicKey<Cs::Group>,
 		info: Info<'_>,
 	) -> Result<PublicKey<Cs::Group>> {
@@ -447,6 +576,17 @@ impl<Cs: CipherSuite> PoprfServer<Cs> {
 		self.key_pair.public_key()
 	}
 
+	/// Returns `t`, the [`SecretKey`](crate::key::SecretKey) tweaked with
+	/// `info`.
+	pub(crate) const fn t(&self) -> NonZeroScalar<Cs> {
+		self.t
+	}
+
+	/// Returns the inverted `t`.
+	pub(crate) const fn t_inverted(&self) -> NonZeroScalar<Cs> {
+		self.t_inverted
+	}
+
 	/// Process the [`BlindedElement`].
 	///
 	/// Corresponds to
@@ -510,16 +650,16 @@ impl<Cs: CipherSuite> PoprfServer<Cs> {
 		let c = evaluation_elements.iter().map(EvaluationElement::as_ref);
 		let d = blinded_elements.iter().map(BlindedElement::as_ref);
 
-		let composites = internal::compute_composites::<_, N>(
-			Mode::Poprf,
+		let composites = dleq::compute_composites::<_, N>(
+			Domain::Rfc(Mode::Poprf),
 			Some(self.t),
 			self.tweaked_key.as_ref(),
 			c,
 			d,
 		)
 		.map_err(Error::into_random::<R>)?;
-		let proof = internal::generate_proof(
-			Mode::Poprf,
+		let proof = dleq::generate_proof(
+			Domain::Rfc(Mode::Poprf),
 			rng,
 			self.t,
 			composites,
@@ -567,8 +707,8 @@ impl<Cs: CipherSuite> PoprfServer<Cs> {
 		);
 		let c = evaluation_elements.iter().map(EvaluationElement::as_ref);
 
-		let composites = internal::alloc_compute_composites(
-			Mode::Poprf,
+		let composites = dleq::alloc_compute_composites(
+			Domain::Rfc(Mode::Poprf),
 			blinded_elements_length,
 			Some(self.t),
 			self.tweaked_key.as_ref(),
@@ -576,8 +716,8 @@ impl<Cs: CipherSuite> PoprfServer<Cs> {
 			d.into_iter(),
 		)
 		.map_err(Error::into_random::<R>)?;
-		let proof = internal::generate_proof(
-			Mode::Poprf,
+		let proof = dleq::generate_proof(
+			Domain::Rfc(Mode::Poprf),
 			rng,
 			self.t,
 			composites,
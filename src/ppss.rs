@@ -0,0 +1,428 @@
+//! Password-protected secret sharing (PPSS) over the [threshold](crate::threshold)
+//! VOPRF, following
+//! [TOPPSS](https://eprint.iacr.org/2017/363).
+//!
+//! A secret is backed up across `count` servers and can be recovered with only
+//! the password by contacting any `threshold` of them. Fewer than `threshold`
+//! colluding servers learn nothing about the password or the secret, not even
+//! by guessing passwords offline.
+//!
+//! 1. The client creates the [`KeyShare`]s for all servers, the public
+//!    [`PpssRecord`] and the secret with [`setup()`]. Each server is given its
+//!    [`KeyShare`] and a copy of the [`PpssRecord`].
+//! 2. To recover the secret, the client blinds the password with
+//!    [`PpssClient::blind()`] and sends the same [`BlindedElement`] to the
+//!    servers.
+//! 3. Each server responds with
+//!    [`ThresholdServer::verifiable_blind_evaluate()`].
+//! 4. The client recovers the secret with [`PpssClient::finalize()`].
+//!
+//! Every [`EvaluationShare`] is verified against its [`PublicKeyShare`] before
+//! recombination and the result is authenticated against the commitment in
+//! the [`PpssRecord`]. Shares of misbehaving servers are skipped and reported,
+//! which makes a wrong password ([`Error::Password`]) distinguishable from
+//! malicious servers ([`Error::Proof`]).
+//!
+//! The [`PpssRecord`] is public but must be authentic, e.g. stored by the
+//! client or compared between copies retrieved from multiple servers.
+//!
+//! [`ThresholdServer::verifiable_blind_evaluate()`]: crate::threshold::ThresholdServer::verifiable_blind_evaluate
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter;
+use core::num::NonZeroU16;
+
+use digest::{FixedOutput, Output, OutputSizeUser, Update};
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+use zeroize::ZeroizeOnDrop;
+
+use crate::cipher_suite::{CipherSuite, Element, ElementLength};
+use crate::common::BlindedElement;
+use crate::error::{Error, Result};
+use crate::internal;
+use crate::key::{KeyPair, PublicKey};
+use crate::threshold::{self, EvaluationShare, KeyShare, PublicKeyShare};
+use crate::voprf::{VoprfBlindResult, VoprfClient, VoprfServer};
+
+/// Label to derive the commitment in a [`PpssRecord`].
+const COMMITMENT_LABEL: &[u8] = b"PPSS-Commitment-";
+/// Label to derive the secret.
+const SECRET_LABEL: &[u8] = b"PPSS-Secret-";
+
+/// PPSS client.
+pub struct PpssClient<Cs: CipherSuite> {
+	/// The underlying [`VoprfClient`].
+	client: VoprfClient<Cs>,
+}
+
+/// Public parameters required to recover the secret.
+pub struct PpssRecord<Cs: CipherSuite> {
+	/// The number of [`EvaluationShare`]s required to recover the secret.
+	threshold: u16,
+	/// The [`PublicKeyShare`]s of all servers.
+	public_key_shares: Vec<PublicKeyShare<Cs::Group>>,
+	/// The commitment to the secret.
+	commitment: Output<Cs::Hash>,
+}
+
+/// Returned from [`setup()`].
+pub struct PpssSetup<Cs: CipherSuite> {
+	/// The [`KeyShare`]s to be given to each server.
+	pub key_shares: Vec<KeyShare<Cs::Group>>,
+	/// The [`PpssRecord`] to be stored by each server.
+	pub record: PpssRecord<Cs>,
+	/// The secret.
+	pub secret: Output<Cs::Hash>,
+}
+
+/// Returned from [`PpssClient::blind()`].
+pub struct PpssBlindResult<Cs: CipherSuite> {
+	/// The [`PpssClient`] to be used with [`PpssClient::finalize()`].
+	pub client: PpssClient<Cs>,
+	/// The [`BlindedElement`] to be sent to all servers.
+	pub blinded_element: BlindedElement<Cs>,
+}
+
+/// Returned from [`PpssClient::finalize()`].
+pub struct PpssRecoverResult<Cs: CipherSuite> {
+	/// The recovered secret.
+	pub secret: Output<Cs::Hash>,
+	/// Indices of [`EvaluationShare`]s that were skipped because their
+	/// [`Proof`](crate::Proof) is missing or invalid or their index is
+	/// unknown.
+	pub invalid_shares: Vec<NonZeroU16>,
+}
+
+impl<Cs: CipherSuite> PpssClient<Cs> {
+	/// Blinds the provided `password`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the provided `password` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the `password` can never produce a valid
+	///   output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind<R>(rng: &mut R, password: &[u8]) -> Result<PpssBlindResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let VoprfBlindResult {
+			client,
+			blinded_element,
+		} = VoprfClient::blind(rng, &[password])?;
+
+		Ok(PpssBlindResult {
+			client: Self { client },
+			blinded_element,
+		})
+	}
+
+	/// Verifies the [`EvaluationShare`]s, combines all valid ones and
+	/// recovers the secret.
+	///
+	/// The `password` must be the same as passed to [`PpssClient::blind()`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::Proof`] if fewer than `threshold` valid `shares` remain
+	///   because some were invalid.
+	/// - [`Error::Threshold`] if fewer than `threshold` `shares` were provided,
+	///   `shares` contain duplicate indices or don't combine to a valid
+	///   result.
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InputLength`] if the provided `password` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::Password`] if all used `shares` were valid but the
+	///   `password` is wrong.
+	pub fn finalize(
+		&self,
+		record: &PpssRecord<Cs>,
+		password: &[u8],
+		shares: &[EvaluationShare<Cs>],
+	) -> Result<PpssRecoverResult<Cs>> {
+		let mut indices = Vec::with_capacity(shares.len());
+		let mut elements: Vec<Element<Cs>> = Vec::with_capacity(shares.len());
+		let mut invalid_shares = Vec::new();
+
+		for share in shares {
+			let Some(public_key_share) = record
+				.public_key_shares
+				.iter()
+				.find(|public_key_share| public_key_share.index() == share.index)
+			else {
+				invalid_shares.push(share.index);
+				continue;
+			};
+
+			match share.verify(public_key_share, self.client.blinded_element()) {
+				Ok(()) => {
+					indices.push(share.index);
+					elements.push((*share.evaluation_element.as_element()).into());
+				}
+				Err(Error::Proof) => invalid_shares.push(share.index),
+				Err(error) => return Err(error),
+			}
+		}
+
+		if indices.len() < record.threshold.into() {
+			return Err(if invalid_shares.is_empty() {
+				Error::Threshold
+			} else {
+				Error::Proof
+			});
+		}
+
+		let evaluation_element =
+			threshold::interpolate::<Cs::Group, _>(&indices, elements.into_iter())?;
+		let unblinded_elements = internal::batch_finalize_element::<Cs, 1>(
+			[self.client.blind()],
+			iter::once(&evaluation_element),
+		);
+		let [output] =
+			internal::batch_finalize::<Cs, 1>(&[&[password]], unblinded_elements.iter(), None)?;
+
+		if derive::<Cs>(&output, COMMITMENT_LABEL) != record.commitment {
+			return Err(Error::Password);
+		}
+
+		Ok(PpssRecoverResult {
+			secret: derive::<Cs>(&output, SECRET_LABEL),
+			invalid_shares,
+		})
+	}
+}
+
+impl<Cs: CipherSuite> PpssRecord<Cs> {
+	/// Returns the number of [`EvaluationShare`]s required to recover the
+	/// secret.
+	#[must_use]
+	pub const fn threshold(&self) -> u16 {
+		self.threshold
+	}
+
+	/// Returns the [`PublicKeyShare`]s of all servers.
+	#[must_use]
+	pub const fn public_key_shares(&self) -> &[PublicKeyShare<Cs::Group>] {
+		self.public_key_shares.as_slice()
+	}
+
+	/// Deserializes the provided `repr` to a [`PpssRecord`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let (threshold, repr) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+		let (commitment, repr) = repr
+			.split_at_checked(<Cs::Hash as OutputSizeUser>::OutputSize::USIZE)
+			.ok_or(Error::FromRepr)?;
+		let chunks = repr.chunks_exact(size_of::<u16>() + ElementLength::<Cs>::USIZE);
+
+		if !chunks.remainder().is_empty() {
+			return Err(Error::FromRepr);
+		}
+
+		let public_key_shares = chunks
+			.map(|chunk| {
+				let (index, public_key) = chunk.split_first_chunk().ok_or(Error::FromRepr)?;
+				let index = NonZeroU16::new(u16::from_be_bytes(*index)).ok_or(Error::FromRepr)?;
+
+				Ok(PublicKeyShare::new(
+					index,
+					PublicKey::from_repr(public_key)?,
+				))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		let threshold = u16::from_be_bytes(*threshold);
+
+		if threshold == 0 || usize::from(threshold) > public_key_shares.len() {
+			return Err(Error::FromRepr);
+		}
+
+		Ok(Self {
+			threshold,
+			public_key_shares,
+			commitment: commitment.try_into().map_err(|_| Error::FromRepr)?,
+		})
+	}
+
+	/// Serializes this [`PpssRecord`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.threshold
+			.to_be_bytes()
+			.into_iter()
+			.chain(self.commitment.iter().copied())
+			.chain(self.public_key_shares.iter().flat_map(|public_key_share| {
+				public_key_share
+					.index()
+					.get()
+					.to_be_bytes()
+					.into_iter()
+					.chain(public_key_share.public_key().as_repr().iter().copied())
+			}))
+			.collect()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PpssClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PpssClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PpssClient")
+			.field("client", &self.client)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PpssClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PpssClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client.eq(&other.client)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PpssClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PpssRecord<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			threshold: self.threshold,
+			public_key_shares: self.public_key_shares.clone(),
+			commitment: self.commitment.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PpssRecord<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PpssRecord")
+			.field("threshold", &self.threshold)
+			.field("public_key_shares", &self.public_key_shares)
+			.field("commitment", &self.commitment)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PpssRecord<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PpssRecord<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.threshold.eq(&other.threshold)
+			&& self.public_key_shares.eq(&other.public_key_shares)
+			&& self.commitment.eq(&other.commitment)
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PpssSetup<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PpssSetup")
+			.field("key_shares", &self.key_shares)
+			.field("record", &self.record)
+			.field("secret", &self.secret)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PpssSetup<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PpssBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PpssBlindResult")
+			.field("client", &self.client)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PpssBlindResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PpssRecoverResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PpssRecoverResult")
+			.field("secret", &self.secret)
+			.field("invalid_shares", &self.invalid_shares)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PpssRecoverResult<Cs> {}
+
+/// Protects a new secret with the provided `password` by creating `count`
+/// [`KeyShare`]s, any `threshold` of which can recover it.
+///
+/// The [`SecretKey`](crate::key::SecretKey) the [`KeyShare`]s are split from
+/// is only used during setup and isn't returned.
+///
+/// # Errors
+///
+/// - [`Error::Threshold`] if `threshold` is zero or larger than `count`.
+/// - [`Error::InputLength`] if the provided `password` exceeds a length of
+///   [`u16::MAX`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and
+///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+/// - [`Error::InvalidInput`] if the `password` can never produce a valid
+///   output.
+/// - [`Error::Random`] if the provided `rng` fails.
+pub fn setup<Cs, R>(
+	rng: &mut R,
+	password: &[u8],
+	threshold: u16,
+	count: u16,
+) -> Result<PpssSetup<Cs>, Error<R::Error>>
+where
+	Cs: CipherSuite,
+	R: ?Sized + TryCryptoRng,
+{
+	let key_pair = KeyPair::<Cs::Group>::generate(rng).map_err(Error::Random)?;
+	let key_shares = threshold::split(rng, key_pair.secret_key(), threshold, count)?;
+	let output = VoprfServer::<Cs>::from_key_pair(key_pair)
+		.evaluate(&[password])
+		.map_err(Error::into_random::<R>)?;
+
+	let public_key_shares = key_shares.iter().map(KeyShare::public_key_share).collect();
+
+	Ok(PpssSetup {
+		key_shares,
+		record: PpssRecord {
+			threshold,
+			public_key_shares,
+			commitment: derive::<Cs>(&output, COMMITMENT_LABEL),
+		},
+		secret: derive::<Cs>(&output, SECRET_LABEL),
+	})
+}
+
+/// Derives a value from the VOPRF `output` with the provided `label`.
+fn derive<Cs: CipherSuite>(output: &Output<Cs::Hash>, label: &[u8]) -> Output<Cs::Hash> {
+	Cs::Hash::default()
+		.chain(output)
+		.chain(label)
+		.chain(&*Cs::ID)
+		.finalize_fixed()
+}
@@ -0,0 +1,1174 @@
+//! [Privacy Pass](https://www.rfc-editor.org/rfc/rfc9578.html) privately
+//! verifiable token issuance.
+//!
+//! Implements token type `0x0001` as specified in
+//! [RFC 9578 § 5](https://www.rfc-editor.org/rfc/rfc9578.html#section-5), which
+//! uses [VOPRF](crate::voprf) with [`NistP384`].
+//!
+//! 1. The client requests a token with [`PrivacyPassClient::request()`] and
+//!    sends the [`TokenRequest`] to the issuer.
+//! 2. The issuer responds with [`PrivacyPassIssuer::issue()`] and sends the
+//!    [`TokenResponse`] back to the client.
+//! 3. The client computes the [`Token`] with [`PrivacyPassClient::finalize()`].
+//! 4. The issuer verifies the [`Token`] on redemption with
+//!    [`PrivacyPassIssuer::redeem()`], rejecting double spending through a
+//!    [`NonceStore`].
+//!
+//! With the `alloc` crate feature, many tokens can be issued in a single round
+//! trip with only one [`Proof`] as specified in the
+//! [batched tokens draft](https://datatracker.ietf.org/doc/draft-ietf-privacypass-batched-tokens/)
+//! through [`PrivacyPassBatchClient`] and [`PrivacyPassIssuer::batch_issue()`].
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeSet;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+
+use digest::{Output, OutputSizeUser};
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::NistP384;
+use crate::cipher_suite::{CipherSuite, ElementLength, ScalarLength};
+#[cfg(feature = "alloc")]
+use crate::common::BatchAllocBlindEvaluateResult;
+use crate::common::{BlindEvaluateResult, BlindedElement, EvaluationElement, Proof};
+use crate::error::{Error, Result};
+use crate::key::{KeyPair, PublicKey};
+use crate::util::{CollectArray, I2osp};
+#[cfg(feature = "alloc")]
+use crate::voprf::VoprfBatchAllocBlindResult;
+use crate::voprf::{VoprfClient, VoprfServer};
+
+/// The Privacy Pass token type implemented by this module.
+pub const TOKEN_TYPE: u16 = 0x0001;
+
+/// Length of a serialized [`BlindedElement`] or [`EvaluationElement`].
+const ELEMENT_LENGTH: usize = ElementLength::<NistP384>::USIZE;
+/// Length of a serialized [`Proof`].
+const PROOF_LENGTH: usize = ScalarLength::<NistP384>::USIZE * 2;
+/// Length of a serialized [`TokenRequest`].
+const TOKEN_REQUEST_LENGTH: usize = 2 + 1 + ELEMENT_LENGTH;
+/// Length of a serialized [`TokenResponse`].
+const TOKEN_RESPONSE_LENGTH: usize = ELEMENT_LENGTH + PROOF_LENGTH;
+/// Length of a serialized [`Token`].
+const TOKEN_LENGTH: usize =
+	2 + 32 + 32 + 32 + <<NistP384 as CipherSuite>::Hash as OutputSizeUser>::OutputSize::USIZE;
+
+/// Privacy Pass client.
+pub struct PrivacyPassClient {
+	/// The underlying [`VoprfClient`].
+	client: VoprfClient<NistP384>,
+	/// The issuers [`PublicKey`].
+	public_key: PublicKey<<NistP384 as CipherSuite>::Group>,
+	/// `nonce`.
+	nonce: [u8; 32],
+	/// `challenge_digest`.
+	challenge_digest: [u8; 32],
+	/// `token_key_id`.
+	token_key_id: [u8; 32],
+}
+
+/// Privacy Pass issuer.
+pub struct PrivacyPassIssuer {
+	/// The underlying [`VoprfServer`].
+	server: VoprfServer<NistP384>,
+	/// `token_key_id`.
+	token_key_id: [u8; 32],
+}
+
+/// Returned by [`PrivacyPassClient::request()`].
+pub struct PrivacyPassRequestResult {
+	/// The [`PrivacyPassClient`] to be used with
+	/// [`PrivacyPassClient::finalize()`].
+	pub client: PrivacyPassClient,
+	/// The [`TokenRequest`] to be sent to the issuer.
+	pub token_request: TokenRequest,
+}
+
+/// Sent from the client to the issuer.
+///
+/// Corresponds to
+/// [`TokenRequest` in RFC 9578 § 5.1](https://www.rfc-editor.org/rfc/rfc9578.html#section-5.1).
+pub struct TokenRequest {
+	/// `truncated_token_key_id`.
+	truncated_token_key_id: u8,
+	/// `blinded_msg`.
+	blinded_element: BlindedElement<NistP384>,
+}
+
+/// Sent from the issuer to the client.
+///
+/// Corresponds to
+/// [`TokenResponse` in RFC 9578 § 5.2](https://www.rfc-editor.org/rfc/rfc9578.html#section-5.2).
+pub struct TokenResponse {
+	/// `evaluate_msg`.
+	evaluation_element: EvaluationElement<NistP384>,
+	/// `evaluate_proof`.
+	proof: Proof<NistP384>,
+}
+
+/// A Privacy Pass token.
+///
+/// Corresponds to
+/// [`Token` in RFC 9578 § 5.3](https://www.rfc-editor.org/rfc/rfc9578.html#section-5.3).
+pub struct Token {
+	/// `nonce`.
+	nonce: [u8; 32],
+	/// `challenge_digest`.
+	challenge_digest: [u8; 32],
+	/// `token_key_id`.
+	token_key_id: [u8; 32],
+	/// `authenticator`.
+	authenticator: Output<<NistP384 as CipherSuite>::Hash>,
+}
+
+/// Batched Privacy Pass client.
+#[cfg(feature = "alloc")]
+pub struct PrivacyPassBatchClient {
+	/// The underlying [`VoprfClient`]s.
+	clients: Vec<VoprfClient<NistP384>>,
+	/// The issuers [`PublicKey`].
+	public_key: PublicKey<<NistP384 as CipherSuite>::Group>,
+	/// `nonce` of each token.
+	nonces: Vec<[u8; 32]>,
+	/// `challenge_digest`.
+	challenge_digest: [u8; 32],
+	/// `token_key_id`.
+	token_key_id: [u8; 32],
+}
+
+/// Returned by [`PrivacyPassBatchClient::request()`].
+#[cfg(feature = "alloc")]
+pub struct PrivacyPassBatchRequestResult {
+	/// The [`PrivacyPassBatchClient`] to be used with
+	/// [`PrivacyPassBatchClient::finalize()`].
+	pub client: PrivacyPassBatchClient,
+	/// The [`BatchTokenRequest`] to be sent to the issuer.
+	pub batch_token_request: BatchTokenRequest,
+}
+
+/// Sent from the client to the issuer to request many [`Token`]s at once.
+///
+/// Corresponds to `BatchTokenRequest` in the
+/// [batched tokens draft](https://datatracker.ietf.org/doc/draft-ietf-privacypass-batched-tokens/),
+/// with the number of [`BlindedElement`]s encoded as a `u16` prefix.
+#[cfg(feature = "alloc")]
+pub struct BatchTokenRequest {
+	/// `truncated_token_key_id`.
+	truncated_token_key_id: u8,
+	/// `blinded_elements`.
+	blinded_elements: Vec<BlindedElement<NistP384>>,
+}
+
+/// Sent from the issuer to the client in response to a [`BatchTokenRequest`].
+///
+/// Corresponds to `BatchTokenResponse` in the
+/// [batched tokens draft](https://datatracker.ietf.org/doc/draft-ietf-privacypass-batched-tokens/),
+/// with the number of [`EvaluationElement`]s encoded as a `u16` prefix.
+#[cfg(feature = "alloc")]
+pub struct BatchTokenResponse {
+	/// `evaluated_elements`.
+	evaluation_elements: Vec<EvaluationElement<NistP384>>,
+	/// `evaluated_proof`.
+	proof: Proof<NistP384>,
+}
+
+/// Storage of redeemed [`Token`] nonces used to prevent double spending.
+pub trait NonceStore {
+	/// Records the provided `nonce` as spent. Returns `false` if the `nonce`
+	/// was already spent.
+	fn insert(&mut self, nonce: &[u8; 32]) -> bool;
+}
+
+/// In-memory [`NonceStore`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MemoryNonceStore(BTreeSet<[u8; 32]>);
+
+impl PrivacyPassClient {
+	/// Creates a [`TokenRequest`] for the provided serialized `TokenChallenge`
+	/// against the issuers [`PublicKey`].
+	///
+	/// Corresponds to
+	/// [Client-to-Issuer Request in RFC 9578 § 5.1](https://www.rfc-editor.org/rfc/rfc9578.html#section-5.1).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidInput`] if the token input can never produce a valid
+	///   output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn request<R>(
+		rng: &mut R,
+		public_key: &PublicKey<<NistP384 as CipherSuite>::Group>,
+		challenge: &[u8],
+	) -> Result<PrivacyPassRequestResult, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let mut nonce = [0; 32];
+		rng.try_fill_bytes(&mut nonce).map_err(Error::Random)?;
+		let challenge_digest = Sha256::digest(challenge).into();
+		let token_key_id = token_key_id(public_key);
+
+		let blind_result =
+			VoprfClient::blind(rng, &token_input(&nonce, &challenge_digest, &token_key_id))?;
+
+		Ok(PrivacyPassRequestResult {
+			client: Self {
+				client: blind_result.client,
+				public_key: public_key.clone(),
+				nonce,
+				challenge_digest,
+				token_key_id,
+			},
+			token_request: TokenRequest {
+				truncated_token_key_id: truncated_token_key_id(&token_key_id),
+				blinded_element: blind_result.blinded_element,
+			},
+		})
+	}
+
+	/// Verifies the [`TokenResponse`] and computes the [`Token`].
+	///
+	/// Corresponds to
+	/// [Finalization in RFC 9578 § 5.3](https://www.rfc-editor.org/rfc/rfc9578.html#section-5.3).
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn finalize(&self, token_response: &TokenResponse) -> Result<Token> {
+		let authenticator = self.client.finalize(
+			&self.public_key,
+			&token_input(&self.nonce, &self.challenge_digest, &self.token_key_id),
+			&token_response.evaluation_element,
+			&token_response.proof,
+		)?;
+
+		Ok(Token {
+			nonce: self.nonce,
+			challenge_digest: self.challenge_digest,
+			token_key_id: self.token_key_id,
+			authenticator,
+		})
+	}
+}
+
+impl PrivacyPassIssuer {
+	/// Creates a new [`PrivacyPassIssuer`] from the provided [`KeyPair`].
+	#[must_use]
+	pub fn from_key_pair(key_pair: KeyPair<<NistP384 as CipherSuite>::Group>) -> Self {
+		let token_key_id = token_key_id(key_pair.public_key());
+
+		Self {
+			server: VoprfServer::from_key_pair(key_pair),
+			token_key_id,
+		}
+	}
+
+	/// Returns the [`PublicKey`] to be distributed to clients.
+	#[must_use]
+	pub const fn public_key(&self) -> &PublicKey<<NistP384 as CipherSuite>::Group> {
+		self.server.public_key()
+	}
+
+	/// Returns the `token_key_id`, the SHA-256 digest of the serialized
+	/// [`PublicKey`].
+	#[must_use]
+	pub const fn token_key_id(&self) -> &[u8; 32] {
+		&self.token_key_id
+	}
+
+	/// Processes the [`TokenRequest`].
+	///
+	/// Corresponds to
+	/// [Issuer-to-Client Response in RFC 9578 § 5.2](https://www.rfc-editor.org/rfc/rfc9578.html#section-5.2).
+	///
+	/// # Errors
+	///
+	/// - [`Error::Token`] if the [`TokenRequest`] is for a different
+	///   `token_key_id`.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn issue<R>(
+		&self,
+		rng: &mut R,
+		token_request: &TokenRequest,
+	) -> Result<TokenResponse, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		if token_request.truncated_token_key_id != truncated_token_key_id(&self.token_key_id) {
+			return Err(Error::Token);
+		}
+
+		let BlindEvaluateResult {
+			evaluation_element,
+			proof,
+		} = self
+			.server
+			.blind_evaluate(rng, &token_request.blinded_element)?;
+
+		Ok(TokenResponse {
+			evaluation_element,
+			proof,
+		})
+	}
+
+	/// Processes the [`BatchTokenRequest`] computing a single combined
+	/// [`Proof`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::Token`] if the [`BatchTokenRequest`] is for a different
+	///   `token_key_id`.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	#[cfg(feature = "alloc")]
+	pub fn batch_issue<R>(
+		&self,
+		rng: &mut R,
+		batch_token_request: &BatchTokenRequest,
+	) -> Result<BatchTokenResponse, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		if batch_token_request.truncated_token_key_id != truncated_token_key_id(&self.token_key_id)
+		{
+			return Err(Error::Token);
+		}
+
+		let BatchAllocBlindEvaluateResult {
+			evaluation_elements,
+			proof,
+		} = self
+			.server
+			.batch_alloc_blind_evaluate(rng, batch_token_request.blinded_elements.iter())?;
+
+		Ok(BatchTokenResponse {
+			evaluation_elements,
+			proof,
+		})
+	}
+
+	/// Verifies the [`Token`] for the provided serialized `TokenChallenge` and
+	/// records its nonce in the [`NonceStore`].
+	///
+	/// Corresponds to
+	/// [Token Verification in RFC 9578 § 5.4](https://www.rfc-editor.org/rfc/rfc9578.html#section-5.4).
+	///
+	/// # Errors
+	///
+	/// - [`Error::Token`] if the [`Token`] is invalid, was issued under a
+	///   different key or for a different `TokenChallenge`.
+	/// - [`Error::DoubleSpend`] if the [`Token`] was already redeemed.
+	pub fn redeem<S>(&self, token: &Token, challenge: &[u8], store: &mut S) -> Result<()>
+	where
+		S: ?Sized + NonceStore,
+	{
+		let challenge_digest: [u8; 32] = Sha256::digest(challenge).into();
+
+		if token.challenge_digest != challenge_digest || token.token_key_id != self.token_key_id {
+			return Err(Error::Token);
+		}
+
+		let expected = self
+			.server
+			.evaluate(&token.token_input())
+			.map_err(|_| Error::Token)?;
+
+		if !bool::from(expected.as_slice().ct_eq(token.authenticator.as_slice())) {
+			return Err(Error::Token);
+		}
+
+		if store.insert(&token.nonce) {
+			Ok(())
+		} else {
+			Err(Error::DoubleSpend)
+		}
+	}
+}
+
+impl TokenRequest {
+	/// Returns the `truncated_token_key_id`.
+	#[must_use]
+	pub const fn truncated_token_key_id(&self) -> u8 {
+		self.truncated_token_key_id
+	}
+
+	/// Returns the [`BlindedElement`].
+	#[must_use]
+	pub const fn blinded_element(&self) -> &BlindedElement<NistP384> {
+		&self.blinded_element
+	}
+
+	/// Deserializes the provided `repr` to a [`TokenRequest`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails or the token type
+	/// isn't [`TOKEN_TYPE`].
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		if repr.len() != TOKEN_REQUEST_LENGTH {
+			return Err(Error::FromRepr);
+		}
+
+		let (token_type, repr) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+		token_type_from_repr(*token_type)?;
+		let (truncated_token_key_id, blinded_element) =
+			repr.split_first().ok_or(Error::FromRepr)?;
+
+		Ok(Self {
+			truncated_token_key_id: *truncated_token_key_id,
+			blinded_element: BlindedElement::from_repr(blinded_element)?,
+		})
+	}
+
+	/// Serializes this [`TokenRequest`].
+	#[must_use]
+	pub fn to_repr(&self) -> [u8; TOKEN_REQUEST_LENGTH] {
+		TOKEN_TYPE
+			.i2osp()
+			.into_iter()
+			.chain([self.truncated_token_key_id])
+			.chain(self.blinded_element.as_repr().iter().copied())
+			.collect_array()
+	}
+}
+
+impl TokenResponse {
+	/// Returns the [`EvaluationElement`].
+	#[must_use]
+	pub const fn evaluation_element(&self) -> &EvaluationElement<NistP384> {
+		&self.evaluation_element
+	}
+
+	/// Returns the [`Proof`].
+	#[must_use]
+	pub const fn proof(&self) -> &Proof<NistP384> {
+		&self.proof
+	}
+
+	/// Deserializes the provided `repr` to a [`TokenResponse`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		if repr.len() != TOKEN_RESPONSE_LENGTH {
+			return Err(Error::FromRepr);
+		}
+
+		let (evaluation_element, proof) = repr
+			.split_at_checked(ELEMENT_LENGTH)
+			.ok_or(Error::FromRepr)?;
+
+		Ok(Self {
+			evaluation_element: EvaluationElement::from_repr(evaluation_element)?,
+			proof: Proof::from_repr(proof)?,
+		})
+	}
+
+	/// Serializes this [`TokenResponse`].
+	#[must_use]
+	pub fn to_repr(&self) -> [u8; TOKEN_RESPONSE_LENGTH] {
+		self.evaluation_element
+			.as_repr()
+			.iter()
+			.copied()
+			.chain(self.proof.to_repr())
+			.collect_array()
+	}
+}
+
+impl Token {
+	/// Returns the `nonce`.
+	#[must_use]
+	pub const fn nonce(&self) -> &[u8; 32] {
+		&self.nonce
+	}
+
+	/// Returns the `challenge_digest`, the SHA-256 digest of the serialized
+	/// `TokenChallenge`.
+	#[must_use]
+	pub const fn challenge_digest(&self) -> &[u8; 32] {
+		&self.challenge_digest
+	}
+
+	/// Returns the `token_key_id`.
+	#[must_use]
+	pub const fn token_key_id(&self) -> &[u8; 32] {
+		&self.token_key_id
+	}
+
+	/// Returns the `authenticator`.
+	#[must_use]
+	pub const fn authenticator(&self) -> &Output<<NistP384 as CipherSuite>::Hash> {
+		&self.authenticator
+	}
+
+	/// Deserializes the provided `repr` to a [`Token`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails or the token type
+	/// isn't [`TOKEN_TYPE`].
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		if repr.len() != TOKEN_LENGTH {
+			return Err(Error::FromRepr);
+		}
+
+		let (token_type, repr) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+		token_type_from_repr(*token_type)?;
+		let (nonce, repr) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+		let (challenge_digest, repr) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+		let (token_key_id, authenticator) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+
+		Ok(Self {
+			nonce: *nonce,
+			challenge_digest: *challenge_digest,
+			token_key_id: *token_key_id,
+			authenticator: authenticator.try_into().map_err(|_| Error::FromRepr)?,
+		})
+	}
+
+	/// Serializes this [`Token`].
+	#[must_use]
+	pub fn to_repr(&self) -> [u8; TOKEN_LENGTH] {
+		self.token_input()
+			.into_iter()
+			.flatten()
+			.copied()
+			.chain(self.authenticator.iter().copied())
+			.collect_array()
+	}
+
+	/// Returns the `token_input`.
+	fn token_input(&self) -> [&[u8]; 4] {
+		token_input(&self.nonce, &self.challenge_digest, &self.token_key_id)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl PrivacyPassBatchClient {
+	/// Creates a [`BatchTokenRequest`] for `count` [`Token`]s for the provided
+	/// serialized `TokenChallenge` against the issuers [`PublicKey`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if `count` is zero.
+	/// - [`Error::InvalidInput`] if a token input can never produce a valid
+	///   output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn request<R>(
+		rng: &mut R,
+		public_key: &PublicKey<<NistP384 as CipherSuite>::Group>,
+		challenge: &[u8],
+		count: u16,
+	) -> Result<PrivacyPassBatchRequestResult, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		if count == 0 {
+			return Err(Error::Batch);
+		}
+
+		let mut nonces = vec![[0; 32]; count.into()];
+
+		for nonce in &mut nonces {
+			rng.try_fill_bytes(nonce).map_err(Error::Random)?;
+		}
+
+		let challenge_digest = Sha256::digest(challenge).into();
+		let token_key_id = token_key_id(public_key);
+
+		let inputs: Vec<_> = nonces
+			.iter()
+			.map(|nonce| token_input(nonce, &challenge_digest, &token_key_id))
+			.collect();
+		let VoprfBatchAllocBlindResult {
+			clients,
+			blinded_elements,
+		} = VoprfClient::batch_alloc_blind(rng, inputs.iter().map(<[_; 4]>::as_slice))?;
+
+		Ok(PrivacyPassBatchRequestResult {
+			client: Self {
+				clients,
+				public_key: public_key.clone(),
+				nonces,
+				challenge_digest,
+				token_key_id,
+			},
+			batch_token_request: BatchTokenRequest {
+				truncated_token_key_id: truncated_token_key_id(&token_key_id),
+				blinded_elements,
+			},
+		})
+	}
+
+	/// Verifies the [`BatchTokenResponse`] and computes all [`Token`]s.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of [`EvaluationElement`]s doesn't
+	///   match the number of requested [`Token`]s.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn finalize(&self, batch_token_response: &BatchTokenResponse) -> Result<Vec<Token>> {
+		let inputs: Vec<_> = self
+			.nonces
+			.iter()
+			.map(|nonce| token_input(nonce, &self.challenge_digest, &self.token_key_id))
+			.collect();
+		let authenticators = VoprfClient::batch_alloc_finalize(
+			self.clients.iter(),
+			&self.public_key,
+			inputs.iter().map(<[_; 4]>::as_slice),
+			batch_token_response.evaluation_elements.iter(),
+			&batch_token_response.proof,
+		)?;
+
+		Ok(self
+			.nonces
+			.iter()
+			.zip(authenticators)
+			.map(|(nonce, authenticator)| Token {
+				nonce: *nonce,
+				challenge_digest: self.challenge_digest,
+				token_key_id: self.token_key_id,
+				authenticator,
+			})
+			.collect())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl BatchTokenRequest {
+	/// Returns the `truncated_token_key_id`.
+	#[must_use]
+	pub const fn truncated_token_key_id(&self) -> u8 {
+		self.truncated_token_key_id
+	}
+
+	/// Returns the [`BlindedElement`]s.
+	#[must_use]
+	pub fn blinded_elements(&self) -> &[BlindedElement<NistP384>] {
+		&self.blinded_elements
+	}
+
+	/// Deserializes the provided `repr` to a [`BatchTokenRequest`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails, the token type
+	/// isn't [`TOKEN_TYPE`] or no [`BlindedElement`]s are present.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let (token_type, repr) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+		token_type_from_repr(*token_type)?;
+		let (truncated_token_key_id, repr) = repr.split_first().ok_or(Error::FromRepr)?;
+		let (blinded_elements, repr) = elements_from_repr(repr, BlindedElement::from_repr)?;
+
+		if !repr.is_empty() {
+			return Err(Error::FromRepr);
+		}
+
+		Ok(Self {
+			truncated_token_key_id: *truncated_token_key_id,
+			blinded_elements,
+		})
+	}
+
+	/// Serializes this [`BatchTokenRequest`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Batch`] if there are more than [`u16::MAX`]
+	/// [`BlindedElement`]s.
+	pub fn to_repr(&self) -> Result<Vec<u8>> {
+		Ok(TOKEN_TYPE
+			.i2osp()
+			.into_iter()
+			.chain([self.truncated_token_key_id])
+			.chain(count_i2osp(self.blinded_elements.len())?)
+			.chain(
+				self.blinded_elements
+					.iter()
+					.flat_map(|blinded_element| blinded_element.as_repr().iter().copied()),
+			)
+			.collect())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl BatchTokenResponse {
+	/// Returns the [`EvaluationElement`]s.
+	#[must_use]
+	pub fn evaluation_elements(&self) -> &[EvaluationElement<NistP384>] {
+		&self.evaluation_elements
+	}
+
+	/// Returns the [`Proof`].
+	#[must_use]
+	pub const fn proof(&self) -> &Proof<NistP384> {
+		&self.proof
+	}
+
+	/// Deserializes the provided `repr` to a [`BatchTokenResponse`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails or no
+	/// [`EvaluationElement`]s are present.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let (evaluation_elements, proof) = elements_from_repr(repr, EvaluationElement::from_repr)?;
+
+		if proof.len() != PROOF_LENGTH {
+			return Err(Error::FromRepr);
+		}
+
+		Ok(Self {
+			evaluation_elements,
+			proof: Proof::from_repr(proof)?,
+		})
+	}
+
+	/// Serializes this [`BatchTokenResponse`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Batch`] if there are more than [`u16::MAX`]
+	/// [`EvaluationElement`]s.
+	pub fn to_repr(&self) -> Result<Vec<u8>> {
+		Ok(count_i2osp(self.evaluation_elements.len())?
+			.into_iter()
+			.chain(
+				self.evaluation_elements
+					.iter()
+					.flat_map(|evaluation_element| evaluation_element.as_repr().iter().copied()),
+			)
+			.chain(self.proof.to_repr())
+			.collect())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl MemoryNonceStore {
+	/// Creates an empty [`MemoryNonceStore`].
+	#[must_use]
+	pub const fn new() -> Self {
+		Self(BTreeSet::new())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl NonceStore for MemoryNonceStore {
+	fn insert(&mut self, nonce: &[u8; 32]) -> bool {
+		self.0.insert(*nonce)
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Clone for PrivacyPassClient {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			public_key: self.public_key.clone(),
+			nonce: self.nonce,
+			challenge_digest: self.challenge_digest,
+			token_key_id: self.token_key_id,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for PrivacyPassClient {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PrivacyPassClient")
+			.field("client", &self.client)
+			.field("public_key", &self.public_key)
+			.field("nonce", &self.nonce)
+			.field("challenge_digest", &self.challenge_digest)
+			.field("token_key_id", &self.token_key_id)
+			.finish()
+	}
+}
+
+impl Eq for PrivacyPassClient {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for PrivacyPassClient {
+	fn eq(&self, other: &Self) -> bool {
+		self.client.eq(&other.client)
+			&& self.public_key.eq(&other.public_key)
+			&& self.nonce.eq(&other.nonce)
+			&& self.challenge_digest.eq(&other.challenge_digest)
+			&& self.token_key_id.eq(&other.token_key_id)
+	}
+}
+
+impl ZeroizeOnDrop for PrivacyPassClient {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Clone for PrivacyPassIssuer {
+	fn clone(&self) -> Self {
+		Self {
+			server: self.server.clone(),
+			token_key_id: self.token_key_id,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for PrivacyPassIssuer {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PrivacyPassIssuer")
+			.field("server", &self.server)
+			.field("token_key_id", &self.token_key_id)
+			.finish()
+	}
+}
+
+impl Eq for PrivacyPassIssuer {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for PrivacyPassIssuer {
+	fn eq(&self, other: &Self) -> bool {
+		self.server.eq(&other.server)
+	}
+}
+
+impl ZeroizeOnDrop for PrivacyPassIssuer {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for PrivacyPassRequestResult {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PrivacyPassRequestResult")
+			.field("client", &self.client)
+			.field("token_request", &self.token_request)
+			.finish()
+	}
+}
+
+impl ZeroizeOnDrop for PrivacyPassRequestResult {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Clone for TokenRequest {
+	fn clone(&self) -> Self {
+		Self {
+			truncated_token_key_id: self.truncated_token_key_id,
+			blinded_element: self.blinded_element.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for TokenRequest {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TokenRequest")
+			.field("truncated_token_key_id", &self.truncated_token_key_id)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+impl Eq for TokenRequest {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for TokenRequest {
+	fn eq(&self, other: &Self) -> bool {
+		self.truncated_token_key_id
+			.eq(&other.truncated_token_key_id)
+			&& self.blinded_element.eq(&other.blinded_element)
+	}
+}
+
+impl ZeroizeOnDrop for TokenRequest {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Clone for TokenResponse {
+	fn clone(&self) -> Self {
+		Self {
+			evaluation_element: self.evaluation_element.clone(),
+			proof: self.proof.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for TokenResponse {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TokenResponse")
+			.field("evaluation_element", &self.evaluation_element)
+			.field("proof", &self.proof)
+			.finish()
+	}
+}
+
+impl Eq for TokenResponse {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for TokenResponse {
+	fn eq(&self, other: &Self) -> bool {
+		self.evaluation_element.eq(&other.evaluation_element) && self.proof.eq(&other.proof)
+	}
+}
+
+impl ZeroizeOnDrop for TokenResponse {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Clone for Token {
+	fn clone(&self) -> Self {
+		Self {
+			nonce: self.nonce,
+			challenge_digest: self.challenge_digest,
+			token_key_id: self.token_key_id,
+			authenticator: self.authenticator,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for Token {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Token")
+			.field("nonce", &self.nonce)
+			.field("challenge_digest", &self.challenge_digest)
+			.field("token_key_id", &self.token_key_id)
+			.field("authenticator", &self.authenticator)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Drop for Token {
+	fn drop(&mut self) {
+		self.authenticator.zeroize();
+	}
+}
+
+impl Eq for Token {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for Token {
+	fn eq(&self, other: &Self) -> bool {
+		self.nonce.eq(&other.nonce)
+			&& self.challenge_digest.eq(&other.challenge_digest)
+			&& self.token_key_id.eq(&other.token_key_id)
+			&& self.authenticator.eq(&other.authenticator)
+	}
+}
+
+impl ZeroizeOnDrop for Token {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Clone for PrivacyPassBatchClient {
+	fn clone(&self) -> Self {
+		Self {
+			clients: self.clients.clone(),
+			public_key: self.public_key.clone(),
+			nonces: self.nonces.clone(),
+			challenge_digest: self.challenge_digest,
+			token_key_id: self.token_key_id,
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for PrivacyPassBatchClient {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PrivacyPassBatchClient")
+			.field("clients", &self.clients)
+			.field("public_key", &self.public_key)
+			.field("nonces", &self.nonces)
+			.field("challenge_digest", &self.challenge_digest)
+			.field("token_key_id", &self.token_key_id)
+			.finish()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Eq for PrivacyPassBatchClient {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for PrivacyPassBatchClient {
+	fn eq(&self, other: &Self) -> bool {
+		self.clients.eq(&other.clients)
+			&& self.public_key.eq(&other.public_key)
+			&& self.nonces.eq(&other.nonces)
+			&& self.challenge_digest.eq(&other.challenge_digest)
+			&& self.token_key_id.eq(&other.token_key_id)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl ZeroizeOnDrop for PrivacyPassBatchClient {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for PrivacyPassBatchRequestResult {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PrivacyPassBatchRequestResult")
+			.field("client", &self.client)
+			.field("batch_token_request", &self.batch_token_request)
+			.finish()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl ZeroizeOnDrop for PrivacyPassBatchRequestResult {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Clone for BatchTokenRequest {
+	fn clone(&self) -> Self {
+		Self {
+			truncated_token_key_id: self.truncated_token_key_id,
+			blinded_elements: self.blinded_elements.clone(),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for BatchTokenRequest {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("BatchTokenRequest")
+			.field("truncated_token_key_id", &self.truncated_token_key_id)
+			.field("blinded_elements", &self.blinded_elements)
+			.finish()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Eq for BatchTokenRequest {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for BatchTokenRequest {
+	fn eq(&self, other: &Self) -> bool {
+		self.truncated_token_key_id
+			.eq(&other.truncated_token_key_id)
+			&& self.blinded_elements.eq(&other.blinded_elements)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl ZeroizeOnDrop for BatchTokenRequest {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Clone for BatchTokenResponse {
+	fn clone(&self) -> Self {
+		Self {
+			evaluation_elements: self.evaluation_elements.clone(),
+			proof: self.proof.clone(),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl Debug for BatchTokenResponse {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("BatchTokenResponse")
+			.field("evaluation_elements", &self.evaluation_elements)
+			.field("proof", &self.proof)
+			.finish()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Eq for BatchTokenResponse {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for BatchTokenResponse {
+	fn eq(&self, other: &Self) -> bool {
+		self.evaluation_elements.eq(&other.evaluation_elements) && self.proof.eq(&other.proof)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl ZeroizeOnDrop for BatchTokenResponse {}
+
+/// Returns the `token_key_id` of the provided [`PublicKey`].
+fn token_key_id(public_key: &PublicKey<<NistP384 as CipherSuite>::Group>) -> [u8; 32] {
+	Sha256::digest(public_key.as_repr()).into()
+}
+
+/// Returns the `truncated_token_key_id` of the provided `token_key_id`.
+const fn truncated_token_key_id(token_key_id: &[u8; 32]) -> u8 {
+	token_key_id[31]
+}
+
+/// Returns the `token_input`.
+fn token_input<'input>(
+	nonce: &'input [u8; 32],
+	challenge_digest: &'input [u8; 32],
+	token_key_id: &'input [u8; 32],
+) -> [&'input [u8]; 4] {
+	/// `token_type` as `token_input` prefix.
+	const TOKEN_TYPE_REPR: [u8; 2] = TOKEN_TYPE.to_be_bytes();
+
+	[&TOKEN_TYPE_REPR, nonce, challenge_digest, token_key_id]
+}
+
+/// Validates the token type.
+///
+/// # Errors
+///
+/// Returns [`Error::FromRepr`] if the token type isn't [`TOKEN_TYPE`].
+fn token_type_from_repr(repr: [u8; 2]) -> Result<()> {
+	if u16::from_be_bytes(repr) == TOKEN_TYPE {
+		Ok(())
+	} else {
+		Err(Error::FromRepr)
+	}
+}
+
+/// Returns the `u16` count prefix of a list with `length` items.
+///
+/// # Errors
+///
+/// Returns [`Error::Batch`] if `length` exceeds [`u16::MAX`].
+#[cfg(feature = "alloc")]
+fn count_i2osp(length: usize) -> Result<[u8; 2]> {
+	u16::try_from(length)
+		.map(u16::i2osp)
+		.map_err(|_| Error::Batch)
+}
+
+/// Deserializes a `u16` count prefixed list of elements and returns the
+/// remaining bytes.
+///
+/// # Errors
+///
+/// Returns [`Error::FromRepr`] if deserialization fails or the list is empty.
+#[cfg(feature = "alloc")]
+fn elements_from_repr<T, F>(repr: &[u8], from_repr: F) -> Result<(Vec<T>, &[u8])>
+where
+	F: FnMut(&[u8]) -> Result<T>,
+{
+	let (count, repr) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+	let count = usize::from(u16::from_be_bytes(*count));
+
+	if count == 0 {
+		return Err(Error::FromRepr);
+	}
+
+	let (elements, repr) = repr
+		.split_at_checked(count * ELEMENT_LENGTH)
+		.ok_or(Error::FromRepr)?;
+	let elements = elements
+		.chunks_exact(ELEMENT_LENGTH)
+		.map(from_repr)
+		.collect::<Result<_>>()?;
+
+	Ok((elements, repr))
+}
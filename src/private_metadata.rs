@@ -0,0 +1,888 @@
+//! Anonymous tokens with a private metadata bit as per
+//! [Kreuter et al.](https://eprint.iacr.org/2020/072) (PMBTokens).
+//!
+//! The [`PmbServer`] embeds a hidden bit into every token it issues, e.g. to
+//! flag suspected fraud. The client can't learn the bit, but the server
+//! recovers it when the [`PmbToken`] is redeemed.
+//!
+//! The server holds two key pairs `(x_b, y_b)` and publishes
+//! `X_b = x_b * G + y_b * H` as its [`PmbPublicKey`], where `H` is a second
+//! generator derived from the [`CipherSuite`].
+//!
+//! 1. The client hashes a random nonce `t` to `T`, blinds it with
+//!    [`PmbClient::blind()`] and sends the [`BlindedElement`] `T'` to the
+//!    server.
+//! 2. The server computes `W' = x_b * T' + y_b * S'` for the chosen bit with
+//!    [`PmbServer::blind_evaluate()`], where `S'` is derived from `T'` and a
+//!    server nonce, and proves with a [`PmbProof`] that *one of* its two keys
+//!    was used.
+//! 3. The client verifies the [`PmbProof`] against the [`PmbPublicKey`] and
+//!    unblinds the [`PmbToken`] with [`PmbClient::finalize()`].
+//! 4. The server recovers the bit from the [`PmbToken`] with
+//!    [`PmbServer::redeem()`].
+//!
+//! The server must reject [`PmbToken`]s whose [nonce](PmbToken::nonce) was
+//! already redeemed. This is left to the caller.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::ops::Deref;
+
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::{
+	CipherSuite, Element, ElementLength, NonIdentityElement, NonZeroScalar, Scalar, ScalarLength,
+};
+use crate::common::{BlindedElement, EvaluationElement};
+use crate::error::{Error, Result};
+use crate::group::{CipherSuiteExt, Group};
+use crate::internal::ElementWithRepr;
+
+/// Length of the client and server nonces.
+const NONCE_LENGTH: usize = 32;
+
+/// Client of the private metadata bit protocol.
+pub struct PmbClient<Cs: CipherSuite> {
+	/// The clients blind `r`.
+	blind: NonZeroScalar<Cs>,
+	/// The clients nonce `t`.
+	nonce: [u8; NONCE_LENGTH],
+	/// The [`BlindedElement`] `T'`.
+	blinded_element: BlindedElement<Cs>,
+}
+
+/// Server of the private metadata bit protocol.
+pub struct PmbServer<Cs: CipherSuite> {
+	/// The secret keys `(x_0, y_0)` and `(x_1, y_1)`.
+	secret_keys: [(NonZeroScalar<Cs>, NonZeroScalar<Cs>); 2],
+	/// The [`PmbPublicKey`].
+	public_key: PmbPublicKey<Cs>,
+}
+
+/// Public key of a [`PmbServer`].
+pub struct PmbPublicKey<Cs: CipherSuite> {
+	/// `X_0`.
+	x_0: ElementWithRepr<Cs::Group>,
+	/// `X_1`.
+	x_1: ElementWithRepr<Cs::Group>,
+}
+
+/// Disjunctive proof that an [`EvaluationElement`] was computed with one of
+/// the two keys of a [`PmbPublicKey`].
+pub struct PmbProof<Cs: CipherSuite> {
+	/// The challenges `c_0` and `c_1`.
+	c: [Scalar<Cs>; 2],
+	/// The responses `u_0` and `u_1`.
+	u: [Scalar<Cs>; 2],
+	/// The responses `v_0` and `v_1`.
+	v: [Scalar<Cs>; 2],
+}
+
+/// A token carrying a private metadata bit.
+pub struct PmbToken<Cs: CipherSuite> {
+	/// The clients nonce `t`.
+	nonce: [u8; NONCE_LENGTH],
+	/// `S`.
+	s: ElementWithRepr<Cs::Group>,
+	/// `W`.
+	w: ElementWithRepr<Cs::Group>,
+}
+
+/// Returned from [`PmbClient::blind()`].
+pub struct PmbBlindResult<Cs: CipherSuite> {
+	/// The [`PmbClient`].
+	pub client: PmbClient<Cs>,
+	/// The [`BlindedElement`] to be sent to the server.
+	pub blinded_element: BlindedElement<Cs>,
+}
+
+/// Returned from [`PmbServer::blind_evaluate()`].
+pub struct PmbBlindEvaluateResult<Cs: CipherSuite> {
+	/// The servers nonce `s`.
+	pub nonce: [u8; NONCE_LENGTH],
+	/// The [`EvaluationElement`] `W'`.
+	pub evaluation_element: EvaluationElement<Cs>,
+	/// The [`PmbProof`].
+	pub proof: PmbProof<Cs>,
+}
+
+/// Statement proven by a [`PmbProof`].
+struct Statement<'elements, Cs: CipherSuite> {
+	/// The second generator `H`.
+	h: Element<Cs>,
+	/// The [`PmbPublicKey`].
+	public_key: &'elements PmbPublicKey<Cs>,
+	/// `T'`.
+	t: &'elements ElementWithRepr<Cs::Group>,
+	/// `S'`.
+	s: &'elements ElementWithRepr<Cs::Group>,
+	/// `W'`.
+	w: &'elements ElementWithRepr<Cs::Group>,
+}
+
+impl<Cs: CipherSuite> PmbClient<Cs> {
+	/// Hashes a random nonce and blinds it.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind<R>(rng: &mut R) -> Result<PmbBlindResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let mut nonce = [0; NONCE_LENGTH];
+		rng.try_fill_bytes(&mut nonce).map_err(Error::Random)?;
+
+		let t = hash_t::<Cs>(&nonce).map_err(Error::into_random::<R>)?;
+		let blind = Cs::Group::scalar_random(rng).map_err(Error::Random)?;
+		let blinded_element = BlindedElement::from(ElementWithRepr::new(blind * &t));
+
+		Ok(PmbBlindResult {
+			client: Self {
+				blind,
+				nonce,
+				blinded_element: blinded_element.clone(),
+			},
+			blinded_element,
+		})
+	}
+
+	/// Verifies the [`PmbProof`] and unblinds the [`PmbToken`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the servers `nonce` can never produce a
+	///   valid [`PmbToken`].
+	/// - [`Error::Proof`] if the [`PmbProof`] is invalid.
+	pub fn finalize(
+		&self,
+		public_key: &PmbPublicKey<Cs>,
+		nonce: &[u8; NONCE_LENGTH],
+		evaluation_element: &EvaluationElement<Cs>,
+		proof: &PmbProof<Cs>,
+	) -> Result<PmbToken<Cs>> {
+		let s = hash_s(&self.blinded_element, nonce)?;
+
+		Statement {
+			h: generator_h::<Cs>()?,
+			public_key,
+			t: self.blinded_element.as_ref(),
+			s: &s,
+			w: evaluation_element.as_ref(),
+		}
+		.verify(proof)?;
+
+		let blind_inverted = Cs::Group::scalar_invert(&self.blind);
+
+		Ok(PmbToken {
+			nonce: self.nonce,
+			s: ElementWithRepr::new(blind_inverted * s.as_element()),
+			w: ElementWithRepr::new(blind_inverted * evaluation_element.as_element()),
+		})
+	}
+}
+
+impl<Cs: CipherSuite> PmbServer<Cs> {
+	/// Creates a new [`PmbServer`] with random keys.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn new<R>(rng: &mut R) -> Result<Self, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let h = generator_h::<Cs>().map_err(Error::into_random::<R>)?;
+		let (x_0, y_0, public_0) = generate_key::<Cs, R>(rng, &h).map_err(Error::Random)?;
+		let (x_1, y_1, public_1) = generate_key::<Cs, R>(rng, &h).map_err(Error::Random)?;
+
+		Ok(Self {
+			secret_keys: [(x_0, y_0), (x_1, y_1)],
+			public_key: PmbPublicKey {
+				x_0: public_0,
+				x_1: public_1,
+			},
+		})
+	}
+
+	/// Returns the [`PmbPublicKey`].
+	#[must_use]
+	pub const fn public_key(&self) -> &PmbPublicKey<Cs> {
+		&self.public_key
+	}
+
+	/// Process the [`BlindedElement`], embedding the private metadata `bit`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the [`BlindedElement`] can never produce a
+	///   valid [`PmbToken`].
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind_evaluate<R>(
+		&self,
+		rng: &mut R,
+		blinded_element: &BlindedElement<Cs>,
+		bit: bool,
+	) -> Result<PmbBlindEvaluateResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let mut nonce = [0; NONCE_LENGTH];
+		rng.try_fill_bytes(&mut nonce).map_err(Error::Random)?;
+
+		let s = hash_s(blinded_element, &nonce).map_err(Error::into_random::<R>)?;
+		let (x, y) = self.secret_key(bit);
+		let w = (*(x * blinded_element.as_element()) + &*(y * s.as_element()))
+			.try_into()
+			.map_err(|_| Error::<R::Error>::InvalidInput)?;
+		let w = ElementWithRepr::new(w);
+
+		let proof = Statement {
+			h: generator_h::<Cs>().map_err(Error::into_random::<R>)?,
+			public_key: &self.public_key,
+			t: blinded_element.as_ref(),
+			s: &s,
+			w: &w,
+		}
+		.prove(rng, bit, x, y)?;
+
+		Ok(PmbBlindEvaluateResult {
+			nonce,
+			evaluation_element: w.into(),
+			proof,
+		})
+	}
+
+	/// Verifies the [`PmbToken`] and returns its private metadata bit.
+	///
+	/// This does *not* check if the [`PmbToken`] was already redeemed.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Token`] if the [`PmbToken`] was not issued by this
+	///   [`PmbServer`].
+	pub fn redeem(&self, token: &PmbToken<Cs>) -> Result<bool> {
+		let t: Element<Cs> = hash_t::<Cs>(&token.nonce)?.into();
+		let s: Element<Cs> = (*token.s.as_element()).into();
+		let [w_0, w_1] = self
+			.secret_keys
+			.map(|(x, y)| Cs::Group::element_to_repr(&Cs::Group::lincomb(&[(t, *x), (s, *y)])));
+
+		if &w_0 == token.w.as_repr() {
+			Ok(false)
+		} else if &w_1 == token.w.as_repr() {
+			Ok(true)
+		} else {
+			Err(Error::Token)
+		}
+	}
+
+	/// Returns the secret key for the private metadata `bit`.
+	const fn secret_key(&self, bit: bool) -> (NonZeroScalar<Cs>, NonZeroScalar<Cs>) {
+		let [key_0, key_1] = self.secret_keys;
+
+		if bit { key_1 } else { key_0 }
+	}
+}
+
+impl<Cs: CipherSuite> PmbPublicKey<Cs> {
+	/// Serializes this [`PmbPublicKey`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.x_0
+			.as_repr()
+			.iter()
+			.chain(self.x_1.as_repr())
+			.copied()
+			.collect()
+	}
+
+	/// Deserializes the provided `repr` to a [`PmbPublicKey`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let (x_0_repr, x_1_repr) = repr
+			.split_at_checked(ElementLength::<Cs>::USIZE)
+			.ok_or(Error::FromRepr)?;
+		let x_0 = ElementWithRepr::from_repr(x_0_repr)?;
+		let x_1 = ElementWithRepr::from_repr(x_1_repr)?;
+
+		Ok(Self { x_0, x_1 })
+	}
+}
+
+impl<Cs: CipherSuite> PmbProof<Cs> {
+	/// Serializes this [`PmbProof`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.c
+			.iter()
+			.chain(&self.u)
+			.chain(&self.v)
+			.flat_map(Cs::Group::scalar_to_repr)
+			.collect()
+	}
+
+	/// Deserializes the provided `repr` to a [`PmbProof`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		if repr.len() != ScalarLength::<Cs>::USIZE * 6 {
+			return Err(Error::FromRepr);
+		}
+
+		let mut scalars = repr.chunks_exact(ScalarLength::<Cs>::USIZE).map(|repr| {
+			repr.try_into()
+				.ok()
+				.and_then(|repr| Cs::Group::scalar_from_repr(repr).ok())
+				.ok_or(Error::FromRepr)
+		});
+		let mut next = || scalars.next().unwrap_or(Err(Error::FromRepr));
+
+		Ok(Self {
+			c: [next()?, next()?],
+			u: [next()?, next()?],
+			v: [next()?, next()?],
+		})
+	}
+}
+
+impl<Cs: CipherSuite> PmbToken<Cs> {
+	/// Returns the clients nonce, which must be used to reject double
+	/// spending.
+	#[must_use]
+	pub const fn nonce(&self) -> &[u8; NONCE_LENGTH] {
+		&self.nonce
+	}
+
+	/// Serializes this [`PmbToken`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.nonce
+			.iter()
+			.chain(self.s.as_repr())
+			.chain(self.w.as_repr())
+			.copied()
+			.collect()
+	}
+
+	/// Deserializes the provided `repr` to a [`PmbToken`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let (nonce, repr) = repr
+			.split_first_chunk::<NONCE_LENGTH>()
+			.ok_or(Error::FromRepr)?;
+		let (s_repr, w_repr) = repr
+			.split_at_checked(ElementLength::<Cs>::USIZE)
+			.ok_or(Error::FromRepr)?;
+		let s = ElementWithRepr::from_repr(s_repr)?;
+		let w = ElementWithRepr::from_repr(w_repr)?;
+
+		Ok(Self {
+			nonce: *nonce,
+			s,
+			w,
+		})
+	}
+}
+
+impl<Cs: CipherSuite> Statement<'_, Cs> {
+	/// Computes the commitments `(K_G, K_T)` of a branch from its response.
+	fn commitments(
+		&self,
+		public_key: &ElementWithRepr<Cs::Group>,
+		c: Scalar<Cs>,
+		u: Scalar<Cs>,
+		v: Scalar<Cs>,
+	) -> [Element<Cs>; 2] {
+		[
+			Cs::Group::lincomb(&[
+				(Cs::Group::element_generator(), u),
+				(self.h, v),
+				((*public_key.as_element()).into(), c),
+			]),
+			Cs::Group::lincomb(&[
+				((*self.t.as_element()).into(), u),
+				((*self.s.as_element()).into(), v),
+				((*self.w.as_element()).into(), c),
+			]),
+		]
+	}
+
+	/// Computes the challenge over this [`Statement`] and the commitments of
+	/// both branches.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+	/// are incompatible.
+	fn challenge(&self, commitments: [[Element<Cs>; 2]; 2]) -> Result<Scalar<Cs>> {
+		let [[k_g_0, k_t_0], [k_g_1, k_t_1]] = commitments.map(|commitments| {
+			commitments.map(|commitment| Cs::Group::element_to_repr(&commitment))
+		});
+
+		Cs::Group::hash_to_scalar::<Cs::ExpandMsg>(
+			&[
+				&Cs::I2OSP_ELEMENT_LEN,
+				self.public_key.x_0.as_repr(),
+				&Cs::I2OSP_ELEMENT_LEN,
+				self.public_key.x_1.as_repr(),
+				&Cs::I2OSP_ELEMENT_LEN,
+				self.t.as_repr(),
+				&Cs::I2OSP_ELEMENT_LEN,
+				self.s.as_repr(),
+				&Cs::I2OSP_ELEMENT_LEN,
+				self.w.as_repr(),
+				&Cs::I2OSP_ELEMENT_LEN,
+				&k_g_0,
+				&Cs::I2OSP_ELEMENT_LEN,
+				&k_t_0,
+				&Cs::I2OSP_ELEMENT_LEN,
+				&k_g_1,
+				&Cs::I2OSP_ELEMENT_LEN,
+				&k_t_1,
+				b"Challenge",
+			],
+			&dst::<Cs>(b"Challenge-"),
+		)
+		.map_err(|_| Error::InvalidCipherSuite)
+	}
+
+	/// Proves that `W'` was computed with the key `(x, y)` of the private
+	/// metadata `bit`, simulating the branch of the other key.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	fn prove<R>(
+		&self,
+		rng: &mut R,
+		bit: bool,
+		x: NonZeroScalar<Cs>,
+		y: NonZeroScalar<Cs>,
+	) -> Result<PmbProof<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let r_x: Scalar<Cs> = Cs::Group::scalar_random(rng).map_err(Error::Random)?.into();
+		let r_y: Scalar<Cs> = Cs::Group::scalar_random(rng).map_err(Error::Random)?.into();
+		let real_commitments = [
+			Cs::Group::lincomb(&[(Cs::Group::element_generator(), r_x), (self.h, r_y)]),
+			Cs::Group::lincomb(&[
+				((*self.t.as_element()).into(), r_x),
+				((*self.s.as_element()).into(), r_y),
+			]),
+		];
+
+		let simulated: [Scalar<Cs>; 3] = [
+			Cs::Group::scalar_random(rng).map_err(Error::Random)?.into(),
+			Cs::Group::scalar_random(rng).map_err(Error::Random)?.into(),
+			Cs::Group::scalar_random(rng).map_err(Error::Random)?.into(),
+		];
+		let [c_simulated, u_simulated, v_simulated] = simulated;
+		let public_key_simulated = if bit {
+			&self.public_key.x_0
+		} else {
+			&self.public_key.x_1
+		};
+		let simulated_commitments =
+			self.commitments(public_key_simulated, c_simulated, u_simulated, v_simulated);
+
+		let commitments = if bit {
+			[simulated_commitments, real_commitments]
+		} else {
+			[real_commitments, simulated_commitments]
+		};
+		let c = self
+			.challenge(commitments)
+			.map_err(Error::into_random::<R>)?;
+
+		let c_real = c - &c_simulated;
+		let real = [
+			c_real,
+			r_x - &(c_real * x.deref()),
+			r_y - &(c_real * y.deref()),
+		];
+
+		let [[c_0, u_0, v_0], [c_1, u_1, v_1]] = if bit {
+			[simulated, real]
+		} else {
+			[real, simulated]
+		};
+
+		Ok(PmbProof {
+			c: [c_0, c_1],
+			u: [u_0, u_1],
+			v: [v_0, v_1],
+		})
+	}
+
+	/// Verifies the [`PmbProof`] against this [`Statement`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Proof`] if the [`PmbProof`] is invalid.
+	fn verify(&self, proof: &PmbProof<Cs>) -> Result<()> {
+		let [c_0, c_1] = proof.c;
+		let [u_0, u_1] = proof.u;
+		let [v_0, v_1] = proof.v;
+
+		let commitments = [
+			self.commitments(&self.public_key.x_0, c_0, u_0, v_0),
+			self.commitments(&self.public_key.x_1, c_1, u_1, v_1),
+		];
+		let c = self.challenge(commitments)?;
+
+		if c == c_0 + &c_1 {
+			Ok(())
+		} else {
+			Err(Error::Proof)
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PmbClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			blind: self.blind,
+			nonce: self.nonce,
+			blinded_element: self.blinded_element.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PmbClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PmbClient")
+			.field("blind", &self.blind)
+			.field("nonce", &self.nonce)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for PmbClient<Cs> {
+	fn drop(&mut self) {
+		self.blind.zeroize();
+		self.nonce.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PmbClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PmbClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.blind.eq(&other.blind)
+			&& self.nonce.eq(&other.nonce)
+			&& self.blinded_element.eq(&other.blinded_element)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PmbClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PmbServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			secret_keys: self.secret_keys,
+			public_key: self.public_key.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PmbServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PmbServer")
+			.field("secret_keys", &self.secret_keys)
+			.field("public_key", &self.public_key)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for PmbServer<Cs> {
+	fn drop(&mut self) {
+		for (x, y) in &mut self.secret_keys {
+			x.zeroize();
+			y.zeroize();
+		}
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PmbServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PmbServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.secret_keys.eq(&other.secret_keys) && self.public_key.eq(&other.public_key)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PmbServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PmbPublicKey<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			x_0: self.x_0.clone(),
+			x_1: self.x_1.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PmbPublicKey<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PmbPublicKey")
+			.field("x_0", &self.x_0)
+			.field("x_1", &self.x_1)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PmbPublicKey<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PmbPublicKey<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.x_0.eq(&other.x_0) && self.x_1.eq(&other.x_1)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PmbPublicKey<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PmbProof<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			c: self.c,
+			u: self.u,
+			v: self.v,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PmbProof<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PmbProof")
+			.field("c", &self.c)
+			.field("u", &self.u)
+			.field("v", &self.v)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for PmbProof<Cs> {
+	fn drop(&mut self) {
+		self.c.zeroize();
+		self.u.zeroize();
+		self.v.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PmbProof<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PmbProof<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.c.eq(&other.c) && self.u.eq(&other.u) && self.v.eq(&other.v)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PmbProof<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PmbToken<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			nonce: self.nonce,
+			s: self.s.clone(),
+			w: self.w.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PmbToken<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PmbToken")
+			.field("nonce", &self.nonce)
+			.field("s", &self.s)
+			.field("w", &self.w)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for PmbToken<Cs> {
+	fn drop(&mut self) {
+		self.nonce.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PmbToken<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PmbToken<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.nonce.eq(&other.nonce) && self.s.eq(&other.s) && self.w.eq(&other.w)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PmbToken<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PmbBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PmbBlindResult")
+			.field("client", &self.client)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PmbBlindResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PmbBlindEvaluateResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PmbBlindEvaluateResult")
+			.field("nonce", &self.nonce)
+			.field("evaluation_element", &self.evaluation_element)
+			.field("proof", &self.proof)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PmbBlindEvaluateResult<Cs> {}
+
+/// Returns the DST for the provided `label`.
+fn dst<Cs: CipherSuite>(label: &'static [u8]) -> [&'static [u8]; 3] {
+	[b"PMBT-", label, &Cs::ID]
+}
+
+/// Generates a random key `(x, y)` with `X = x * G + y * H`.
+///
+/// # Errors
+///
+/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the provided
+/// `rng` fails.
+fn generate_key<Cs, R>(
+	rng: &mut R,
+	h: &NonIdentityElement<Cs>,
+) -> Result<
+	(
+		NonZeroScalar<Cs>,
+		NonZeroScalar<Cs>,
+		ElementWithRepr<Cs::Group>,
+	),
+	R::Error,
+>
+where
+	Cs: CipherSuite,
+	R: ?Sized + TryCryptoRng,
+{
+	loop {
+		let x = Cs::Group::scalar_random(rng)?;
+		let y = Cs::Group::scalar_random(rng)?;
+
+		if let Ok(public_key) = (Cs::Group::scalar_mul_by_generator(&x) + &*(y * h)).try_into() {
+			return Ok((x, y, ElementWithRepr::new(public_key)));
+		}
+	}
+}
+
+/// Derives the second generator `H`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+/// [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg) are
+/// incompatible.
+fn generator_h<Cs: CipherSuite>() -> Result<NonIdentityElement<Cs>> {
+	Cs::Group::hash_to_curve::<Cs::ExpandMsg>(&[b"Generator"], &dst::<Cs>(b"Generator-"))
+		.map_err(|_| Error::InvalidCipherSuite)?
+		.try_into()
+		.map_err(|_| Error::InvalidCipherSuite)
+}
+
+/// Hashes the servers `nonce` and `T'` to `S'`.
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::InvalidInput`] if the provided `nonce` can never produce a valid
+///   [`PmbToken`].
+fn hash_s<Cs: CipherSuite>(
+	blinded_element: &BlindedElement<Cs>,
+	nonce: &[u8; NONCE_LENGTH],
+) -> Result<ElementWithRepr<Cs::Group>> {
+	Cs::Group::hash_to_curve::<Cs::ExpandMsg>(
+		&[blinded_element.as_repr(), nonce],
+		&dst::<Cs>(b"HashToGroupS-"),
+	)
+	.map_err(|_| Error::InvalidCipherSuite)?
+	.try_into()
+	.map(ElementWithRepr::new)
+	.map_err(|_| Error::InvalidInput)
+}
+
+/// Hashes the clients `nonce` to `T`.
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and [`ExpandMsg`](CipherSuite::ExpandMsg)
+///   are incompatible.
+/// - [`Error::InvalidInput`] if the provided `nonce` can never produce a valid
+///   [`PmbToken`].
+fn hash_t<Cs: CipherSuite>(nonce: &[u8; NONCE_LENGTH]) -> Result<NonIdentityElement<Cs>> {
+	Cs::Group::hash_to_curve::<Cs::ExpandMsg>(&[nonce], &dst::<Cs>(b"HashToGroupT-"))
+		.map_err(|_| Error::InvalidCipherSuite)?
+		.try_into()
+		.map_err(|_| Error::InvalidInput)
+}
@@ -0,0 +1,696 @@
+//! OPRF-based private set intersection (PSI).
+//!
+//! The server evaluates its own set with [`PsiServer::set()`] and publishes the
+//! resulting [`PsiSet`]. The client blinds its items with
+//! [`PsiClient::blind()`], the server evaluates the [`BlindedElement`]s with
+//! [`PsiServer::blind_evaluate()`] and the client learns which of its items
+//! are in the intersection with [`PsiClient::intersection()`].
+//!
+//! # Cardinality
+//!
+//! With [`PsiCardinalityClient`] the client only learns the size of the
+//! intersection. All items are blinded with the same blind and the
+//! [`PsiCardinalityServer`] shuffles the [`EvaluationElement`]s with
+//! [`PsiCardinalityServer::blind_evaluate()`], so the client can't match them
+//! to its items. Because the client can't associate an [`EvaluationElement`]
+//! with its input, the outputs aren't bound to the input as in
+//! [RFC 9497](https://www.rfc-editor.org/rfc/rfc9497.html) and the server has
+//! to publish a separate [`PsiSet`] created with
+//! [`PsiCardinalityServer::set()`].
+//!
+//! The client still learns the unblinded elements of its items, so it could
+//! compute the regular outputs of every item under the same key. A
+//! [`PsiCardinalityServer`] must therefore never share its [`SecretKey`] with
+//! a [`PsiServer`], otherwise a client seeing both [`PsiSet`]s learns the
+//! actual intersection.
+//!
+//! # Unbalanced PSI
+//!
+//! For large server sets the server can publish a compact [`CuckooFilter`]
+//! created with [`PsiServer::filter()`] instead of a [`PsiSet`] and keep it up
+//! to date with [`PsiServer::filter_insert()`] and
+//! [`PsiServer::filter_remove()`]. The client learns the intersection with
+//! [`PsiClient::filter_intersection()`], which might include false positives.
+//! See the [`cuckoo`] module for details.
+
+pub mod cuckoo;
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::slice;
+
+use digest::{FixedOutput, Output, Update};
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use self::cuckoo::CuckooFilter;
+use crate::cipher_suite::{CipherSuite, NonIdentityElement, NonZeroScalar};
+use crate::common::{BlindedElement, EvaluationElement, Mode};
+use crate::error::{Error, Result};
+use crate::group::{CipherSuiteExt, Group};
+use crate::key::SecretKey;
+use crate::oprf::{OprfBatchAllocBlindResult, OprfClient, OprfServer};
+
+/// PSI client.
+pub struct PsiClient<Cs: CipherSuite> {
+	/// The underlying [`OprfClient`]s, one for each item.
+	clients: Vec<OprfClient<Cs>>,
+}
+
+/// PSI-cardinality client.
+pub struct PsiCardinalityClient<Cs: CipherSuite> {
+	/// The blind shared by all items.
+	blind: NonZeroScalar<Cs>,
+	/// The number of blinded items.
+	length: usize,
+}
+
+/// PSI server.
+pub struct PsiServer<Cs: CipherSuite> {
+	/// The underlying [`OprfServer`].
+	server: OprfServer<Cs>,
+}
+
+/// PSI-cardinality server.
+pub struct PsiCardinalityServer<Cs: CipherSuite> {
+	/// The underlying [`OprfServer`].
+	server: OprfServer<Cs>,
+}
+
+/// The evaluated set of a [`PsiServer`] or [`PsiCardinalityServer`].
+///
+/// The outputs are sorted to hide the order of the original items.
+pub struct PsiSet<Cs: CipherSuite> {
+	/// Sorted outputs.
+	outputs: Vec<Output<Cs::Hash>>,
+}
+
+/// Returned from [`PsiClient::blind()`].
+pub struct PsiBlindResult<Cs: CipherSuite> {
+	/// The [`PsiClient`].
+	pub client: PsiClient<Cs>,
+	/// The [`BlindedElement`]s each corresponding to an item in order.
+	pub blinded_elements: Vec<BlindedElement<Cs>>,
+}
+
+/// Returned from [`PsiCardinalityClient::blind()`].
+pub struct PsiCardinalityBlindResult<Cs: CipherSuite> {
+	/// The [`PsiCardinalityClient`].
+	pub client: PsiCardinalityClient<Cs>,
+	/// The [`BlindedElement`]s each corresponding to an item in order.
+	pub blinded_elements: Vec<BlindedElement<Cs>>,
+}
+
+impl<Cs: CipherSuite> PsiClient<Cs> {
+	/// Blinds the provided `items`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if an item exceeds a length of [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if an item can never produce a valid output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind<R>(rng: &mut R, items: &[&[u8]]) -> Result<PsiBlindResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let OprfBatchAllocBlindResult {
+			clients,
+			blinded_elements,
+		} = OprfClient::batch_alloc_blind(rng, items.iter().map(slice::from_ref))?;
+
+		Ok(PsiBlindResult {
+			client: Self { clients },
+			blinded_elements,
+		})
+	}
+
+	/// Returns all `items` that are in the [`PsiSet`] of the server.
+	///
+	/// The `items` must be the same as passed to [`PsiClient::blind()`] and
+	/// the [`EvaluationElement`]s must be in the same order as the
+	/// [`BlindedElement`]s.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of `items` and `evaluation_elements`
+	///   don't match the number of blinded items.
+	/// - [`Error::InputLength`] if an item exceeds a length of [`u16::MAX`].
+	pub fn intersection<'items>(
+		&self,
+		items: &[&'items [u8]],
+		evaluation_elements: &[EvaluationElement<Cs>],
+		set: &PsiSet<Cs>,
+	) -> Result<Vec<&'items [u8]>> {
+		self.filter_items(items, evaluation_elements, |output| set.contains(output))
+	}
+
+	/// Returns all `items` that are in the [`CuckooFilter`] of the server.
+	///
+	/// The result might contain false positives, see the [`cuckoo`] module.
+	/// The `items` must be the same as passed to [`PsiClient::blind()`] and
+	/// the [`EvaluationElement`]s must be in the same order as the
+	/// [`BlindedElement`]s.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of `items` and `evaluation_elements`
+	///   don't match the number of blinded items.
+	/// - [`Error::InputLength`] if an item exceeds a length of [`u16::MAX`].
+	pub fn filter_intersection<'items>(
+		&self,
+		items: &[&'items [u8]],
+		evaluation_elements: &[EvaluationElement<Cs>],
+		filter: &CuckooFilter,
+	) -> Result<Vec<&'items [u8]>> {
+		self.filter_items(items, evaluation_elements, |output| filter.contains(output))
+	}
+
+	/// Returns all `items` whose output satisfies `contains`.
+	fn filter_items<'items, F>(
+		&self,
+		items: &[&'items [u8]],
+		evaluation_elements: &[EvaluationElement<Cs>],
+		contains: F,
+	) -> Result<Vec<&'items [u8]>>
+	where
+		F: Fn(&Output<Cs::Hash>) -> bool,
+	{
+		let outputs = OprfClient::batch_alloc_finalize(
+			self.clients.iter(),
+			items.iter().map(slice::from_ref),
+			evaluation_elements.iter(),
+		)?;
+
+		Ok(items
+			.iter()
+			.zip(outputs)
+			.filter(|(_, output)| contains(output))
+			.map(|(item, _)| *item)
+			.collect())
+	}
+}
+
+impl<Cs: CipherSuite> PsiCardinalityClient<Cs> {
+	/// Blinds the provided `items` with a single blind.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if an item can never produce a valid output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind<R>(
+		rng: &mut R,
+		items: &[&[u8]],
+	) -> Result<PsiCardinalityBlindResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let elements = items
+			.iter()
+			.map(|item| Cs::hash_to_curve(Mode::Oprf, slice::from_ref(item)))
+			.collect::<Result<Vec<_>>>()
+			.map_err(Error::into_random::<R>)?;
+		let blind = Cs::Group::scalar_random(rng).map_err(Error::Random)?;
+
+		let blinded_elements =
+			BlindedElement::new_batch_alloc(elements.into_iter().map(|element| (element, blind)));
+
+		Ok(PsiCardinalityBlindResult {
+			client: Self {
+				blind,
+				length: items.len(),
+			},
+			blinded_elements,
+		})
+	}
+
+	/// Returns the number of items that are in the [`PsiSet`] of the server.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Batch`] if the number of `evaluation_elements` doesn't
+	/// match the number of blinded items.
+	pub fn cardinality(
+		&self,
+		evaluation_elements: &[EvaluationElement<Cs>],
+		set: &PsiSet<Cs>,
+	) -> Result<usize> {
+		if evaluation_elements.len() != self.length {
+			return Err(Error::Batch);
+		}
+
+		let inverted_blind = Cs::Group::scalar_invert(&self.blind);
+
+		Ok(evaluation_elements
+			.iter()
+			.map(|evaluation_element| {
+				cardinality_output::<Cs>(&(inverted_blind * evaluation_element.as_element()))
+			})
+			.filter(|output| set.contains(output))
+			.count())
+	}
+}
+
+impl<Cs: CipherSuite> PsiServer<Cs> {
+	/// Creates a new [`PsiServer`] by generating a random [`SecretKey`].
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn new<R>(rng: &mut R) -> Result<Self, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		Ok(Self {
+			server: OprfServer::new(rng)?,
+		})
+	}
+
+	/// Creates a new [`PsiServer`] from the provided [`SecretKey`].
+	///
+	/// The [`SecretKey`] must not be used by a [`PsiCardinalityServer`].
+	#[must_use]
+	pub const fn from_key(secret_key: SecretKey<Cs::Group>) -> Self {
+		Self {
+			server: OprfServer::from_key(secret_key),
+		}
+	}
+
+	/// Returns the [`SecretKey`].
+	#[must_use]
+	pub const fn secret_key(&self) -> &SecretKey<Cs::Group> {
+		self.server.secret_key()
+	}
+
+	/// Evaluates the provided `items` to a [`PsiSet`] to be published to
+	/// [`PsiClient`]s.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if an item can never produce a valid output.
+	/// - [`Error::InputLength`] if an item exceeds a length of [`u16::MAX`].
+	pub fn set(&self, items: &[&[u8]]) -> Result<PsiSet<Cs>> {
+		let inputs: Vec<_> = items.iter().map(slice::from_ref).collect();
+
+		self.server
+			.batch_alloc_evaluate(&inputs)
+			.map(PsiSet::from_outputs)
+	}
+
+	/// Evaluates the provided `items` to a [`CuckooFilter`] with fingerprints
+	/// of `fingerprint_length` bytes to be published to [`PsiClient`]s.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Filter`] if `fingerprint_length` is zero or larger than 8 or
+	///   the [`CuckooFilter`] couldn't fit all `items`.
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if an item can never produce a valid output.
+	/// - [`Error::InputLength`] if an item exceeds a length of [`u16::MAX`].
+	pub fn filter(&self, items: &[&[u8]], fingerprint_length: u8) -> Result<CuckooFilter> {
+		let mut filter = CuckooFilter::new(items.len(), fingerprint_length)?;
+		self.filter_insert(&mut filter, items)?;
+		filter.clear_delta();
+
+		Ok(filter)
+	}
+
+	/// Evaluates the provided `items` and inserts them into the
+	/// [`CuckooFilter`]. The changes can be published with
+	/// [`CuckooFilter::delta()`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::Filter`] if the [`CuckooFilter`] couldn't fit all `items`.
+	///   Items inserted before the failing one remain inserted.
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if an item can never produce a valid output.
+	/// - [`Error::InputLength`] if an item exceeds a length of [`u16::MAX`].
+	pub fn filter_insert(&self, filter: &mut CuckooFilter, items: &[&[u8]]) -> Result<()> {
+		let inputs: Vec<_> = items.iter().map(slice::from_ref).collect();
+
+		self.server
+			.batch_alloc_evaluate(&inputs)?
+			.iter()
+			.try_for_each(|output| filter.insert(output))
+	}
+
+	/// Evaluates the provided `items` and removes them from the
+	/// [`CuckooFilter`]. The changes can be published with
+	/// [`CuckooFilter::delta()`].
+	///
+	/// Only items that were previously inserted must be removed, see
+	/// [`CuckooFilter::remove()`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if an item can never produce a valid output.
+	/// - [`Error::InputLength`] if an item exceeds a length of [`u16::MAX`].
+	pub fn filter_remove(&self, filter: &mut CuckooFilter, items: &[&[u8]]) -> Result<()> {
+		let inputs: Vec<_> = items.iter().map(slice::from_ref).collect();
+
+		for output in self.server.batch_alloc_evaluate(&inputs)? {
+			filter.remove(&output);
+		}
+
+		Ok(())
+	}
+
+	/// Processes the [`BlindedElement`]s of a [`PsiClient`].
+	#[must_use]
+	pub fn blind_evaluate(
+		&self,
+		blinded_elements: &[BlindedElement<Cs>],
+	) -> Vec<EvaluationElement<Cs>> {
+		self.server
+			.batch_alloc_blind_evaluate(blinded_elements.iter())
+	}
+}
+
+impl<Cs: CipherSuite> PsiCardinalityServer<Cs> {
+	/// Creates a new [`PsiCardinalityServer`] by generating a random
+	/// [`SecretKey`].
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn new<R>(rng: &mut R) -> Result<Self, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		Ok(Self {
+			server: OprfServer::new(rng)?,
+		})
+	}
+
+	/// Creates a new [`PsiCardinalityServer`] from the provided [`SecretKey`].
+	///
+	/// The [`SecretKey`] must not be used by a [`PsiServer`].
+	#[must_use]
+	pub const fn from_key(secret_key: SecretKey<Cs::Group>) -> Self {
+		Self {
+			server: OprfServer::from_key(secret_key),
+		}
+	}
+
+	/// Returns the [`SecretKey`].
+	#[must_use]
+	pub const fn secret_key(&self) -> &SecretKey<Cs::Group> {
+		self.server.secret_key()
+	}
+
+	/// Evaluates the provided `items` to a [`PsiSet`] to be published to
+	/// [`PsiCardinalityClient`]s.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s [`Group`] and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if an item can never produce a valid output.
+	pub fn set(&self, items: &[&[u8]]) -> Result<PsiSet<Cs>> {
+		let secret_key = self.server.secret_key().to_scalar();
+
+		items
+			.iter()
+			.map(|item| {
+				let element = Cs::hash_to_curve(Mode::Oprf, slice::from_ref(item))?;
+				Ok(cardinality_output::<Cs>(&(secret_key * &element)))
+			})
+			.collect::<Result<_>>()
+			.map(PsiSet::from_outputs)
+	}
+
+	/// Processes the [`BlindedElement`]s of a [`PsiCardinalityClient`] and
+	/// shuffles the resulting [`EvaluationElement`]s.
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn blind_evaluate<R>(
+		&self,
+		rng: &mut R,
+		blinded_elements: &[BlindedElement<Cs>],
+	) -> Result<Vec<EvaluationElement<Cs>>, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let mut evaluation_elements = self
+			.server
+			.batch_alloc_blind_evaluate(blinded_elements.iter());
+		shuffle(rng, &mut evaluation_elements)?;
+
+		Ok(evaluation_elements)
+	}
+}
+
+impl<Cs: CipherSuite> PsiSet<Cs> {
+	/// Creates a [`PsiSet`] from the provided published `outputs`.
+	#[must_use]
+	pub fn from_outputs(mut outputs: Vec<Output<Cs::Hash>>) -> Self {
+		outputs.sort_unstable();
+		Self { outputs }
+	}
+
+	/// Returns the sorted outputs to be published.
+	#[must_use]
+	pub const fn as_outputs(&self) -> &[Output<Cs::Hash>] {
+		self.outputs.as_slice()
+	}
+
+	/// Returns the number of outputs.
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.outputs.len()
+	}
+
+	/// Returns [`true`] if this [`PsiSet`] contains no outputs.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.outputs.is_empty()
+	}
+
+	/// Returns [`true`] if this [`PsiSet`] contains the provided `output`.
+	#[must_use]
+	pub fn contains(&self, output: &Output<Cs::Hash>) -> bool {
+		self.outputs.binary_search(output).is_ok()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PsiClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			clients: self.clients.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PsiClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PsiClient")
+			.field("clients", &self.clients)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PsiClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PsiClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.clients.eq(&other.clients)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PsiClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PsiCardinalityClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			blind: self.blind,
+			length: self.length,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PsiCardinalityClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PsiCardinalityClient")
+			.field("blind", &self.blind)
+			.field("length", &self.length)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for PsiCardinalityClient<Cs> {
+	fn drop(&mut self) {
+		self.blind.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PsiCardinalityClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PsiCardinalityClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.blind.eq(&other.blind) && self.length.eq(&other.length)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PsiCardinalityClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PsiServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			server: self.server.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PsiServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PsiServer")
+			.field("server", &self.server)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PsiServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PsiServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.server.eq(&other.server)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PsiServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PsiCardinalityServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			server: self.server.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PsiCardinalityServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PsiCardinalityServer")
+			.field("server", &self.server)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PsiCardinalityServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PsiCardinalityServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.server.eq(&other.server)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PsiCardinalityServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PsiSet<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			outputs: self.outputs.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PsiSet<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PsiSet")
+			.field("outputs", &self.outputs)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PsiSet<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PsiSet<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.outputs.eq(&other.outputs)
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PsiBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PsiBlindResult")
+			.field("client", &self.client)
+			.field("blinded_elements", &self.blinded_elements)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PsiBlindResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PsiCardinalityBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PsiCardinalityBlindResult")
+			.field("client", &self.client)
+			.field("blinded_elements", &self.blinded_elements)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PsiCardinalityBlindResult<Cs> {}
+
+/// Computes the output of an unblinded element for PSI-cardinality. Unlike
+/// `Finalize()` in RFC 9497 it doesn't include the input.
+fn cardinality_output<Cs: CipherSuite>(element: &NonIdentityElement<Cs>) -> Output<Cs::Hash> {
+	Cs::Hash::default()
+		.chain(Cs::I2OSP_ELEMENT_LEN)
+		.chain(Cs::Group::element_to_repr(element))
+		.chain(b"CardinalityFinalize")
+		.finalize_fixed()
+}
+
+/// Fisher-Yates shuffle.
+///
+/// # Errors
+///
+/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the provided
+/// `rng` fails.
+fn shuffle<T, R>(rng: &mut R, items: &mut [T]) -> Result<(), R::Error>
+where
+	R: ?Sized + TryCryptoRng,
+{
+	for index in (1..items.len()).rev() {
+		// The bias is negligible for any realistic number of items.
+		let bound = u64::try_from(index + 1).expect("`usize` must fit into `u64`");
+		let other =
+			usize::try_from(rng.try_next_u64()? % bound).expect("must be smaller than `index`");
+		items.swap(index, other);
+	}
+
+	Ok(())
+}
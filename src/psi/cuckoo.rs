@@ -0,0 +1,494 @@
+//! Compact encoding of server sets for unbalanced PSI.
+//!
+//! A [`CuckooFilter`] stores truncated fingerprints of outputs instead of the
+//! full outputs, which reduces the size of a published set to
+//! `fingerprint_length` bytes per item plus some slack. The false-positive rate
+//! is roughly `8 / 2^(8 * fingerprint_length)`, e.g. about `2^-29` for a
+//! `fingerprint_length` of 4.
+//!
+//! The server can [`insert()`](CuckooFilter::insert) and
+//! [`remove()`](CuckooFilter::remove) outputs after publishing the filter and
+//! send only the modified buckets to clients as a [`CuckooFilterDelta`], which
+//! clients [`apply()`](CuckooFilter::apply) to their copy.
+
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use core::ops::Range;
+
+use crate::error::{Error, Result};
+
+/// Number of fingerprints per bucket.
+const BUCKET_SIZE: usize = 4;
+/// Maximum number of evictions before an insertion fails.
+const MAX_KICKS: usize = 500;
+/// Maximum supported fingerprint length in bytes.
+const MAX_FINGERPRINT_LENGTH: u8 = 8;
+/// Maximum number of buckets as a power of two, so indices fit into [`u32`].
+const MAX_LOG2: u8 = 32;
+/// Multiplier used to derive the alternate bucket from a fingerprint.
+const MIX: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Cuckoo filter over OPRF outputs.
+///
+/// Outputs are expected to be uniformly random and at least `8 +
+/// fingerprint_length` bytes long, which is the case for the output of any
+/// [`CipherSuite`](crate::cipher_suite::CipherSuite).
+#[derive(Clone, Debug)]
+pub struct CuckooFilter {
+	/// Length of a fingerprint in bytes.
+	fingerprint_length: u8,
+	/// Number of buckets as a power of two.
+	log2: u8,
+	/// Fingerprints of all buckets, zero marks an empty slot.
+	table: Vec<u8>,
+	/// Buckets modified since the last [`CuckooFilter::delta()`].
+	dirty: BTreeSet<u32>,
+}
+
+/// Modified buckets of a [`CuckooFilter`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CuckooFilterDelta {
+	/// Length of a fingerprint in bytes.
+	fingerprint_length: u8,
+	/// Indices of the modified buckets.
+	indices: Vec<u32>,
+	/// Fingerprints of the modified buckets in order of `indices`.
+	table: Vec<u8>,
+}
+
+impl CuckooFilter {
+	/// Creates an empty [`CuckooFilter`] able to hold at least `capacity`
+	/// outputs with fingerprints of `fingerprint_length` bytes.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Filter`] if `fingerprint_length` is zero or larger than
+	/// 8 or `capacity` is too large.
+	pub fn new(capacity: usize, fingerprint_length: u8) -> Result<Self> {
+		if !(1..=MAX_FINGERPRINT_LENGTH).contains(&fingerprint_length) {
+			return Err(Error::Filter);
+		}
+
+		// Keep the load factor below 90% to make insertions unlikely to fail.
+		let buckets = capacity
+			.div_ceil(BUCKET_SIZE)
+			.max(1)
+			.checked_mul(10)
+			.map(|buckets| buckets.div_ceil(9))
+			.and_then(usize::checked_next_power_of_two)
+			.ok_or(Error::Filter)?;
+
+		let log2 = u8::try_from(buckets.trailing_zeros()).map_err(|_| Error::Filter)?;
+
+		if log2 > MAX_LOG2 {
+			return Err(Error::Filter);
+		}
+
+		let length = buckets
+			.checked_mul(BUCKET_SIZE * usize::from(fingerprint_length))
+			.ok_or(Error::Filter)?;
+
+		Ok(Self {
+			fingerprint_length,
+			log2,
+			table: vec![0; length],
+			dirty: BTreeSet::new(),
+		})
+	}
+
+	/// Returns the length of a fingerprint in bytes.
+	#[must_use]
+	pub const fn fingerprint_length(&self) -> u8 {
+		self.fingerprint_length
+	}
+
+	/// Returns the maximum number of outputs this [`CuckooFilter`] can hold.
+	/// Insertions might fail before reaching it.
+	#[must_use]
+	pub const fn capacity(&self) -> usize {
+		self.buckets() * BUCKET_SIZE
+	}
+
+	/// Inserts the provided `output`.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Filter`] if this [`CuckooFilter`] has no capacity left.
+	/// In which case it remains unchanged.
+	pub fn insert(&mut self, output: &[u8]) -> Result<()> {
+		let (index, fingerprint) = self.locate(output);
+		let alternate = self.alternate(index, fingerprint);
+
+		for index in [index, alternate] {
+			if self.try_place(index, fingerprint) {
+				return Ok(());
+			}
+		}
+
+		let mut path = Vec::new();
+		let mut index = alternate;
+		let mut fingerprint = fingerprint;
+
+		for kick in 0..MAX_KICKS {
+			let slot = kick % BUCKET_SIZE;
+			let evicted = self.get(index, slot);
+			self.set(index, slot, fingerprint);
+			path.push((index, slot, evicted));
+
+			fingerprint = evicted;
+			index = self.alternate(index, fingerprint);
+
+			if self.try_place(index, fingerprint) {
+				for (index, ..) in path {
+					self.mark_dirty(index);
+				}
+
+				return Ok(());
+			}
+		}
+
+		// Revert all evictions to not lose any previously inserted output.
+		for (index, slot, evicted) in path.into_iter().rev() {
+			self.set(index, slot, evicted);
+		}
+
+		Err(Error::Filter)
+	}
+
+	/// Removes the provided `output`. Returns [`false`] if it wasn't found.
+	///
+	/// Only outputs that were previously inserted must be removed, otherwise
+	/// an output sharing the same fingerprint might be removed instead.
+	pub fn remove(&mut self, output: &[u8]) -> bool {
+		let (index, fingerprint) = self.locate(output);
+
+		for index in [index, self.alternate(index, fingerprint)] {
+			if let Some(slot) = self.find(index, fingerprint) {
+				self.set(index, slot, 0);
+				self.mark_dirty(index);
+				return true;
+			}
+		}
+
+		false
+	}
+
+	/// Returns [`true`] if this [`CuckooFilter`] contains the provided
+	/// `output`. False positives are possible, see the
+	/// [module level documentation](self).
+	#[must_use]
+	pub fn contains(&self, output: &[u8]) -> bool {
+		let (index, fingerprint) = self.locate(output);
+
+		self.find(index, fingerprint).is_some()
+			|| self
+				.find(self.alternate(index, fingerprint), fingerprint)
+				.is_some()
+	}
+
+	/// Returns all buckets modified since the last call to this function or
+	/// the creation of this [`CuckooFilter`].
+	#[must_use]
+	pub fn delta(&mut self) -> CuckooFilterDelta {
+		let dirty = mem::take(&mut self.dirty);
+		let mut table = Vec::with_capacity(dirty.len() * self.bucket_length());
+
+		for index in &dirty {
+			table.extend_from_slice(self.bucket(*index));
+		}
+
+		CuckooFilterDelta {
+			fingerprint_length: self.fingerprint_length,
+			indices: dirty.into_iter().collect(),
+			table,
+		}
+	}
+
+	/// Discards all modifications tracked for the next
+	/// [`delta()`](Self::delta), e.g. after publishing the whole
+	/// [`CuckooFilter`].
+	pub fn clear_delta(&mut self) {
+		self.dirty.clear();
+	}
+
+	/// Applies a [`CuckooFilterDelta`] received from the server.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Filter`] if the [`CuckooFilterDelta`] doesn't belong to
+	/// a [`CuckooFilter`] with the same parameters. In which case this
+	/// [`CuckooFilter`] remains unchanged.
+	pub fn apply(&mut self, delta: &CuckooFilterDelta) -> Result<()> {
+		if delta.fingerprint_length != self.fingerprint_length
+			|| delta.indices.iter().any(|index| {
+				usize::try_from(*index)
+					.ok()
+					.is_none_or(|index| index >= self.buckets())
+			}) {
+			return Err(Error::Filter);
+		}
+
+		for (index, bucket) in delta
+			.indices
+			.iter()
+			.zip(delta.table.chunks_exact(self.bucket_length()))
+		{
+			self.bucket_mut(*index).copy_from_slice(bucket);
+		}
+
+		Ok(())
+	}
+
+	/// Deserializes a [`CuckooFilter`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let [fingerprint_length, log2, table @ ..] = repr else {
+			return Err(Error::FromRepr);
+		};
+
+		if !(1..=MAX_FINGERPRINT_LENGTH).contains(fingerprint_length) || *log2 > MAX_LOG2 {
+			return Err(Error::FromRepr);
+		}
+
+		let buckets = 1_usize
+			.checked_shl(u32::from(*log2))
+			.ok_or(Error::FromRepr)?;
+
+		if buckets.checked_mul(BUCKET_SIZE * usize::from(*fingerprint_length)) != Some(table.len())
+		{
+			return Err(Error::FromRepr);
+		}
+
+		Ok(Self {
+			fingerprint_length: *fingerprint_length,
+			log2: *log2,
+			table: table.to_vec(),
+			dirty: BTreeSet::new(),
+		})
+	}
+
+	/// Serializes this [`CuckooFilter`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		[self.fingerprint_length, self.log2]
+			.into_iter()
+			.chain(self.table.iter().copied())
+			.collect()
+	}
+
+	/// Returns the number of buckets.
+	const fn buckets(&self) -> usize {
+		1 << self.log2
+	}
+
+	/// Returns the number of bytes of a bucket.
+	fn bucket_length(&self) -> usize {
+		BUCKET_SIZE * usize::from(self.fingerprint_length)
+	}
+
+	/// Returns the range of the bucket at `index` in the table.
+	fn bucket_range(&self, index: usize) -> Range<usize> {
+		let start = index * self.bucket_length();
+		start..start + self.bucket_length()
+	}
+
+	/// Returns the bucket at `index`.
+	fn bucket(&self, index: u32) -> &[u8] {
+		let index = usize::try_from(index).expect("`u32` must fit into `usize`");
+		self.table
+			.get(self.bucket_range(index))
+			.expect("`index` must be in bounds")
+	}
+
+	/// Returns the bucket at `index` mutably.
+	fn bucket_mut(&mut self, index: u32) -> &mut [u8] {
+		let index = usize::try_from(index).expect("`u32` must fit into `usize`");
+		let range = self.bucket_range(index);
+		self.table
+			.get_mut(range)
+			.expect("`index` must be in bounds")
+	}
+
+	/// Marks the bucket at `index` as modified.
+	fn mark_dirty(&mut self, index: usize) {
+		self.dirty
+			.insert(u32::try_from(index).expect("number of buckets must fit into `u32`"));
+	}
+
+	/// Returns the range of the fingerprint at `slot` in the bucket at `index`.
+	fn slot_range(&self, index: usize, slot: usize) -> Range<usize> {
+		let length = usize::from(self.fingerprint_length);
+		let start = self.bucket_range(index).start + slot * length;
+		start..start + length
+	}
+
+	/// Returns the bucket index and fingerprint of the provided `output`.
+	fn locate(&self, output: &[u8]) -> (usize, u64) {
+		let mut index = [0; 8];
+		index
+			.iter_mut()
+			.zip(output)
+			.for_each(|(index, output)| *index = *output);
+
+		let mut fingerprint = [0; 8];
+		fingerprint
+			.iter_mut()
+			.zip(output.iter().skip(8))
+			.take(usize::from(self.fingerprint_length))
+			.for_each(|(fingerprint, output)| *fingerprint = *output);
+
+		// Zero marks an empty slot.
+		let fingerprint = u64::from_le_bytes(fingerprint).max(1);
+
+		(self.reduce(u64::from_le_bytes(index)), fingerprint)
+	}
+
+	/// Returns the alternate bucket index for a fingerprint in the bucket at
+	/// `index`.
+	fn alternate(&self, index: usize, fingerprint: u64) -> usize {
+		let index = u64::try_from(index).expect("`usize` must fit into `u64`");
+		self.reduce(index ^ fingerprint.wrapping_mul(MIX))
+	}
+
+	/// Reduces a hash to a bucket index.
+	fn reduce(&self, hash: u64) -> usize {
+		let mask = u64::try_from(self.buckets() - 1).expect("`usize` must fit into `u64`");
+		usize::try_from(hash & mask).expect("must be smaller than `buckets`")
+	}
+
+	/// Returns the slot of `fingerprint` in the bucket at `index`.
+	fn find(&self, index: usize, fingerprint: u64) -> Option<usize> {
+		(0..BUCKET_SIZE).find(|slot| self.get(index, *slot) == fingerprint)
+	}
+
+	/// Places `fingerprint` in an empty slot of the bucket at `index`. Returns
+	/// [`false`] if the bucket is full.
+	fn try_place(&mut self, index: usize, fingerprint: u64) -> bool {
+		let Some(slot) = self.find(index, 0) else {
+			return false;
+		};
+
+		self.set(index, slot, fingerprint);
+		self.mark_dirty(index);
+
+		true
+	}
+
+	/// Returns the fingerprint at `slot` in the bucket at `index`.
+	fn get(&self, index: usize, slot: usize) -> u64 {
+		let mut fingerprint = [0; 8];
+		fingerprint
+			.iter_mut()
+			.zip(
+				self.table
+					.get(self.slot_range(index, slot))
+					.expect("`index` and `slot` must be in bounds"),
+			)
+			.for_each(|(fingerprint, byte)| *fingerprint = *byte);
+
+		u64::from_le_bytes(fingerprint)
+	}
+
+	/// Sets the fingerprint at `slot` in the bucket at `index`.
+	fn set(&mut self, index: usize, slot: usize, fingerprint: u64) {
+		let range = self.slot_range(index, slot);
+		let length = range.len();
+
+		self.table
+			.get_mut(range)
+			.expect("`index` and `slot` must be in bounds")
+			.copy_from_slice(
+				fingerprint
+					.to_le_bytes()
+					.get(..length)
+					.expect("fingerprint length must be at most 8"),
+			);
+	}
+}
+
+impl CuckooFilterDelta {
+	/// Returns the number of modified buckets.
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.indices.len()
+	}
+
+	/// Returns [`true`] if no buckets were modified.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.indices.is_empty()
+	}
+
+	/// Deserializes a [`CuckooFilterDelta`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let [fingerprint_length, buckets @ ..] = repr else {
+			return Err(Error::FromRepr);
+		};
+
+		if !(1..=MAX_FINGERPRINT_LENGTH).contains(fingerprint_length) {
+			return Err(Error::FromRepr);
+		}
+
+		let chunks = buckets.chunks_exact(4 + BUCKET_SIZE * usize::from(*fingerprint_length));
+
+		if !chunks.remainder().is_empty() {
+			return Err(Error::FromRepr);
+		}
+
+		let mut indices = Vec::with_capacity(chunks.len());
+		let mut table = Vec::with_capacity(buckets.len() - chunks.len() * 4);
+
+		for chunk in chunks {
+			let (index, bucket) = chunk.split_first_chunk().ok_or(Error::FromRepr)?;
+			indices.push(u32::from_be_bytes(*index));
+			table.extend_from_slice(bucket);
+		}
+
+		Ok(Self {
+			fingerprint_length: *fingerprint_length,
+			indices,
+			table,
+		})
+	}
+
+	/// Serializes this [`CuckooFilterDelta`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		let bucket_length = BUCKET_SIZE * usize::from(self.fingerprint_length);
+
+		[self.fingerprint_length]
+			.into_iter()
+			.chain(
+				self.indices
+					.iter()
+					.zip(self.table.chunks_exact(bucket_length))
+					.flat_map(|(index, bucket)| {
+						index
+							.to_be_bytes()
+							.into_iter()
+							.chain(bucket.iter().copied())
+					}),
+			)
+			.collect()
+	}
+}
+
+impl Eq for CuckooFilter {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl PartialEq for CuckooFilter {
+	fn eq(&self, other: &Self) -> bool {
+		self.fingerprint_length.eq(&other.fingerprint_length)
+			&& self.log2.eq(&other.log2)
+			&& self.table.eq(&other.table)
+	}
+}
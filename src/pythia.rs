@@ -0,0 +1,453 @@
+//! [Pythia](https://eprint.iacr.org/2015/644)-style password hardening
+//! service based on [POPRF](crate::poprf).
+//!
+//! Every tenant of the service is identified by a public `tenant` identifier,
+//! which is used as the POPRF `info` to derive a per-tenant key from the single
+//! [`KeyPair`] of the [`PythiaServer`].
+//!
+//! 1. The client blinds a password with [`PythiaClient::blind()`] and sends the
+//!    [`BlindedElement`] to the server.
+//! 2. The server evaluates it with [`PythiaServer::harden()`] and sends the
+//!    [`EvaluationElement`] and [`Proof`] back to the client.
+//! 3. The client verifies the [`Proof`] against the [`PublicKey`] of the server
+//!    and computes the [`HardenedPassword`] with [`PythiaClient::finalize()`],
+//!    which can be stored instead of a password hash.
+//!
+//! # Key Rotation
+//!
+//! The server can rotate its [`KeyPair`] with [`PythiaServer::ratchet()`]. The
+//! returned [`PythiaRatchet`] produces an [`UpdateToken`] per tenant, which
+//! lets the tenant re-key all its stored [`HardenedPassword`]s with
+//! [`HardenedPassword::update()`] without involving users.
+//!
+//! Unlike regular POPRF outputs, [`HardenedPassword`]s are unblinded elements
+//! that aren't hashed, which is what makes them updatable.
+
+use core::fmt::{self, Debug, Formatter};
+use core::mem;
+
+use hybrid_array::Array;
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::{CipherSuite, ElementLength, NonZeroScalar, ScalarLength};
+use crate::common::{BlindEvaluateResult, BlindedElement, EvaluationElement, Proof};
+use crate::error::{Error, Result};
+use crate::group::Group;
+use crate::internal::{self, ElementWithRepr};
+use crate::key::{KeyPair, PublicKey};
+use crate::poprf::{PoprfBlindResult, PoprfClient, PoprfServer};
+
+/// Pythia client.
+pub struct PythiaClient<Cs: CipherSuite> {
+	/// The underlying [`PoprfClient`].
+	client: PoprfClient<Cs>,
+}
+
+/// Pythia server.
+pub struct PythiaServer<Cs: CipherSuite> {
+	/// The current [`KeyPair`].
+	key_pair: KeyPair<Cs::Group>,
+}
+
+/// Returned from [`PythiaServer::ratchet()`].
+pub struct PythiaRatchet<Cs: CipherSuite> {
+	/// The replaced [`KeyPair`].
+	old: KeyPair<Cs::Group>,
+	/// The new [`KeyPair`].
+	new: KeyPair<Cs::Group>,
+}
+
+/// A hardened password, see the [module level documentation](self).
+pub struct HardenedPassword<Cs: CipherSuite> {
+	/// The unblinded element.
+	element: ElementWithRepr<Cs::Group>,
+}
+
+/// Re-keys [`HardenedPassword`]s of a single tenant after a key rotation.
+pub struct UpdateToken<Cs: CipherSuite> {
+	/// `t_old / t_new`.
+	delta: NonZeroScalar<Cs>,
+}
+
+/// Returned from [`PythiaClient::blind()`].
+pub struct PythiaBlindResult<Cs: CipherSuite> {
+	/// The [`PythiaClient`].
+	pub client: PythiaClient<Cs>,
+	/// The [`BlindedElement`].
+	pub blinded_element: BlindedElement<Cs>,
+}
+
+impl<Cs: CipherSuite> PythiaClient<Cs> {
+	/// Blinds the provided `password`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the provided `password` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the `password` can never produce a valid
+	///   output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind<R>(rng: &mut R, password: &[u8]) -> Result<PythiaBlindResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let PoprfBlindResult {
+			client,
+			blinded_element,
+		} = PoprfClient::blind(rng, &[password])?;
+
+		Ok(PythiaBlindResult {
+			client: Self { client },
+			blinded_element,
+		})
+	}
+
+	/// Verifies the [`Proof`] and computes the [`HardenedPassword`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::InfoLength`] if the provided `tenant` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfo`] if the provided `tenant` can never produce a
+	///   valid output.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn finalize(
+		&self,
+		public_key: &PublicKey<Cs::Group>,
+		tenant: &[u8],
+		evaluation_element: &EvaluationElement<Cs>,
+		proof: &Proof<Cs>,
+	) -> Result<HardenedPassword<Cs>> {
+		let unblinded_element =
+			self.client
+				.finalize_element(public_key, evaluation_element, proof, tenant)?;
+
+		Ok(HardenedPassword {
+			element: unblinded_element.as_ref().clone(),
+		})
+	}
+}
+
+impl<Cs: CipherSuite> PythiaServer<Cs> {
+	/// Creates a new [`PythiaServer`] by generating a random [`KeyPair`].
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn new<R>(rng: &mut R) -> Result<Self, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		Ok(Self {
+			key_pair: KeyPair::generate(rng)?,
+		})
+	}
+
+	/// Creates a new [`PythiaServer`] from the provided [`KeyPair`].
+	#[must_use]
+	pub const fn from_key_pair(key_pair: KeyPair<Cs::Group>) -> Self {
+		Self { key_pair }
+	}
+
+	/// Returns the current [`KeyPair`].
+	#[must_use]
+	pub const fn key_pair(&self) -> &KeyPair<Cs::Group> {
+		&self.key_pair
+	}
+
+	/// Returns the current [`PublicKey`].
+	#[must_use]
+	pub const fn public_key(&self) -> &PublicKey<Cs::Group> {
+		self.key_pair.public_key()
+	}
+
+	/// Processes the [`BlindedElement`] of a [`PythiaClient`] for the provided
+	/// `tenant`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InfoLength`] if the provided `tenant` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfoDanger`] if the provided `tenant` maps to the
+	///   servers [`SecretKey`](crate::key::SecretKey), the client can be
+	///   assumed to know it and it should be [`ratchet()`](Self::ratchet)ed.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn harden<R>(
+		&self,
+		rng: &mut R,
+		tenant: &[u8],
+		blinded_element: &BlindedElement<Cs>,
+	) -> Result<BlindEvaluateResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		PoprfServer::from_key_pair(self.key_pair.clone(), tenant)
+			.map_err(Error::into_random::<R>)?
+			.blind_evaluate(rng, blinded_element)
+	}
+
+	/// Replaces the current [`KeyPair`] with a randomly generated one. The
+	/// returned [`PythiaRatchet`] creates [`UpdateToken`]s for tenants.
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails. In which case the current [`KeyPair`] remains
+	/// unchanged.
+	pub fn ratchet<R>(&mut self, rng: &mut R) -> Result<PythiaRatchet<Cs>, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let new = KeyPair::generate(rng)?;
+		let old = mem::replace(&mut self.key_pair, new.clone());
+
+		Ok(PythiaRatchet { old, new })
+	}
+}
+
+impl<Cs: CipherSuite> PythiaRatchet<Cs> {
+	/// Returns the new [`PublicKey`].
+	#[must_use]
+	pub const fn public_key(&self) -> &PublicKey<Cs::Group> {
+		self.new.public_key()
+	}
+
+	/// Creates the [`UpdateToken`] for the provided `tenant`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InfoLength`] if the provided `tenant` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInfoDanger`] if the provided `tenant` maps to the old
+	///   or new [`SecretKey`](crate::key::SecretKey).
+	pub fn update_token(&self, tenant: &[u8]) -> Result<UpdateToken<Cs>> {
+		let old = PoprfServer::<Cs>::from_key_pair(self.old.clone(), tenant)?;
+		let new = PoprfServer::<Cs>::from_key_pair(self.new.clone(), tenant)?;
+
+		Ok(UpdateToken {
+			delta: internal::non_zero_scalar_mul::<Cs::Group>(old.t(), new.t_inverted()),
+		})
+	}
+}
+
+impl<Cs: CipherSuite> HardenedPassword<Cs> {
+	/// Re-keys this [`HardenedPassword`] with the [`UpdateToken`] of its
+	/// tenant.
+	pub fn update(&mut self, update_token: &UpdateToken<Cs>) {
+		self.element = ElementWithRepr::new(update_token.delta * self.element.as_element());
+	}
+
+	/// Deserializes the provided `repr` to a [`HardenedPassword`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		ElementWithRepr::from_repr(repr).map(|element| Self { element })
+	}
+
+	/// Returns the representation of this [`HardenedPassword`].
+	#[must_use]
+	pub const fn as_repr(&self) -> &Array<u8, ElementLength<Cs>> {
+		self.element.as_repr()
+	}
+}
+
+impl<Cs: CipherSuite> UpdateToken<Cs> {
+	/// Deserializes the provided `repr` to an [`UpdateToken`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		repr.try_into()
+			.ok()
+			.and_then(|repr| Cs::Group::non_zero_scalar_from_repr(repr).ok())
+			.ok_or(Error::FromRepr)
+			.map(|delta| Self { delta })
+	}
+
+	/// Serializes this [`UpdateToken`].
+	#[must_use]
+	pub fn to_repr(&self) -> Array<u8, ScalarLength<Cs>> {
+		Cs::Group::scalar_to_repr(&self.delta)
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PythiaClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PythiaClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PythiaClient")
+			.field("client", &self.client)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PythiaClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PythiaClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client.eq(&other.client)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PythiaClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PythiaServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			key_pair: self.key_pair.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PythiaServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PythiaServer")
+			.field("key_pair", &self.key_pair)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PythiaServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PythiaServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.key_pair.eq(&other.key_pair)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PythiaServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for PythiaRatchet<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			old: self.old.clone(),
+			new: self.new.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PythiaRatchet<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PythiaRatchet")
+			.field("old", &self.old)
+			.field("new", &self.new)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for PythiaRatchet<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for PythiaRatchet<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.old.eq(&other.old) && self.new.eq(&other.new)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PythiaRatchet<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for HardenedPassword<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			element: self.element.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for HardenedPassword<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("HardenedPassword")
+			.field("element", &self.element)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for HardenedPassword<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for HardenedPassword<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.element.eq(&other.element)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for HardenedPassword<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for UpdateToken<Cs> {
+	fn clone(&self) -> Self {
+		Self { delta: self.delta }
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for UpdateToken<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("UpdateToken")
+			.field("delta", &self.delta)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for UpdateToken<Cs> {
+	fn drop(&mut self) {
+		self.delta.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for UpdateToken<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for UpdateToken<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.delta.eq(&other.delta)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for UpdateToken<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for PythiaBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PythiaBlindResult")
+			.field("client", &self.client)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for PythiaBlindResult<Cs> {}
@@ -0,0 +1,337 @@
+//! Oblivious relay for unlinkable [VOPRF](crate::voprf) evaluation.
+//!
+//! A [`Relay`] sits between a [`VoprfClient`] and a
+//! [`VoprfServer`](crate::voprf::VoprfServer). It re-blinds the clients
+//! [`BlindedElement`] with its own fresh scalar `r` before forwarding it, so
+//! the server only ever sees `r * B` and can't link requests to clients even
+//! when colluding with the network.
+//!
+//! The [`Relay`] verifies the servers [`Proof`] against the re-blinded
+//! elements, strips `r` from the [`EvaluationElement`] and proves to the client
+//! with its own [`Proof`] that it did so consistently. The client then
+//! [`finalize()`]s with both [`Proof`]s, which together show that the
+//! [`EvaluationElement`] was computed with the servers [`PublicKey`]. The
+//! relays [`Proof`] uses its own DST, so a [VOPRF](crate::voprf) [`Proof`]
+//! can't be passed off as one.
+
+use core::array;
+use core::fmt::{self, Debug, Formatter};
+
+use digest::Output;
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::{CipherSuite, NonZeroScalar};
+use crate::common::{BlindedElement, EvaluationElement, Mode, Proof};
+use crate::dleq::{self, Composites, Domain};
+use crate::error::{Error, Result};
+use crate::group::Group;
+use crate::internal::{self, ElementWithRepr};
+use crate::key::PublicKey;
+use crate::voprf::VoprfClient;
+
+/// DST of the relays [`Proof`].
+const PROOF_DST: &[u8] = b"OPRF-Relay";
+
+/// Oblivious relay state of a single request.
+pub struct Relay<Cs: CipherSuite> {
+	/// The relays blind `r`.
+	blind: NonZeroScalar<Cs>,
+	/// The [`BlindedElement`] received from the client.
+	blinded_element: BlindedElement<Cs>,
+	/// The [`BlindedElement`] forwarded to the server.
+	relayed_blinded_element: BlindedElement<Cs>,
+}
+
+/// Returned from [`Relay::blind()`].
+pub struct RelayBlindResult<Cs: CipherSuite> {
+	/// The [`Relay`].
+	pub relay: Relay<Cs>,
+	/// The [`BlindedElement`] to forward to the server.
+	pub blinded_element: BlindedElement<Cs>,
+}
+
+/// Returned from [`Relay::finalize()`] and sent to the client.
+pub struct RelayResponse<Cs: CipherSuite> {
+	/// The [`BlindedElement`] forwarded to the server.
+	pub relayed_blinded_element: BlindedElement<Cs>,
+	/// The [`EvaluationElement`] returned by the server.
+	pub relayed_evaluation_element: EvaluationElement<Cs>,
+	/// The [`Proof`] returned by the server.
+	pub relayed_proof: Proof<Cs>,
+	/// The relays per-request [`PublicKey`] `r * G`.
+	pub relay_key: PublicKey<Cs::Group>,
+	/// The [`EvaluationElement`] with the relays blind removed.
+	pub evaluation_element: EvaluationElement<Cs>,
+	/// Proves that [`Self::relay_key`] re-blinded the clients
+	/// [`BlindedElement`] and [`Self::evaluation_element`] consistently.
+	pub proof: Proof<Cs>,
+}
+
+impl<Cs: CipherSuite> Relay<Cs> {
+	/// Re-blinds the clients [`BlindedElement`] with a fresh random scalar.
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn blind<R>(
+		rng: &mut R,
+		blinded_element: &BlindedElement<Cs>,
+	) -> Result<RelayBlindResult<Cs>, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let blind = Cs::Group::scalar_random(rng)?;
+		let relayed_blinded_element =
+			BlindedElement::from(ElementWithRepr::new(blind * blinded_element.as_element()));
+
+		Ok(RelayBlindResult {
+			relay: Self {
+				blind,
+				blinded_element: blinded_element.clone(),
+				relayed_blinded_element: relayed_blinded_element.clone(),
+			},
+			blinded_element: relayed_blinded_element,
+		})
+	}
+
+	/// Verifies the servers [`Proof`], removes the relays blind from the
+	/// [`EvaluationElement`] and proves to the client that it did so.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Proof`] if the servers [`Proof`] is invalid.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn finalize<R>(
+		&self,
+		rng: &mut R,
+		public_key: &PublicKey<Cs::Group>,
+		evaluation_element: &EvaluationElement<Cs>,
+		proof: &Proof<Cs>,
+	) -> Result<RelayResponse<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		verify_server_proof(
+			public_key,
+			&self.relayed_blinded_element,
+			evaluation_element,
+			proof,
+		)
+		.map_err(Error::into_random::<R>)?;
+
+		let unblinded_element = EvaluationElement::from(ElementWithRepr::new(
+			Cs::Group::scalar_invert(&self.blind) * evaluation_element.as_element(),
+		));
+		let relay_key = PublicKey::new(Cs::Group::non_zero_scalar_mul_by_generator(&self.blind));
+
+		let generator = dleq::generator::<Cs>();
+		let domain = Domain::Custom {
+			dst: PROOF_DST,
+			A: &generator,
+		};
+		let composites = relay_composites(
+			domain,
+			Some(self.blind),
+			&relay_key,
+			&self.blinded_element,
+			&unblinded_element,
+			&self.relayed_blinded_element,
+			evaluation_element,
+		)
+		.map_err(Error::into_random::<R>)?;
+		let relay_proof =
+			dleq::generate_proof(domain, rng, self.blind, composites, relay_key.as_ref())?;
+
+		Ok(RelayResponse {
+			relayed_blinded_element: self.relayed_blinded_element.clone(),
+			relayed_evaluation_element: evaluation_element.clone(),
+			relayed_proof: proof.clone(),
+			relay_key,
+			evaluation_element: unblinded_element,
+			proof: relay_proof,
+		})
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for Relay<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			blind: self.blind,
+			blinded_element: self.blinded_element.clone(),
+			relayed_blinded_element: self.relayed_blinded_element.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for Relay<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Relay")
+			.field("blind", &self.blind)
+			.field("blinded_element", &self.blinded_element)
+			.field("relayed_blinded_element", &self.relayed_blinded_element)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for Relay<Cs> {
+	fn drop(&mut self) {
+		self.blind.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for Relay<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for Relay<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.blind.eq(&other.blind)
+			&& self.blinded_element.eq(&other.blinded_element)
+			&& self
+				.relayed_blinded_element
+				.eq(&other.relayed_blinded_element)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for Relay<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for RelayBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RelayBlindResult")
+			.field("relay", &self.relay)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for RelayBlindResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for RelayResponse<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RelayResponse")
+			.field("relayed_blinded_element", &self.relayed_blinded_element)
+			.field(
+				"relayed_evaluation_element",
+				&self.relayed_evaluation_element,
+			)
+			.field("relayed_proof", &self.relayed_proof)
+			.field("relay_key", &self.relay_key)
+			.field("evaluation_element", &self.evaluation_element)
+			.field("proof", &self.proof)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for RelayResponse<Cs> {}
+
+/// Verifies both [`Proof`]s of the [`RelayResponse`] and completes the
+/// evaluation.
+///
+/// The output is identical to the output of [`VoprfClient::finalize()`] with
+/// the server directly.
+///
+/// # Errors
+///
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and
+///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+/// - [`Error::Proof`] if either [`Proof`] is invalid.
+/// - [`Error::InputLength`] if the provided `input` exceeds a length of
+///   [`u16::MAX`].
+pub fn finalize<Cs: CipherSuite>(
+	client: &VoprfClient<Cs>,
+	public_key: &PublicKey<Cs::Group>,
+	input: &[&[u8]],
+	response: &RelayResponse<Cs>,
+) -> Result<Output<Cs::Hash>> {
+	verify_server_proof(
+		public_key,
+		&response.relayed_blinded_element,
+		&response.relayed_evaluation_element,
+		&response.relayed_proof,
+	)?;
+
+	let generator = dleq::generator::<Cs>();
+	let domain = Domain::Custom {
+		dst: PROOF_DST,
+		A: &generator,
+	};
+	let composites = relay_composites(
+		domain,
+		None,
+		&response.relay_key,
+		client.blinded_element(),
+		&response.evaluation_element,
+		&response.relayed_blinded_element,
+		&response.relayed_evaluation_element,
+	)?;
+	dleq::verify_proof(
+		domain,
+		composites,
+		response.relay_key.as_ref(),
+		&response.proof,
+	)?;
+
+	let unblinded_elements = internal::batch_finalize_element::<Cs, 1>(
+		[client.blind()],
+		array::from_ref(response.evaluation_element.as_element()).iter(),
+	);
+	let [output] = internal::batch_finalize::<Cs, 1>(&[input], unblinded_elements.iter(), None)?;
+
+	Ok(output)
+}
+
+/// Verifies the servers [`Proof`] over the elements seen by the server.
+fn verify_server_proof<Cs: CipherSuite>(
+	public_key: &PublicKey<Cs::Group>,
+	blinded_element: &BlindedElement<Cs>,
+	evaluation_element: &EvaluationElement<Cs>,
+	proof: &Proof<Cs>,
+) -> Result<()> {
+	let c = array::from_ref(blinded_element.as_ref()).iter();
+	let d = array::from_ref(evaluation_element.as_ref()).iter();
+
+	let composites = dleq::compute_composites::<_, 1>(
+		Domain::Rfc(Mode::Voprf),
+		None,
+		public_key.as_ref(),
+		c,
+		d,
+	)?;
+	dleq::verify_proof(
+		Domain::Rfc(Mode::Voprf),
+		composites,
+		public_key.as_ref(),
+		proof,
+	)
+}
+
+/// Computes the [`Composites`] of the relays [`Proof`], which proves that
+/// the relays blind maps the client-side elements to the relayed ones.
+fn relay_composites<Cs: CipherSuite>(
+	domain: Domain<'_, Cs>,
+	blind: Option<NonZeroScalar<Cs>>,
+	relay_key: &PublicKey<Cs::Group>,
+	blinded_element: &BlindedElement<Cs>,
+	evaluation_element: &EvaluationElement<Cs>,
+	relayed_blinded_element: &BlindedElement<Cs>,
+	relayed_evaluation_element: &EvaluationElement<Cs>,
+) -> Result<Composites<Cs>> {
+	let c = [blinded_element.as_ref(), evaluation_element.as_ref()].into_iter();
+	let d = [
+		relayed_blinded_element.as_ref(),
+		relayed_evaluation_element.as_ref(),
+	]
+	.into_iter();
+
+	dleq::compute_composites::<_, 2>(domain, blind, relay_key.as_ref(), c, d)
+}
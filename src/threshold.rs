@@ -0,0 +1,679 @@
+//! Threshold OPRF where the [`SecretKey`] is split between multiple servers via
+//! [Shamir secret sharing](https://dl.acm.org/doi/10.1145/359168.359176).
+//!
+//! Each server holds a [`KeyShare`] and returns an [`EvaluationShare`] for a
+//! [`BlindedElement`]. Any `threshold` [`EvaluationShare`]s can then be
+//! [`combine()`]d by the client via Lagrange interpolation and passed to the
+//! regular `finalize()`. The resulting output is identical to the output of a
+//! single server holding the full [`SecretKey`].
+//!
+//! [`EvaluationShare`]s can optionally carry a [`Proof`] against the
+//! [`PublicKeyShare`] of the server, see
+//! [`ThresholdServer::verifiable_blind_evaluate()`] and
+//! [`verifiable_finalize()`].
+//!
+//! [`KeyShare`]s can either be [`split()`] by a trusted dealer or generated
+//! without one via [`dkg`]. They can be periodically re-randomized via
+//! [`refresh`] without changing the group [`PublicKey`].
+
+pub mod dkg;
+pub mod refresh;
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter;
+use core::num::NonZeroU16;
+use core::ops::Deref;
+
+#[cfg(feature = "serde")]
+use ::serde::ser::SerializeStruct;
+#[cfg(feature = "serde")]
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+use digest::Output;
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::{CipherSuite, NonZeroScalar};
+use crate::common::{BlindedElement, EvaluationElement, Mode, Proof};
+use crate::dleq::{self, Domain};
+use crate::error::{Error, Result};
+use crate::group::Group;
+use crate::internal::{self, ElementWithRepr};
+use crate::key::{KeyPair, PublicKey, SecretKey};
+#[cfg(feature = "serde")]
+use crate::serde;
+use crate::voprf::VoprfClient;
+
+/// A share of a [`SecretKey`] together with its index.
+///
+/// Created by [`split()`].
+pub struct KeyShare<G: Group> {
+	/// The index of this share.
+	index: NonZeroU16,
+	/// The [`KeyPair`] of this share.
+	key_pair: KeyPair<G>,
+}
+
+/// The [`PublicKey`] of a [`KeyShare`] together with its index.
+///
+/// Used to verify the [`Proof`] of an [`EvaluationShare`].
+pub struct PublicKeyShare<G: Group> {
+	/// The index of this share.
+	index: NonZeroU16,
+	/// The [`PublicKey`] of this share.
+	public_key: PublicKey<G>,
+}
+
+/// Threshold OPRF server holding a [`KeyShare`].
+pub struct ThresholdServer<Cs: CipherSuite> {
+	/// [`KeyShare`].
+	key_share: KeyShare<Cs::Group>,
+}
+
+/// Returned by [`ThresholdServer::blind_evaluate()`]. Sent to the client to be
+/// [`combine()`]d.
+pub struct EvaluationShare<Cs: CipherSuite> {
+	/// The index of the [`KeyShare`] this was evaluated with.
+	pub index: NonZeroU16,
+	/// The partial [`EvaluationElement`].
+	pub evaluation_element: EvaluationElement<Cs>,
+	/// The optional [`Proof`] against the corresponding [`PublicKeyShare`].
+	pub proof: Option<Proof<Cs>>,
+}
+
+impl<G: Group> KeyShare<G> {
+	/// Creates a new [`KeyShare`].
+	#[must_use]
+	pub fn new(index: NonZeroU16, secret_key: SecretKey<G>) -> Self {
+		Self {
+			index,
+			key_pair: KeyPair::from_secret_key(secret_key),
+		}
+	}
+
+	/// Returns the index of this [`KeyShare`].
+	#[must_use]
+	pub const fn index(&self) -> NonZeroU16 {
+		self.index
+	}
+
+	/// Returns the [`KeyPair`] of this [`KeyShare`].
+	#[must_use]
+	pub const fn key_pair(&self) -> &KeyPair<G> {
+		&self.key_pair
+	}
+
+	/// Returns the corresponding [`PublicKeyShare`].
+	#[must_use]
+	pub fn public_key_share(&self) -> PublicKeyShare<G> {
+		PublicKeyShare::new(self.index, self.key_pair.public_key().clone())
+	}
+}
+
+impl<G: Group> PublicKeyShare<G> {
+	/// Creates a new [`PublicKeyShare`].
+	#[must_use]
+	pub const fn new(index: NonZeroU16, public_key: PublicKey<G>) -> Self {
+		Self { index, public_key }
+	}
+
+	/// Returns the index of this [`PublicKeyShare`].
+	#[must_use]
+	pub const fn index(&self) -> NonZeroU16 {
+		self.index
+	}
+
+	/// Returns the [`PublicKey`] of this [`PublicKeyShare`].
+	#[must_use]
+	pub const fn public_key(&self) -> &PublicKey<G> {
+		&self.public_key
+	}
+}
+
+impl<Cs: CipherSuite> ThresholdServer<Cs> {
+	/// Creates a new [`ThresholdServer`] from the provided [`KeyShare`].
+	#[must_use]
+	pub const fn from_key_share(key_share: KeyShare<Cs::Group>) -> Self {
+		Self { key_share }
+	}
+
+	/// Returns the [`KeyShare`].
+	#[must_use]
+	pub const fn key_share(&self) -> &KeyShare<Cs::Group> {
+		&self.key_share
+	}
+
+	/// Process the [`BlindedElement`] without a [`Proof`].
+	///
+	/// Corresponds to
+	/// [`BlindEvaluate()` in RFC 9497 § 3.3.1](https://www.rfc-editor.org/rfc/rfc9497.html#section-3.3.1-4)
+	/// with the [`KeyShare`].
+	#[must_use]
+	pub fn blind_evaluate(&self, blinded_element: &BlindedElement<Cs>) -> EvaluationShare<Cs> {
+		let [evaluation_element] = EvaluationElement::new_batch(iter::once((
+			*blinded_element.as_element(),
+			self.secret_scalar(),
+		)));
+
+		EvaluationShare {
+			index: self.key_share.index,
+			evaluation_element,
+			proof: None,
+		}
+	}
+
+	/// Process the [`BlindedElement`] and generate a [`Proof`] against the
+	/// [`PublicKeyShare`].
+	///
+	/// Corresponds to
+	/// [`BlindEvaluate()` in RFC 9497 § 3.3.2](https://www.rfc-editor.org/rfc/rfc9497.html#section-3.3.2-2)
+	/// with the [`KeyShare`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn verifiable_blind_evaluate<R>(
+		&self,
+		rng: &mut R,
+		blinded_element: &BlindedElement<Cs>,
+	) -> Result<EvaluationShare<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let mut share = self.blind_evaluate(blinded_element);
+		let public_key = self.key_share.key_pair.public_key();
+
+		let composites = dleq::compute_composites::<_, 1>(
+			Domain::Rfc(Mode::Voprf),
+			Some(self.secret_scalar()),
+			public_key.as_ref(),
+			iter::once(blinded_element.as_ref()),
+			iter::once(share.evaluation_element.as_ref()),
+		)
+		.map_err(Error::into_random::<R>)?;
+		let proof = dleq::generate_proof(
+			Domain::Rfc(Mode::Voprf),
+			rng,
+			self.secret_scalar(),
+			composites,
+			public_key.as_ref(),
+		)?;
+
+		share.proof = Some(proof);
+
+		Ok(share)
+	}
+
+	/// Returns the [`NonZeroScalar`] of the [`KeyShare`].
+	fn secret_scalar(&self) -> NonZeroScalar<Cs> {
+		self.key_share.key_pair.secret_key().to_scalar()
+	}
+}
+
+impl<Cs: CipherSuite> EvaluationShare<Cs> {
+	/// Verifies the [`Proof`] of this [`EvaluationShare`] against the provided
+	/// [`PublicKeyShare`] and [`BlindedElement`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::Threshold`] if the index of the [`PublicKeyShare`] doesn't
+	///   match.
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Proof`] if the [`Proof`] is missing or invalid.
+	pub fn verify(
+		&self,
+		public_key_share: &PublicKeyShare<Cs::Group>,
+		blinded_element: &BlindedElement<Cs>,
+	) -> Result<()> {
+		if self.index != public_key_share.index {
+			return Err(Error::Threshold);
+		}
+
+		let proof = self.proof.as_ref().ok_or(Error::Proof)?;
+		let public_key = public_key_share.public_key.as_ref();
+
+		let composites = dleq::compute_composites::<_, 1>(
+			Domain::Rfc(Mode::Voprf),
+			None,
+			public_key,
+			iter::once(blinded_element.as_ref()),
+			iter::once(self.evaluation_element.as_ref()),
+		)?;
+		dleq::verify_proof(Domain::Rfc(Mode::Voprf), composites, public_key, proof)
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Clone for KeyShare<G> {
+	fn clone(&self) -> Self {
+		Self {
+			index: self.index,
+			key_pair: self.key_pair.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for KeyShare<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("KeyShare")
+			.field("index", &self.index)
+			.field("key_pair", &self.key_pair)
+			.finish()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de, G> Deserialize<'de> for KeyShare<G>
+where
+	G: Group,
+	G::NonZeroScalar: Deserialize<'de>,
+{
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		serde::struct_2(deserializer, "KeyShare", &["index", "key_pair"])
+			.map(|(index, key_pair)| Self { index, key_pair })
+	}
+}
+
+impl<G: Group> Eq for KeyShare<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> PartialEq for KeyShare<G> {
+	fn eq(&self, other: &Self) -> bool {
+		self.index.eq(&other.index) && self.key_pair.eq(&other.key_pair)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<G> Serialize for KeyShare<G>
+where
+	G: Group,
+	G::NonZeroScalar: Serialize,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut state = serializer.serialize_struct("KeyShare", 2)?;
+		state.serialize_field("index", &self.index)?;
+		state.serialize_field("key_pair", &self.key_pair)?;
+		state.end()
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for KeyShare<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Clone for PublicKeyShare<G> {
+	fn clone(&self) -> Self {
+		Self {
+			index: self.index,
+			public_key: self.public_key.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for PublicKeyShare<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PublicKeyShare")
+			.field("index", &self.index)
+			.field("public_key", &self.public_key)
+			.finish()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de, G: Group> Deserialize<'de> for PublicKeyShare<G> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		serde::struct_2(deserializer, "PublicKeyShare", &["index", "public_key"])
+			.map(|(index, public_key)| Self { index, public_key })
+	}
+}
+
+impl<G: Group> Eq for PublicKeyShare<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> PartialEq for PublicKeyShare<G> {
+	fn eq(&self, other: &Self) -> bool {
+		self.index.eq(&other.index) && self.public_key.eq(&other.public_key)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<G: Group> Serialize for PublicKeyShare<G> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut state = serializer.serialize_struct("PublicKeyShare", 2)?;
+		state.serialize_field("index", &self.index)?;
+		state.serialize_field("public_key", &self.public_key)?;
+		state.end()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for ThresholdServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			key_share: self.key_share.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for ThresholdServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ThresholdServer")
+			.field("key_share", &self.key_share)
+			.finish()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Cs> Deserialize<'de> for ThresholdServer<Cs>
+where
+	Cs: CipherSuite,
+	NonZeroScalar<Cs>: Deserialize<'de>,
+{
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		serde::newtype_struct(deserializer, "ThresholdServer").map(|key_share| Self { key_share })
+	}
+}
+
+impl<Cs: CipherSuite> Eq for ThresholdServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for ThresholdServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.key_share.eq(&other.key_share)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<Cs> Serialize for ThresholdServer<Cs>
+where
+	Cs: CipherSuite,
+	NonZeroScalar<Cs>: Serialize,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_newtype_struct("ThresholdServer", &self.key_share)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for ThresholdServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for EvaluationShare<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("EvaluationShare")
+			.field("index", &self.index)
+			.field("evaluation_element", &self.evaluation_element)
+			.field("proof", &self.proof)
+			.finish()
+	}
+}
+
+/// Splits the provided [`SecretKey`] into `count` [`KeyShare`]s, any
+/// `threshold` of which can jointly evaluate.
+///
+/// The caller acts as a trusted dealer and learns the full [`SecretKey`]. The
+/// [`KeyShare`]s are indexed from `1` to `count`.
+///
+/// # Errors
+///
+/// - [`Error::Threshold`] if `threshold` is zero or larger than `count`.
+/// - [`Error::Random`] if the provided `rng` fails.
+pub fn split<G, R>(
+	rng: &mut R,
+	secret_key: &SecretKey<G>,
+	threshold: u16,
+	count: u16,
+) -> Result<Vec<KeyShare<G>>, Error<R::Error>>
+where
+	G: Group,
+	R: ?Sized + TryCryptoRng,
+{
+	if threshold == 0 || threshold > count {
+		return Err(Error::Threshold);
+	}
+
+	let mut coefficients =
+		random_polynomial::<G, R>(rng, (*secret_key.as_scalar()).into(), threshold)
+			.map_err(Error::Random)?;
+
+	let key_shares = (1..=count)
+		.filter_map(NonZeroU16::new)
+		.map(|index| {
+			let scalar = evaluate_polynomial::<G>(&coefficients, index)
+				.try_into()
+				.map_err(|_| Error::Threshold)?;
+
+			Ok(KeyShare::new(index, SecretKey::new(scalar)))
+		})
+		.collect();
+
+	coefficients.iter_mut().for_each(Zeroize::zeroize);
+
+	key_shares
+}
+
+/// Combines the provided [`EvaluationShare`]s into an [`EvaluationElement`] via
+/// Lagrange interpolation.
+///
+/// The returned [`EvaluationElement`] can be passed to the regular
+/// `finalize()`. [`Proof`]s are *not* verified, see [`verifiable_finalize()`].
+///
+/// # Errors
+///
+/// Returns [`Error::Threshold`] if fewer than `threshold` `shares` were
+/// provided, `shares` contain duplicate indices or don't combine to a valid
+/// [`EvaluationElement`].
+pub fn combine<Cs: CipherSuite>(
+	threshold: u16,
+	shares: &[EvaluationShare<Cs>],
+) -> Result<EvaluationElement<Cs>> {
+	if threshold == 0 || shares.len() < threshold.into() {
+		return Err(Error::Threshold);
+	}
+
+	let indices: Vec<_> = shares.iter().map(|share| share.index).collect();
+	let elements = shares
+		.iter()
+		.map(|share| (*share.evaluation_element.as_element()).into());
+
+	interpolate::<Cs::Group, _>(&indices, elements)
+		.map(ElementWithRepr::new)
+		.map(EvaluationElement::from)
+}
+
+/// Verifies the [`Proof`]s of the provided [`EvaluationShare`]s,
+/// [`combine()`]s them and completes the evaluation.
+///
+/// The output is identical to the output of
+/// [`VoprfClient::finalize()`] with a [`VoprfServer`] holding the full
+/// [`SecretKey`].
+///
+/// [`VoprfServer`]: crate::voprf::VoprfServer
+///
+/// # Errors
+///
+/// - [`Error::Threshold`] if fewer than `threshold` `shares` were provided,
+///   `shares` contain duplicate indices, an index is missing from
+///   `public_key_shares` or `shares` don't combine to a valid
+///   [`EvaluationElement`].
+/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+///   [`Group`](CipherSuite::Group) and
+///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+/// - [`Error::Proof`] if a [`Proof`] is missing or invalid.
+/// - [`Error::InputLength`] if the provided `input` exceeds a length of
+///   [`u16::MAX`].
+pub fn verifiable_finalize<Cs: CipherSuite>(
+	client: &VoprfClient<Cs>,
+	threshold: u16,
+	public_key_shares: &[PublicKeyShare<Cs::Group>],
+	input: &[&[u8]],
+	shares: &[EvaluationShare<Cs>],
+) -> Result<Output<Cs::Hash>> {
+	for share in shares {
+		let public_key_share = public_key_shares
+			.iter()
+			.find(|public_key_share| public_key_share.index == share.index)
+			.ok_or(Error::Threshold)?;
+
+		share.verify(public_key_share, client.blinded_element())?;
+	}
+
+	let evaluation_element = combine(threshold, shares)?;
+
+	let unblinded_elements = internal::batch_finalize_element::<Cs, 1>(
+		[client.blind()],
+		iter::once(evaluation_element.as_element()),
+	);
+	let [output] = internal::batch_finalize::<Cs, 1>(&[input], unblinded_elements.iter(), None)?;
+
+	Ok(output)
+}
+
+/// Generates a random polynomial of degree `threshold - 1` with the provided
+/// `constant` term.
+///
+/// # Errors
+///
+/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+/// provided `rng` fails.
+pub(crate) fn random_polynomial<G, R>(
+	rng: &mut R,
+	constant: G::Scalar,
+	threshold: u16,
+) -> Result<Vec<G::Scalar>, R::Error>
+where
+	G: Group,
+	R: ?Sized + TryCryptoRng,
+{
+	let mut coefficients = Vec::with_capacity(threshold.into());
+	coefficients.push(constant);
+
+	for _ in 1..threshold {
+		coefficients.push(G::scalar_random(rng)?.into());
+	}
+
+	Ok(coefficients)
+}
+
+/// Evaluates the polynomial with the provided `coefficients` at `index`.
+pub(crate) fn evaluate_polynomial<G: Group>(
+	coefficients: &[G::Scalar],
+	index: NonZeroU16,
+) -> G::Scalar {
+	let x = G::scalar_from_u64(index.get().into());
+
+	coefficients
+		.iter()
+		.rev()
+		.fold(G::Scalar::default(), |result, coefficient| {
+			result * &x + coefficient
+		})
+}
+
+/// Computes the Lagrange coefficients at zero for the provided `indices`.
+///
+/// # Errors
+///
+/// Returns [`Error::Threshold`] if `indices` contains duplicates.
+pub(crate) fn lagrange_coefficients<G: Group>(indices: &[NonZeroU16]) -> Result<Vec<G::Scalar>> {
+	let mut numerators = Vec::with_capacity(indices.len());
+	let mut denominators = Vec::with_capacity(indices.len());
+
+	for (position, index) in indices.iter().enumerate() {
+		let xi = G::scalar_from_u64(index.get().into());
+		let mut numerator = G::scalar_from_u64(1);
+		let mut denominator = G::scalar_from_u64(1);
+
+		for (other_position, other) in indices.iter().enumerate() {
+			if position == other_position {
+				continue;
+			}
+
+			let xj = G::scalar_from_u64(other.get().into());
+			numerator = numerator * &xj;
+			denominator = denominator * &(xj - &xi);
+		}
+
+		// Only zero if `indices` contains duplicates.
+		let denominator: G::NonZeroScalar = denominator.try_into().map_err(|_| Error::Threshold)?;
+
+		numerators.push(numerator);
+		denominators.push(denominator);
+	}
+
+	Ok(G::scalar_batch_alloc_invert(denominators)
+		.into_iter()
+		.zip(numerators)
+		.map(|(denominator, numerator)| numerator * denominator.deref())
+		.collect())
+}
+
+/// Interpolates the provided `elements` at zero.
+///
+/// # Errors
+///
+/// Returns [`Error::Threshold`] if `indices` contains duplicates or the result
+/// is the identity element.
+pub(crate) fn interpolate<G, I>(
+	indices: &[NonZeroU16],
+	elements: I,
+) -> Result<G::NonIdentityElement>
+where
+	G: Group,
+	I: ExactSizeIterator<Item = G::Element>,
+{
+	debug_assert_eq!(indices.len(), elements.len(), "found unequal item length");
+
+	let elements_and_scalars: Vec<_> = elements.zip(lagrange_coefficients::<G>(indices)?).collect();
+
+	G::alloc_lincomb(&elements_and_scalars)
+		.try_into()
+		.map_err(|_| Error::Threshold)
+}
+
+/// Evaluates the provided Feldman `commitments` to the coefficients of a
+/// polynomial at `index` in the exponent.
+pub(crate) fn evaluate_commitments<G, I>(commitments: I, index: NonZeroU16) -> G::Element
+where
+	G: Group,
+	I: Iterator<Item = G::Element>,
+{
+	let x = G::scalar_from_u64(index.get().into());
+	let mut power = G::scalar_from_u64(1);
+
+	let elements_and_scalars: Vec<_> = commitments
+		.map(|commitment| {
+			let element_and_scalar = (commitment, power);
+			power = power * &x;
+			element_and_scalar
+		})
+		.collect();
+
+	G::alloc_lincomb(&elements_and_scalars)
+}
+
+/// Verifies the provided `share` at `index` against the Feldman `commitments`.
+pub(crate) fn verify_share<G, I>(commitments: I, index: NonZeroU16, share: &G::Scalar) -> bool
+where
+	G: Group,
+	I: Iterator<Item = G::Element>,
+{
+	let expected = evaluate_commitments::<G, _>(commitments, index);
+
+	G::element_to_repr(&G::scalar_mul_by_generator(share)) == G::element_to_repr(&expected)
+}
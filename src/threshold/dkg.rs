@@ -0,0 +1,876 @@
+//! Dealerless distributed key generation of [`KeyShare`]s.
+//!
+//! Implements the [Pedersen DKG](https://doi.org/10.1007/3-540-46416-6_47)
+//! with [Feldman verifiable secret sharing](https://doi.org/10.1109/SFCS.1987.4)
+//! as a sans-IO round-based state machine:
+//!
+//! 1. Every participant creates a [`DkgRound1`], broadcasts its
+//!    [`DkgCommitment`] and sends every other participant its [`DkgShare`].
+//! 2. Every participant [`receive()`](DkgRound1::receive)s all messages
+//!    addressed to it and broadcasts its [`DkgComplaint`]s against dealers whose
+//!    [`DkgShare`] was missing or didn't match their [`DkgCommitment`].
+//! 3. Every accused dealer broadcasts the [`DkgShare`]s returned by
+//!    [`DkgRound2::justify()`].
+//! 4. Every participant calls [`DkgRound2::finish()`] with all broadcast
+//!    [`DkgComplaint`]s and justifications.
+//!
+//! Dealers that didn't broadcast exactly one valid [`DkgCommitment`] or failed
+//! to justify a [`DkgComplaint`] are [disqualified](DkgOutput::disqualified)
+//! and don't contribute to the resulting [`SecretKey`]. Participants whose
+//! [`DkgComplaint`] was justified are
+//! [recorded](DkgOutput::false_complainers), but the DKG still completes.
+//!
+//! Broadcasts are assumed to be reliable, i.e. every participant receives the
+//! same broadcast messages.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::num::NonZeroU16;
+use core::ops::Deref;
+use core::{iter, mem};
+
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::{KeyShare, PublicKeyShare};
+use crate::error::{Error, Result};
+use crate::group::Group;
+use crate::internal::ElementWithRepr;
+use crate::key::{PublicKey, SecretKey};
+use crate::util::I2osp;
+
+/// First round of the DKG. See the [module level documentation](self).
+pub struct DkgRound1<G: Group> {
+	/// Our index.
+	index: NonZeroU16,
+	/// The threshold.
+	threshold: u16,
+	/// The number of participants.
+	participants: u16,
+	/// Whether the constant term is zero and not committed to.
+	zero: bool,
+	/// The coefficients of our polynomial.
+	coefficients: Vec<G::Scalar>,
+	/// Our [`DkgCommitment`].
+	commitment: DkgCommitment<G>,
+}
+
+/// Second round of the DKG. See the [module level documentation](self).
+pub struct DkgRound2<G: Group> {
+	/// Our index.
+	index: NonZeroU16,
+	/// The threshold.
+	threshold: u16,
+	/// Whether the constant term is zero and not committed to.
+	zero: bool,
+	/// The coefficients of our polynomial.
+	coefficients: Vec<G::Scalar>,
+	/// The commitments of all dealers that weren't disqualified yet.
+	commitments: BTreeMap<NonZeroU16, Vec<ElementWithRepr<G>>>,
+	/// The valid shares we received.
+	shares: BTreeMap<NonZeroU16, G::Scalar>,
+	/// Our [`DkgComplaint`]s.
+	complaints: Vec<DkgComplaint>,
+	/// Dealers that didn't broadcast a valid [`DkgCommitment`].
+	disqualified: Vec<NonZeroU16>,
+}
+
+/// Broadcast by every dealer in the first round. Holds Feldman commitments to
+/// the coefficients of the dealers polynomial, excluding the constant term when
+/// [refreshing](super::refresh).
+pub struct DkgCommitment<G: Group> {
+	/// The index of the dealer.
+	dealer: NonZeroU16,
+	/// The commitments to the coefficients.
+	coefficients: Vec<ElementWithRepr<G>>,
+}
+
+/// Sent privately from a dealer to every other participant in the first round.
+///
+/// Also broadcast publicly to justify a [`DkgComplaint`].
+pub struct DkgShare<G: Group> {
+	/// The index of the dealer.
+	dealer: NonZeroU16,
+	/// The index of the recipient.
+	recipient: NonZeroU16,
+	/// The share.
+	share: G::Scalar,
+}
+
+/// Broadcast by a participant in the second round if a dealers [`DkgShare`]
+/// was missing or didn't match its [`DkgCommitment`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DkgComplaint {
+	/// The index of the complaining participant.
+	complainer: NonZeroU16,
+	/// The index of the accused dealer.
+	dealer: NonZeroU16,
+}
+
+/// Returned by [`DkgRound2::finish()`].
+pub struct DkgOutput<G: Group> {
+	/// Our [`KeyShare`].
+	key_share: KeyShare<G>,
+	/// The group [`PublicKey`].
+	public_key: PublicKey<G>,
+	/// Feldman commitments to the coefficients of the combined polynomial.
+	commitments: Vec<ElementWithRepr<G>>,
+	/// Disqualified dealers.
+	disqualified: Vec<NonZeroU16>,
+	/// Participants whose [`DkgComplaint`] was justified.
+	false_complainers: Vec<NonZeroU16>,
+}
+
+impl<G: Group> DkgRound1<G> {
+	/// Starts the DKG for the participant with the provided `index` out of
+	/// `participants`, any `threshold` of which can jointly evaluate.
+	///
+	/// Participants are indexed from `1` to `participants`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Threshold`] if `threshold` is zero or larger than
+	///   `participants` or `index` is larger than `participants`.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn new<R>(
+		rng: &mut R,
+		index: NonZeroU16,
+		threshold: u16,
+		participants: u16,
+	) -> Result<Self, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		Self::new_internal(rng, index, threshold, participants, false)
+	}
+
+	/// Starts the DKG. If `zero` is `true` the constant term of our polynomial
+	/// is zero and not committed to.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Threshold`] if `threshold` is zero or larger than
+	///   `participants` or `index` is larger than `participants`.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub(super) fn new_internal<R>(
+		rng: &mut R,
+		index: NonZeroU16,
+		threshold: u16,
+		participants: u16,
+		zero: bool,
+	) -> Result<Self, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		if threshold == 0 || threshold > participants || index.get() > participants {
+			return Err(Error::Threshold);
+		}
+
+		let mut coefficients = Vec::with_capacity(threshold.into());
+		let mut commitments = Vec::with_capacity(threshold.into());
+
+		if zero {
+			coefficients.push(G::Scalar::default());
+		}
+
+		for _ in u16::from(zero)..threshold {
+			let coefficient = G::scalar_random(rng).map_err(Error::Random)?;
+			commitments.push(ElementWithRepr::new(G::non_zero_scalar_mul_by_generator(
+				&coefficient,
+			)));
+			coefficients.push(coefficient.into());
+		}
+
+		Ok(Self {
+			index,
+			threshold,
+			participants,
+			zero,
+			coefficients,
+			commitment: DkgCommitment {
+				dealer: index,
+				coefficients: commitments,
+			},
+		})
+	}
+
+	/// Returns our [`DkgCommitment`] to be broadcast to all participants.
+	#[must_use]
+	pub const fn commitment(&self) -> &DkgCommitment<G> {
+		&self.commitment
+	}
+
+	/// Returns the [`DkgShare`]s to be sent privately to every other
+	/// participant.
+	#[must_use]
+	pub fn shares(&self) -> Vec<DkgShare<G>> {
+		(1..=self.participants)
+			.filter_map(NonZeroU16::new)
+			.filter(|recipient| *recipient != self.index)
+			.map(|recipient| share(self.index, &self.coefficients, recipient))
+			.collect()
+	}
+
+	/// Processes the [`DkgCommitment`]s broadcast by all dealers and the
+	/// [`DkgShare`]s addressed to us.
+	///
+	/// Our own [`DkgCommitment`] and messages with an unknown dealer or
+	/// recipient are ignored. Dealers without a valid [`DkgCommitment`] or with
+	/// conflicting [`DkgCommitment`]s are disqualified. Dealers whose
+	/// [`DkgShare`] is missing, conflicting or invalid are listed in
+	/// [`DkgRound2::complaints()`].
+	#[must_use]
+	pub fn receive(
+		mut self,
+		commitments: &[DkgCommitment<G>],
+		shares: &[DkgShare<G>],
+	) -> DkgRound2<G> {
+		let mut received_commitments = BTreeMap::new();
+
+		for commitment in commitments {
+			if commitment.dealer == self.index || commitment.dealer.get() > self.participants {
+				continue;
+			}
+
+			received_commitments
+				.entry(commitment.dealer)
+				.and_modify(|existing: &mut Option<&DkgCommitment<G>>| {
+					if *existing != Some(commitment) {
+						*existing = None;
+					}
+				})
+				.or_insert(Some(commitment));
+		}
+
+		let mut valid_commitments: BTreeMap<_, _> = received_commitments
+			.into_iter()
+			.filter_map(|(dealer, commitment)| {
+				commitment
+					.filter(|commitment| {
+						commitment.coefficients.len()
+							== usize::from(self.threshold - u16::from(self.zero))
+					})
+					.map(|commitment| (dealer, commitment.coefficients.clone()))
+			})
+			.collect();
+		valid_commitments.insert(self.index, mem::take(&mut self.commitment.coefficients));
+
+		let mut received = BTreeMap::new();
+
+		for share in shares {
+			if share.recipient != self.index || share.dealer == self.index {
+				continue;
+			}
+
+			received
+				.entry(share.dealer)
+				.and_modify(|existing: &mut Option<G::Scalar>| {
+					if *existing != Some(share.share) {
+						existing.zeroize();
+					}
+				})
+				.or_insert(Some(share.share));
+		}
+
+		received.insert(
+			self.index,
+			Some(super::evaluate_polynomial::<G>(
+				&self.coefficients,
+				self.index,
+			)),
+		);
+
+		let mut valid_shares = BTreeMap::new();
+		let mut complaints = Vec::new();
+
+		for (dealer, commitment) in &valid_commitments {
+			match received.get(dealer) {
+				Some(Some(share))
+					if super::verify_share::<G, _>(
+						elements(self.zero, commitment),
+						self.index,
+						share,
+					) =>
+				{
+					valid_shares.insert(*dealer, *share);
+				}
+				_ => complaints.push(DkgComplaint {
+					complainer: self.index,
+					dealer: *dealer,
+				}),
+			}
+		}
+
+		received.values_mut().for_each(Zeroize::zeroize);
+
+		let disqualified = (1..=self.participants)
+			.filter_map(NonZeroU16::new)
+			.filter(|dealer| !valid_commitments.contains_key(dealer))
+			.collect();
+
+		DkgRound2 {
+			index: self.index,
+			threshold: self.threshold,
+			zero: self.zero,
+			coefficients: mem::take(&mut self.coefficients),
+			commitments: valid_commitments,
+			shares: valid_shares,
+			complaints,
+			disqualified,
+		}
+	}
+}
+
+impl<G: Group> DkgRound2<G> {
+	/// Returns our [`DkgComplaint`]s to be broadcast to all participants.
+	#[must_use]
+	pub fn complaints(&self) -> &[DkgComplaint] {
+		&self.complaints
+	}
+
+	/// Returns the [`DkgShare`]s justifying all provided [`DkgComplaint`]s
+	/// against us, to be broadcast to all participants.
+	#[must_use]
+	pub fn justify(&self, complaints: &[DkgComplaint]) -> Vec<DkgShare<G>> {
+		complaints
+			.iter()
+			.filter(|complaint| complaint.dealer == self.index)
+			.map(|complaint| share(self.index, &self.coefficients, complaint.complainer))
+			.collect()
+	}
+
+	/// Completes the DKG with all broadcast [`DkgComplaint`]s, including our
+	/// own, and all broadcast `justifications`.
+	///
+	/// Dealers that failed to justify a [`DkgComplaint`] are disqualified.
+	/// Participants whose [`DkgComplaint`] was justified are listed in
+	/// [`DkgOutput::false_complainers()`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Threshold`] if no dealer qualified or the resulting
+	/// [`SecretKey`] or [`PublicKey`] is invalid.
+	pub fn finish(
+		mut self,
+		complaints: &[DkgComplaint],
+		justifications: &[DkgShare<G>],
+	) -> Result<DkgOutput<G>> {
+		let (mut secret, commitments, disqualified, false_complainers) =
+			self.qualify(complaints, justifications)?;
+
+		let secret_key = secret.try_into().map_err(|_| Error::Threshold);
+		secret.zeroize();
+
+		let public_key = PublicKey::new(*commitments.first().ok_or(Error::Threshold)?.as_element());
+
+		Ok(DkgOutput {
+			key_share: KeyShare::new(self.index, SecretKey::new(secret_key?)),
+			public_key,
+			commitments,
+			disqualified,
+			false_complainers,
+		})
+	}
+
+	/// Disqualifies all dealers that failed to justify a [`DkgComplaint`].
+	///
+	/// Returns the sum of the shares and the aggregated commitments of all
+	/// qualified dealers together with all disqualified dealers and all
+	/// participants whose [`DkgComplaint`] was justified.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Threshold`] if no dealer qualified or an aggregated
+	/// commitment is the identity element.
+	#[expect(clippy::type_complexity, reason = "internal")]
+	pub(super) fn qualify(
+		&mut self,
+		complaints: &[DkgComplaint],
+		justifications: &[DkgShare<G>],
+	) -> Result<(
+		G::Scalar,
+		Vec<ElementWithRepr<G>>,
+		Vec<NonZeroU16>,
+		Vec<NonZeroU16>,
+	)> {
+		let mut disqualified = mem::take(&mut self.disqualified);
+		let mut false_complainers = Vec::new();
+
+		for complaint in complaints {
+			let Some(commitment) = self.commitments.get(&complaint.dealer) else {
+				continue;
+			};
+
+			let justification = justifications.iter().find(|justification| {
+				justification.dealer == complaint.dealer
+					&& justification.recipient == complaint.complainer
+			});
+
+			match justification {
+				Some(justification)
+					if super::verify_share::<G, _>(
+						elements(self.zero, commitment),
+						justification.recipient,
+						&justification.share,
+					) =>
+				{
+					if justification.recipient == self.index {
+						self.shares
+							.insert(justification.dealer, justification.share);
+					}
+
+					false_complainers.push(complaint.complainer);
+				}
+				_ => {
+					self.commitments.remove(&complaint.dealer);
+
+					if let Some(mut share) = self.shares.remove(&complaint.dealer) {
+						share.zeroize();
+					}
+
+					disqualified.push(complaint.dealer);
+				}
+			}
+		}
+
+		disqualified.sort_unstable();
+		false_complainers.sort_unstable();
+		false_complainers.dedup();
+
+		if self.commitments.is_empty() {
+			return Err(Error::Threshold);
+		}
+
+		let mut aggregate = alloc::vec![
+			G::element_identity();
+			usize::from(self.threshold - u16::from(self.zero))
+		];
+
+		for commitment in self.commitments.values() {
+			for (sum, coefficient) in aggregate.iter_mut().zip(commitment) {
+				*sum = *sum + coefficient.as_element().deref();
+			}
+		}
+
+		let commitments = aggregate
+			.into_iter()
+			.map(|element| {
+				element
+					.try_into()
+					.map(ElementWithRepr::new)
+					.map_err(|_| Error::Threshold)
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let mut secret = G::Scalar::default();
+
+		for dealer in self.commitments.keys() {
+			let Some(share) = self.shares.get(dealer) else {
+				secret.zeroize();
+				return Err(Error::Threshold);
+			};
+
+			secret = secret + share;
+		}
+
+		Ok((secret, commitments, disqualified, false_complainers))
+	}
+}
+
+impl<G: Group> DkgCommitment<G> {
+	/// Returns the index of the dealer.
+	#[must_use]
+	pub const fn dealer(&self) -> NonZeroU16 {
+		self.dealer
+	}
+
+	/// Returns the commitments to the coefficients of the dealers polynomial.
+	pub fn coefficients(&self) -> impl ExactSizeIterator<Item = &G::NonIdentityElement> {
+		self.coefficients.iter().map(ElementWithRepr::as_element)
+	}
+
+	/// Deserializes the provided `repr` to a [`DkgCommitment`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let dealer = index_from_repr(repr.get(..2))?;
+		let coefficients = repr.get(2..).ok_or(Error::FromRepr)?;
+		let chunks = coefficients.chunks_exact(G::ElementLength::USIZE);
+
+		if !chunks.remainder().is_empty() {
+			return Err(Error::FromRepr);
+		}
+
+		Ok(Self {
+			dealer,
+			coefficients: chunks
+				.map(ElementWithRepr::from_repr)
+				.collect::<Result<_>>()?,
+		})
+	}
+
+	/// Serializes this [`DkgCommitment`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.dealer
+			.get()
+			.i2osp()
+			.into_iter()
+			.chain(
+				self.coefficients
+					.iter()
+					.flat_map(|coefficient| coefficient.as_repr().iter().copied()),
+			)
+			.collect()
+	}
+}
+
+impl<G: Group> DkgShare<G> {
+	/// Returns the index of the dealer.
+	#[must_use]
+	pub const fn dealer(&self) -> NonZeroU16 {
+		self.dealer
+	}
+
+	/// Returns the index of the recipient.
+	#[must_use]
+	pub const fn recipient(&self) -> NonZeroU16 {
+		self.recipient
+	}
+
+	/// Deserializes the provided `repr` to a [`DkgShare`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let dealer = index_from_repr(repr.get(..2))?;
+		let recipient = index_from_repr(repr.get(2..4))?;
+		let share = repr
+			.get(4..)
+			.and_then(|repr| repr.try_into().ok())
+			.and_then(|repr| G::scalar_from_repr(&repr).ok())
+			.ok_or(Error::FromRepr)?;
+
+		Ok(Self {
+			dealer,
+			recipient,
+			share,
+		})
+	}
+
+	/// Serializes this [`DkgShare`].
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		self.dealer
+			.get()
+			.i2osp()
+			.into_iter()
+			.chain(self.recipient.get().i2osp())
+			.chain(G::scalar_to_repr(&self.share))
+			.collect()
+	}
+}
+
+impl DkgComplaint {
+	/// Returns the index of the complaining participant.
+	#[must_use]
+	pub const fn complainer(&self) -> NonZeroU16 {
+		self.complainer
+	}
+
+	/// Returns the index of the accused dealer.
+	#[must_use]
+	pub const fn dealer(&self) -> NonZeroU16 {
+		self.dealer
+	}
+
+	/// Deserializes the provided `repr` to a [`DkgComplaint`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		if repr.len() != 4 {
+			return Err(Error::FromRepr);
+		}
+
+		Ok(Self {
+			complainer: index_from_repr(repr.get(..2))?,
+			dealer: index_from_repr(repr.get(2..))?,
+		})
+	}
+
+	/// Serializes this [`DkgComplaint`].
+	#[must_use]
+	pub fn to_repr(&self) -> [u8; 4] {
+		let [complainer_1, complainer_2] = self.complainer.get().i2osp();
+		let [dealer_1, dealer_2] = self.dealer.get().i2osp();
+
+		[complainer_1, complainer_2, dealer_1, dealer_2]
+	}
+}
+
+impl<G: Group> DkgOutput<G> {
+	/// Returns our [`KeyShare`].
+	#[must_use]
+	pub const fn key_share(&self) -> &KeyShare<G> {
+		&self.key_share
+	}
+
+	/// Returns our [`KeyShare`].
+	#[must_use]
+	pub fn into_key_share(self) -> KeyShare<G> {
+		self.key_share
+	}
+
+	/// Returns the group [`PublicKey`] corresponding to the distributed
+	/// [`SecretKey`].
+	#[must_use]
+	pub const fn public_key(&self) -> &PublicKey<G> {
+		&self.public_key
+	}
+
+	/// Returns the Feldman commitments to the coefficients of the combined
+	/// polynomial. The first commitment is the group [`PublicKey`].
+	pub fn commitments(&self) -> impl ExactSizeIterator<Item = &G::NonIdentityElement> {
+		self.commitments.iter().map(ElementWithRepr::as_element)
+	}
+
+	/// Returns the [`PublicKeyShare`] of the participant with the provided
+	/// `index`.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Threshold`] if the resulting [`PublicKey`] is invalid.
+	pub fn public_key_share(&self, index: NonZeroU16) -> Result<PublicKeyShare<G>> {
+		let element =
+			super::evaluate_commitments::<G, _>(elements(false, &self.commitments), index)
+				.try_into()
+				.map_err(|_| Error::Threshold)?;
+
+		Ok(PublicKeyShare::new(index, PublicKey::new(element)))
+	}
+
+	/// Returns the indices of all disqualified dealers in ascending order.
+	#[must_use]
+	pub fn disqualified(&self) -> &[NonZeroU16] {
+		&self.disqualified
+	}
+
+	/// Returns the indices of all participants whose [`DkgComplaint`] was
+	/// justified by the accused dealer in ascending order.
+	///
+	/// This indicates either a false accusation or a [`DkgShare`] that was
+	/// lost or modified in transit, these participants are not excluded.
+	#[must_use]
+	pub fn false_complainers(&self) -> &[NonZeroU16] {
+		&self.false_complainers
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for DkgRound1<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DkgRound1")
+			.field("index", &self.index)
+			.field("threshold", &self.threshold)
+			.field("participants", &self.participants)
+			.field("zero", &self.zero)
+			.field("coefficients", &self.coefficients)
+			.field("commitment", &self.commitment)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Drop for DkgRound1<G> {
+	fn drop(&mut self) {
+		self.coefficients.iter_mut().for_each(Zeroize::zeroize);
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for DkgRound1<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for DkgRound2<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DkgRound2")
+			.field("index", &self.index)
+			.field("threshold", &self.threshold)
+			.field("zero", &self.zero)
+			.field("coefficients", &self.coefficients)
+			.field("commitments", &self.commitments)
+			.field("shares", &self.shares)
+			.field("complaints", &self.complaints)
+			.field("disqualified", &self.disqualified)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Drop for DkgRound2<G> {
+	fn drop(&mut self) {
+		self.coefficients.iter_mut().for_each(Zeroize::zeroize);
+		self.shares.values_mut().for_each(Zeroize::zeroize);
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for DkgRound2<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Clone for DkgCommitment<G> {
+	fn clone(&self) -> Self {
+		Self {
+			dealer: self.dealer,
+			coefficients: self.coefficients.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for DkgCommitment<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DkgCommitment")
+			.field("dealer", &self.dealer)
+			.field("coefficients", &self.coefficients)
+			.finish()
+	}
+}
+
+impl<G: Group> Eq for DkgCommitment<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> PartialEq for DkgCommitment<G> {
+	fn eq(&self, other: &Self) -> bool {
+		self.dealer.eq(&other.dealer) && self.coefficients.eq(&other.coefficients)
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for DkgCommitment<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Clone for DkgShare<G> {
+	fn clone(&self) -> Self {
+		Self {
+			dealer: self.dealer,
+			recipient: self.recipient,
+			share: self.share,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for DkgShare<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DkgShare")
+			.field("dealer", &self.dealer)
+			.field("recipient", &self.recipient)
+			.field("share", &self.share)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Drop for DkgShare<G> {
+	fn drop(&mut self) {
+		self.share.zeroize();
+	}
+}
+
+impl<G: Group> Eq for DkgShare<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> PartialEq for DkgShare<G> {
+	fn eq(&self, other: &Self) -> bool {
+		self.dealer.eq(&other.dealer)
+			&& self.recipient.eq(&other.recipient)
+			&& self.share.eq(&other.share)
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for DkgShare<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Clone for DkgOutput<G> {
+	fn clone(&self) -> Self {
+		Self {
+			key_share: self.key_share.clone(),
+			public_key: self.public_key.clone(),
+			commitments: self.commitments.clone(),
+			disqualified: self.disqualified.clone(),
+			false_complainers: self.false_complainers.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for DkgOutput<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DkgOutput")
+			.field("key_share", &self.key_share)
+			.field("public_key", &self.public_key)
+			.field("commitments", &self.commitments)
+			.field("disqualified", &self.disqualified)
+			.field("false_complainers", &self.false_complainers)
+			.finish()
+	}
+}
+
+impl<G: Group> Eq for DkgOutput<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> PartialEq for DkgOutput<G> {
+	fn eq(&self, other: &Self) -> bool {
+		self.key_share.eq(&other.key_share)
+			&& self.public_key.eq(&other.public_key)
+			&& self.commitments.eq(&other.commitments)
+			&& self.disqualified.eq(&other.disqualified)
+			&& self.false_complainers.eq(&other.false_complainers)
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for DkgOutput<G> {}
+
+/// Computes the [`DkgShare`] of `dealer` for `recipient`.
+fn share<G: Group>(
+	dealer: NonZeroU16,
+	coefficients: &[G::Scalar],
+	recipient: NonZeroU16,
+) -> DkgShare<G> {
+	DkgShare {
+		dealer,
+		recipient,
+		share: super::evaluate_polynomial::<G>(coefficients, recipient),
+	}
+}
+
+/// Converts the provided commitments to [`Element`](Group::Element)s. If
+/// `zero` is `true` the identity element is prepended as the commitment to the
+/// constant term.
+fn elements<G: Group>(
+	zero: bool,
+	commitments: &[ElementWithRepr<G>],
+) -> impl '_ + Iterator<Item = G::Element> {
+	iter::repeat_n(G::element_identity(), zero.into()).chain(
+		commitments
+			.iter()
+			.map(|commitment| (*commitment.as_element()).into()),
+	)
+}
+
+/// Deserializes an index.
+///
+/// # Errors
+///
+/// Returns [`Error::FromRepr`] if deserialization fails.
+fn index_from_repr(repr: Option<&[u8]>) -> Result<NonZeroU16> {
+	repr.and_then(|repr| repr.try_into().ok())
+		.map(u16::from_be_bytes)
+		.and_then(NonZeroU16::new)
+		.ok_or(Error::FromRepr)
+}
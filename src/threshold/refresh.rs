@@ -0,0 +1,285 @@
+//! Proactive refresh of [`KeyShare`]s.
+//!
+//! Re-randomizes all [`KeyShare`]s of the same [`SecretKey`] without changing
+//! the group [`PublicKey`](crate::key::PublicKey). Every participant deals a
+//! random sharing of zero with Feldman commitments to all but the constant
+//! term, which lets recipients verify that the sharing they receive is indeed
+//! a sharing of zero.
+//!
+//! The protocol runs through the same rounds as the [`dkg`](super::dkg) and
+//! uses the same messages:
+//!
+//! 1. Every participant creates a [`RefreshRound1`] from its current
+//!    [`KeyShare`], broadcasts its [`DkgCommitment`] and sends every other
+//!    participant its [`DkgShare`].
+//! 2. Every participant [`receive()`](RefreshRound1::receive)s all messages
+//!    addressed to it and broadcasts its [`DkgComplaint`]s.
+//! 3. Every accused dealer broadcasts the [`DkgShare`]s returned by
+//!    [`RefreshRound2::justify()`].
+//! 4. Every participant calls [`RefreshRound2::finish()`] with all broadcast
+//!    [`DkgComplaint`]s and justifications.
+//!
+//! Old [`KeyShare`]s must be deleted after a successful refresh, they can't be
+//! combined with refreshed [`KeyShare`]s.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter;
+use core::num::NonZeroU16;
+use core::ops::Deref;
+
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::dkg::{DkgCommitment, DkgComplaint, DkgRound1, DkgRound2, DkgShare};
+use super::{KeyShare, PublicKeyShare};
+use crate::error::{Error, Result};
+use crate::group::Group;
+use crate::internal::ElementWithRepr;
+use crate::key::{PublicKey, SecretKey};
+
+/// First round of the refresh. See the [module level documentation](self).
+pub struct RefreshRound1<G: Group> {
+	/// Our current [`KeyShare`].
+	key_share: KeyShare<G>,
+	/// The underlying [`DkgRound1`].
+	round: DkgRound1<G>,
+}
+
+/// Second round of the refresh. See the [module level documentation](self).
+pub struct RefreshRound2<G: Group> {
+	/// Our current [`KeyShare`].
+	key_share: KeyShare<G>,
+	/// The underlying [`DkgRound2`].
+	round: DkgRound2<G>,
+}
+
+/// Returned by [`RefreshRound2::finish()`].
+pub struct RefreshOutput<G: Group> {
+	/// Our refreshed [`KeyShare`].
+	key_share: KeyShare<G>,
+	/// Feldman commitments to the non-constant coefficients of the combined
+	/// sharing of zero.
+	commitments: Vec<ElementWithRepr<G>>,
+	/// Disqualified dealers.
+	disqualified: Vec<NonZeroU16>,
+	/// Participants whose [`DkgComplaint`] was justified.
+	false_complainers: Vec<NonZeroU16>,
+}
+
+impl<G: Group> RefreshRound1<G> {
+	/// Starts the refresh of the provided [`KeyShare`] between `participants`,
+	/// any `threshold` of which can jointly evaluate.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Threshold`] if `threshold` is zero or larger than
+	///   `participants` or the index of the [`KeyShare`] is larger than
+	///   `participants`.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn new<R>(
+		rng: &mut R,
+		key_share: KeyShare<G>,
+		threshold: u16,
+		participants: u16,
+	) -> Result<Self, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let round = DkgRound1::new_internal(rng, key_share.index(), threshold, participants, true)?;
+
+		Ok(Self { key_share, round })
+	}
+
+	/// Returns our [`DkgCommitment`] to be broadcast to all participants.
+	#[must_use]
+	pub const fn commitment(&self) -> &DkgCommitment<G> {
+		self.round.commitment()
+	}
+
+	/// Returns the [`DkgShare`]s to be sent privately to every other
+	/// participant.
+	#[must_use]
+	pub fn shares(&self) -> Vec<DkgShare<G>> {
+		self.round.shares()
+	}
+
+	/// Processes the [`DkgCommitment`]s broadcast by all dealers and the
+	/// [`DkgShare`]s addressed to us.
+	///
+	/// See [`DkgRound1::receive()`].
+	#[must_use]
+	pub fn receive(
+		self,
+		commitments: &[DkgCommitment<G>],
+		shares: &[DkgShare<G>],
+	) -> RefreshRound2<G> {
+		RefreshRound2 {
+			round: self.round.receive(commitments, shares),
+			key_share: self.key_share,
+		}
+	}
+}
+
+impl<G: Group> RefreshRound2<G> {
+	/// Returns our [`DkgComplaint`]s to be broadcast to all participants.
+	#[must_use]
+	pub fn complaints(&self) -> &[DkgComplaint] {
+		self.round.complaints()
+	}
+
+	/// Returns the [`DkgShare`]s justifying all provided [`DkgComplaint`]s
+	/// against us, to be broadcast to all participants.
+	#[must_use]
+	pub fn justify(&self, complaints: &[DkgComplaint]) -> Vec<DkgShare<G>> {
+		self.round.justify(complaints)
+	}
+
+	/// Completes the refresh with all broadcast [`DkgComplaint`]s, including
+	/// our own, and all broadcast `justifications`.
+	///
+	/// Dealers that failed to justify a [`DkgComplaint`] are disqualified.
+	/// Participants whose [`DkgComplaint`] was justified are listed in
+	/// [`RefreshOutput::false_complainers()`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Threshold`] if no dealer qualified or the refreshed
+	/// [`KeyShare`] is invalid.
+	pub fn finish(
+		mut self,
+		complaints: &[DkgComplaint],
+		justifications: &[DkgShare<G>],
+	) -> Result<RefreshOutput<G>> {
+		let (mut delta, commitments, disqualified, false_complainers) =
+			self.round.qualify(complaints, justifications)?;
+
+		let mut secret = *self.key_share.key_pair().secret_key().as_scalar().deref() + &delta;
+		delta.zeroize();
+		let secret_key = secret.try_into().map_err(|_| Error::Threshold);
+		secret.zeroize();
+
+		Ok(RefreshOutput {
+			key_share: KeyShare::new(self.key_share.index(), SecretKey::new(secret_key?)),
+			commitments,
+			disqualified,
+			false_complainers,
+		})
+	}
+}
+
+impl<G: Group> RefreshOutput<G> {
+	/// Returns our refreshed [`KeyShare`].
+	#[must_use]
+	pub const fn key_share(&self) -> &KeyShare<G> {
+		&self.key_share
+	}
+
+	/// Returns our refreshed [`KeyShare`].
+	#[must_use]
+	pub fn into_key_share(self) -> KeyShare<G> {
+		self.key_share
+	}
+
+	/// Returns the Feldman commitments to the non-constant coefficients of the
+	/// combined sharing of zero.
+	pub fn commitments(&self) -> impl ExactSizeIterator<Item = &G::NonIdentityElement> {
+		self.commitments.iter().map(ElementWithRepr::as_element)
+	}
+
+	/// Returns the refreshed [`PublicKeyShare`] corresponding to the provided
+	/// [`PublicKeyShare`] from before the refresh.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Threshold`] if the resulting [`PublicKey`] is invalid.
+	pub fn public_key_share(&self, old: &PublicKeyShare<G>) -> Result<PublicKeyShare<G>> {
+		let commitments = iter::once::<G::Element>((*old.public_key().as_element()).into()).chain(
+			self.commitments
+				.iter()
+				.map(|commitment| (*commitment.as_element()).into()),
+		);
+		let element = super::evaluate_commitments::<G, _>(commitments, old.index())
+			.try_into()
+			.map_err(|_| Error::Threshold)?;
+
+		Ok(PublicKeyShare::new(old.index(), PublicKey::new(element)))
+	}
+
+	/// Returns the indices of all disqualified dealers in ascending order.
+	#[must_use]
+	pub fn disqualified(&self) -> &[NonZeroU16] {
+		&self.disqualified
+	}
+
+	/// Returns the indices of all participants whose [`DkgComplaint`] was
+	/// justified by the accused dealer in ascending order.
+	///
+	/// See [`DkgOutput::false_complainers()`](super::dkg::DkgOutput::false_complainers).
+	#[must_use]
+	pub fn false_complainers(&self) -> &[NonZeroU16] {
+		&self.false_complainers
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for RefreshRound1<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RefreshRound1")
+			.field("key_share", &self.key_share)
+			.field("round", &self.round)
+			.finish()
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for RefreshRound1<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for RefreshRound2<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RefreshRound2")
+			.field("key_share", &self.key_share)
+			.field("round", &self.round)
+			.finish()
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for RefreshRound2<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Clone for RefreshOutput<G> {
+	fn clone(&self) -> Self {
+		Self {
+			key_share: self.key_share.clone(),
+			commitments: self.commitments.clone(),
+			disqualified: self.disqualified.clone(),
+			false_complainers: self.false_complainers.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> Debug for RefreshOutput<G> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RefreshOutput")
+			.field("key_share", &self.key_share)
+			.field("commitments", &self.commitments)
+			.field("disqualified", &self.disqualified)
+			.field("false_complainers", &self.false_complainers)
+			.finish()
+	}
+}
+
+impl<G: Group> Eq for RefreshOutput<G> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<G: Group> PartialEq for RefreshOutput<G> {
+	fn eq(&self, other: &Self) -> bool {
+		self.key_share.eq(&other.key_share)
+			&& self.commitments.eq(&other.commitments)
+			&& self.disqualified.eq(&other.disqualified)
+			&& self.false_complainers.eq(&other.false_complainers)
+	}
+}
+
+impl<G: Group> ZeroizeOnDrop for RefreshOutput<G> {}
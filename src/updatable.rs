@@ -0,0 +1,365 @@
+//! Updatable [OPRF](crate::oprf) with key rotation.
+//!
+//! Regular OPRF outputs are hashed and become useless when the server rotates
+//! its [`SecretKey`](crate::key::SecretKey). Instead, the [`UpdatableClient`]
+//! keeps the unblinded [`UpdatableElement`], which can be re-keyed to the new
+//! key with a [`RotationToken`] produced by [`UpdatableServer::rotate()`].
+//!
+//! A [`RotationToken`] contains `delta = k_new / k_old`. Holders can
+//! [`verify()`](RotationToken::verify) that `delta` maps the old [`PublicKey`]
+//! to the new one before
+//! [`apply_rotation()`](UpdatableElement::apply_rotation)ing it.
+
+use core::fmt::{self, Debug, Formatter};
+
+use hybrid_array::Array;
+use rand_core::TryCryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::{CipherSuite, ElementLength, NonZeroScalar, ScalarLength};
+use crate::common::{BlindedElement, EvaluationElement};
+use crate::error::{Error, Result};
+use crate::group::Group;
+use crate::internal::{self, ElementWithRepr};
+use crate::key::{KeyPair, PublicKey};
+use crate::oprf::{OprfBlindResult, OprfClient, OprfServer};
+
+/// Updatable OPRF client.
+pub struct UpdatableClient<Cs: CipherSuite> {
+	/// The underlying [`OprfClient`].
+	client: OprfClient<Cs>,
+}
+
+/// Updatable OPRF server.
+pub struct UpdatableServer<Cs: CipherSuite> {
+	/// The current [`KeyPair`].
+	key_pair: KeyPair<Cs::Group>,
+}
+
+/// An unblinded element that can be re-keyed with a [`RotationToken`].
+pub struct UpdatableElement<Cs: CipherSuite> {
+	/// The unblinded element.
+	element: ElementWithRepr<Cs::Group>,
+}
+
+/// Re-keys [`UpdatableElement`]s after a key rotation. Returned by
+/// [`UpdatableServer::rotate()`].
+pub struct RotationToken<Cs: CipherSuite> {
+	/// `k_new / k_old`.
+	delta: NonZeroScalar<Cs>,
+}
+
+/// Returned from [`UpdatableClient::blind()`].
+pub struct UpdatableBlindResult<Cs: CipherSuite> {
+	/// The [`UpdatableClient`].
+	pub client: UpdatableClient<Cs>,
+	/// The [`BlindedElement`].
+	pub blinded_element: BlindedElement<Cs>,
+}
+
+impl<Cs: CipherSuite> UpdatableClient<Cs> {
+	/// Blinds the provided `input`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the provided `input` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the provided `input` can never produce a
+	///   valid [`BlindedElement`].
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind<R>(
+		rng: &mut R,
+		input: &[&[u8]],
+	) -> Result<UpdatableBlindResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let OprfBlindResult {
+			client,
+			blinded_element,
+		} = OprfClient::blind(rng, input)?;
+
+		Ok(UpdatableBlindResult {
+			client: Self { client },
+			blinded_element,
+		})
+	}
+
+	/// Completes the evaluation without hashing the unblinded element.
+	#[must_use]
+	pub fn finalize(&self, evaluation_element: &EvaluationElement<Cs>) -> UpdatableElement<Cs> {
+		UpdatableElement {
+			element: self
+				.client
+				.finalize_element(evaluation_element)
+				.as_ref()
+				.clone(),
+		}
+	}
+}
+
+impl<Cs: CipherSuite> UpdatableServer<Cs> {
+	/// Creates a new [`UpdatableServer`] by generating a random [`KeyPair`].
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails.
+	pub fn new<R>(rng: &mut R) -> Result<Self, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		Ok(Self {
+			key_pair: KeyPair::generate(rng)?,
+		})
+	}
+
+	/// Creates a new [`UpdatableServer`] from the provided [`KeyPair`].
+	#[must_use]
+	pub const fn from_key_pair(key_pair: KeyPair<Cs::Group>) -> Self {
+		Self { key_pair }
+	}
+
+	/// Returns the current [`KeyPair`].
+	#[must_use]
+	pub const fn key_pair(&self) -> &KeyPair<Cs::Group> {
+		&self.key_pair
+	}
+
+	/// Returns the current [`PublicKey`].
+	#[must_use]
+	pub const fn public_key(&self) -> &PublicKey<Cs::Group> {
+		self.key_pair.public_key()
+	}
+
+	/// Process the [`BlindedElement`].
+	#[must_use]
+	pub fn blind_evaluate(&self, blinded_element: &BlindedElement<Cs>) -> EvaluationElement<Cs> {
+		OprfServer::from_key(self.key_pair.secret_key().clone()).blind_evaluate(blinded_element)
+	}
+
+	/// Replaces the current [`KeyPair`] with a randomly generated one and
+	/// returns the [`RotationToken`] to re-key existing
+	/// [`UpdatableElement`]s.
+	///
+	/// # Errors
+	///
+	/// Returns [`TryRngCore::Error`](rand_core::TryRngCore::Error) if the
+	/// provided `rng` fails. In which case the current [`KeyPair`] remains
+	/// unchanged.
+	pub fn rotate<R>(&mut self, rng: &mut R) -> Result<RotationToken<Cs>, R::Error>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let new = KeyPair::generate(rng)?;
+		let delta = internal::non_zero_scalar_mul::<Cs::Group>(
+			new.secret_key().to_scalar(),
+			Cs::Group::scalar_invert(&self.key_pair.secret_key().to_scalar()),
+		);
+
+		self.key_pair = new;
+
+		Ok(RotationToken { delta })
+	}
+}
+
+impl<Cs: CipherSuite> UpdatableElement<Cs> {
+	/// Re-keys this [`UpdatableElement`] to the new key of the
+	/// [`RotationToken`].
+	///
+	/// The [`RotationToken`] is not verified, see
+	/// [`RotationToken::verify()`].
+	pub fn apply_rotation(&mut self, rotation_token: &RotationToken<Cs>) {
+		self.element = ElementWithRepr::new(rotation_token.delta * self.element.as_element());
+	}
+
+	/// Deserializes the provided `repr` to an [`UpdatableElement`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		ElementWithRepr::from_repr(repr).map(|element| Self { element })
+	}
+
+	/// Returns the representation of this [`UpdatableElement`].
+	#[must_use]
+	pub const fn as_repr(&self) -> &Array<u8, ElementLength<Cs>> {
+		self.element.as_repr()
+	}
+}
+
+impl<Cs: CipherSuite> RotationToken<Cs> {
+	/// Verifies that this [`RotationToken`] re-keys [`UpdatableElement`]s
+	/// from the `old` to the `new` [`PublicKey`], i.e. that
+	/// `delta * old == new`.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::Proof`] if this [`RotationToken`] doesn't map `old` to
+	/// `new`.
+	pub fn verify(&self, old: &PublicKey<Cs::Group>, new: &PublicKey<Cs::Group>) -> Result<()> {
+		if self.delta * old.as_element() == *new.as_element() {
+			Ok(())
+		} else {
+			Err(Error::Proof)
+		}
+	}
+
+	/// Serializes this [`RotationToken`].
+	#[must_use]
+	pub fn to_repr(&self) -> Array<u8, ScalarLength<Cs>> {
+		Cs::Group::scalar_to_repr(&self.delta)
+	}
+
+	/// Deserializes the provided `repr` to a [`RotationToken`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let delta = repr
+			.try_into()
+			.ok()
+			.and_then(|repr| Cs::Group::non_zero_scalar_from_repr(repr).ok())
+			.ok_or(Error::FromRepr)?;
+
+		Ok(Self { delta })
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for UpdatableClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for UpdatableClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("UpdatableClient")
+			.field("client", &self.client)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for UpdatableClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for UpdatableClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client.eq(&other.client)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for UpdatableClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for UpdatableServer<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			key_pair: self.key_pair.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for UpdatableServer<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("UpdatableServer")
+			.field("key_pair", &self.key_pair)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for UpdatableServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for UpdatableServer<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.key_pair.eq(&other.key_pair)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for UpdatableServer<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for UpdatableElement<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			element: self.element.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for UpdatableElement<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("UpdatableElement")
+			.field("element", &self.element)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for UpdatableElement<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for UpdatableElement<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.element.eq(&other.element)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for UpdatableElement<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for RotationToken<Cs> {
+	fn clone(&self) -> Self {
+		Self { delta: self.delta }
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for RotationToken<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RotationToken")
+			.field("delta", &self.delta)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for RotationToken<Cs> {
+	fn drop(&mut self) {
+		self.delta.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for RotationToken<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for RotationToken<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.delta.eq(&other.delta)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for RotationToken<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for UpdatableBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("UpdatableBlindResult")
+			.field("client", &self.client)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for UpdatableBlindResult<Cs> {}
@@ -0,0 +1,785 @@
+//! Password-protected key vault with guess limiting, in the style of
+//! [Secure Value Recovery](https://signal.org/blog/secure-value-recovery/).
+//!
+//! The server holds a separate [`SecretKey`] per user in a [`VaultStore`]. The
+//! client derives its vault key from a password through [OPRF](crate::oprf),
+//! which can then be used to encrypt a backup stored anywhere. Passwords can't
+//! be guessed offline without the server, and the server only evaluates a
+//! limited number of guesses.
+//!
+//! # Enrollment
+//!
+//! 1. The client blinds the password with [`VaultClient::blind()`] and sends
+//!    the [`BlindedElement`] to the server.
+//! 2. The server creates a new [`VaultRecord`] with [`VaultServer::enroll()`]
+//!    and sends the [`EvaluationElement`] and the [`VaultEnrollment`] back to
+//!    the client.
+//! 3. The client derives the [`VaultKeys`] with [`VaultClient::finalize()`]
+//!    and sends the [`VaultTag`] and the [`VaultEnrollment`] to the server.
+//! 4. The server completes the enrollment with
+//!    [`VaultServer::confirm_enrollment()`].
+//!
+//! # Restore
+//!
+//! 1. The client blinds the password with [`VaultClient::blind()`] and sends
+//!    the [`BlindedElement`] to the server.
+//! 2. The server counts the attempt and evaluates the [`BlindedElement`] with
+//!    [`VaultServer::evaluate()`].
+//! 3. The client derives the [`VaultKeys`] with [`VaultClient::finalize()`]
+//!    and sends the [`VaultTag`] to the server.
+//! 4. The server resets the attempt counter with [`VaultServer::confirm()`]
+//!    if the [`VaultTag`] matches.
+//!
+//! After `max_attempts` attempts without a matching [`VaultTag`], the
+//! [`VaultRecord`] is removed from the [`VaultStore`], which wipes the
+//! [`SecretKey`] of the user and makes the vault key unrecoverable.
+//!
+//! This module doesn't authenticate the `user_id`, which is up to the caller.
+//!
+//! # 🚨 Warning
+//!
+//! [`VaultServer::evaluate()`] reads the [`VaultRecord`], counts the attempt
+//! and writes it back through separate [`VaultStore`] calls. The
+//! `max_attempts` limit only holds if no two attempts of the same user are
+//! processed concurrently, e.g. by several server instances sharing a
+//! [`VaultStore`]. Callers must serialize attempts per user, for example with
+//! a lock or a transaction around the whole call.
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+#[cfg(feature = "alloc")]
+use core::iter;
+use core::num::NonZeroU32;
+
+use digest::{FixedOutput, Output, Update};
+use hybrid_array::typenum::Unsigned;
+use rand_core::TryCryptoRng;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cipher_suite::{CipherSuite, ScalarLength};
+use crate::common::{BlindedElement, EvaluationElement};
+use crate::error::{Error, Result};
+use crate::key::SecretKey;
+use crate::oprf::{OprfBlindResult, OprfClient, OprfServer};
+
+/// Label to derive the vault key.
+const KEY_LABEL: &[u8] = b"Vault-Key-";
+/// Label to derive the [`VaultTag`].
+const TAG_LABEL: &[u8] = b"Vault-Tag-";
+
+/// Vault client.
+pub struct VaultClient<Cs: CipherSuite> {
+	/// The underlying [`OprfClient`].
+	client: OprfClient<Cs>,
+}
+
+/// Vault server.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct VaultServer {
+	/// The maximum number of attempts without a matching [`VaultTag`].
+	max_attempts: NonZeroU32,
+}
+
+/// The state of a single user stored in a [`VaultStore`].
+pub struct VaultRecord<Cs: CipherSuite> {
+	/// The [`OprfServer`] holding the [`SecretKey`] of the user.
+	server: OprfServer<Cs>,
+	/// The enrollment state of the user.
+	state: VaultState<Cs>,
+	/// Attempts since the last matching [`VaultTag`].
+	attempts: u32,
+}
+
+/// The enrollment state of a [`VaultRecord`].
+enum VaultState<Cs: CipherSuite> {
+	/// Waiting for [`VaultServer::confirm_enrollment()`] with the
+	/// [`VaultEnrollment`].
+	Pending(VaultEnrollment<Cs>),
+	/// Confirmed with the [`VaultTag`] of the user.
+	Confirmed(VaultTag<Cs>),
+}
+
+/// Sent from the server to the client during enrollment and back to bind
+/// the confirmation to the enrollment.
+pub struct VaultEnrollment<Cs: CipherSuite> {
+	/// The enrollment secret.
+	secret: Output<Cs::Hash>,
+}
+
+/// Sent from the client to the server to prove knowledge of the password.
+pub struct VaultTag<Cs: CipherSuite> {
+	/// The tag.
+	tag: Output<Cs::Hash>,
+}
+
+/// Returned from [`VaultClient::blind()`].
+pub struct VaultBlindResult<Cs: CipherSuite> {
+	/// The [`VaultClient`] to be used with [`VaultClient::finalize()`].
+	pub client: VaultClient<Cs>,
+	/// The [`BlindedElement`] to be sent to the server.
+	pub blinded_element: BlindedElement<Cs>,
+}
+
+/// Returned from [`VaultServer::enroll()`].
+pub struct VaultEnrollResult<Cs: CipherSuite> {
+	/// The [`EvaluationElement`] to be sent to the client.
+	pub evaluation_element: EvaluationElement<Cs>,
+	/// The [`VaultEnrollment`] to be sent to the client.
+	pub enrollment: VaultEnrollment<Cs>,
+}
+
+/// Returned from [`VaultClient::finalize()`].
+pub struct VaultKeys<Cs: CipherSuite> {
+	/// The vault key.
+	pub key: Output<Cs::Hash>,
+	/// The [`VaultTag`] to be sent to the server.
+	pub tag: VaultTag<Cs>,
+}
+
+/// Storage of [`VaultRecord`]s by user.
+///
+/// Implementations must not retain a [`VaultRecord`] after it was removed.
+///
+/// The [`VaultServer`] reads and writes [`VaultRecord`]s in separate calls,
+/// so attempts of the same user must not be processed concurrently, see the
+/// [module documentation](self).
+pub trait VaultStore<Cs: CipherSuite> {
+	/// Returns the [`VaultRecord`] of the provided `user_id`.
+	fn get(&self, user_id: &[u8]) -> Option<VaultRecord<Cs>>;
+
+	/// Inserts or replaces the [`VaultRecord`] of the provided `user_id`.
+	fn insert(&mut self, user_id: &[u8], record: VaultRecord<Cs>);
+
+	/// Removes the [`VaultRecord`] of the provided `user_id`.
+	fn remove(&mut self, user_id: &[u8]);
+}
+
+/// In-memory [`VaultStore`].
+#[cfg(feature = "alloc")]
+pub struct MemoryVaultStore<Cs: CipherSuite>(BTreeMap<Vec<u8>, VaultRecord<Cs>>);
+
+impl<Cs: CipherSuite> VaultClient<Cs> {
+	/// Blinds the provided `password`.
+	///
+	/// # Errors
+	///
+	/// - [`Error::InputLength`] if the provided `password` exceeds a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](crate::group::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::InvalidInput`] if the `password` can never produce a valid
+	///   output.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn blind<R>(rng: &mut R, password: &[u8]) -> Result<VaultBlindResult<Cs>, Error<R::Error>>
+	where
+		R: ?Sized + TryCryptoRng,
+	{
+		let OprfBlindResult {
+			client,
+			blinded_element,
+		} = OprfClient::blind(rng, &[password])?;
+
+		Ok(VaultBlindResult {
+			client: Self { client },
+			blinded_element,
+		})
+	}
+
+	/// Completes the evaluation and derives the [`VaultKeys`].
+	///
+	/// The `password` must be the same as passed to [`VaultClient::blind()`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::InputLength`] if the provided `password` exceeds a
+	/// length of [`u16::MAX`].
+	pub fn finalize(
+		&self,
+		password: &[u8],
+		evaluation_element: &EvaluationElement<Cs>,
+	) -> Result<VaultKeys<Cs>> {
+		let output = self.client.finalize(&[password], evaluation_element)?;
+
+		Ok(VaultKeys {
+			key: derive::<Cs>(&output, KEY_LABEL),
+			tag: VaultTag {
+				tag: derive::<Cs>(&output, TAG_LABEL),
+			},
+		})
+	}
+}
+
+impl VaultServer {
+	/// Creates a new [`VaultServer`] allowing `max_attempts` attempts without
+	/// a matching [`VaultTag`].
+	#[must_use]
+	pub const fn new(max_attempts: NonZeroU32) -> Self {
+		Self { max_attempts }
+	}
+
+	/// Returns the maximum number of attempts without a matching
+	/// [`VaultTag`].
+	#[must_use]
+	pub const fn max_attempts(&self) -> NonZeroU32 {
+		self.max_attempts
+	}
+
+	/// Enrolls the provided `user_id` with a new [`SecretKey`] and evaluates
+	/// the [`BlindedElement`].
+	///
+	/// Replaces an unconfirmed [`VaultRecord`] of the `user_id`. The
+	/// enrollment must be completed with
+	/// [`VaultServer::confirm_enrollment()`].
+	///
+	/// A confirmed [`VaultRecord`] is never replaced, otherwise anybody could
+	/// wipe the vault of a user. To re-enroll, the caller must authenticate the
+	/// user and remove the [`VaultRecord`] with [`VaultStore::remove()`] first.
+	///
+	/// # Errors
+	///
+	/// - [`Error::AlreadyEnrolled`] if the enrollment of the `user_id` was
+	///   already confirmed.
+	/// - [`Error::Random`] if the provided `rng` fails.
+	pub fn enroll<Cs, R, S>(
+		rng: &mut R,
+		store: &mut S,
+		user_id: &[u8],
+		blinded_element: &BlindedElement<Cs>,
+	) -> Result<VaultEnrollResult<Cs>, Error<R::Error>>
+	where
+		Cs: CipherSuite,
+		R: ?Sized + TryCryptoRng,
+		S: ?Sized + VaultStore<Cs>,
+	{
+		if store
+			.get(user_id)
+			.is_some_and(|record| record.is_confirmed())
+		{
+			return Err(Error::AlreadyEnrolled);
+		}
+
+		let server = OprfServer::new(rng).map_err(Error::Random)?;
+		let evaluation_element = server.blind_evaluate(blinded_element);
+		let mut enrollment = VaultEnrollment {
+			secret: Output::<Cs::Hash>::default(),
+		};
+		rng.try_fill_bytes(&mut enrollment.secret)
+			.map_err(Error::Random)?;
+
+		store.insert(
+			user_id,
+			VaultRecord {
+				server,
+				state: VaultState::Pending(enrollment.clone()),
+				attempts: 0,
+			},
+		);
+
+		Ok(VaultEnrollResult {
+			evaluation_element,
+			enrollment,
+		})
+	}
+
+	/// Completes the enrollment of the provided `user_id` with its
+	/// [`VaultTag`].
+	///
+	/// The [`VaultEnrollment`] must be the one returned from
+	/// [`VaultServer::enroll()`], so only the party that started the
+	/// enrollment can complete it.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Vault`] if the `user_id` is unknown or the
+	///   [`VaultEnrollment`] doesn't match.
+	/// - [`Error::AlreadyEnrolled`] if the enrollment of the `user_id` was
+	///   already confirmed.
+	pub fn confirm_enrollment<Cs, S>(
+		store: &mut S,
+		user_id: &[u8],
+		enrollment: &VaultEnrollment<Cs>,
+		tag: &VaultTag<Cs>,
+	) -> Result<()>
+	where
+		Cs: CipherSuite,
+		S: ?Sized + VaultStore<Cs>,
+	{
+		let mut record = store.get(user_id).ok_or(Error::Vault)?;
+
+		let VaultState::Pending(expected) = &record.state else {
+			return Err(Error::AlreadyEnrolled);
+		};
+
+		if !bool::from(
+			expected
+				.secret
+				.as_slice()
+				.ct_eq(enrollment.secret.as_slice()),
+		) {
+			return Err(Error::Vault);
+		}
+
+		record.state = VaultState::Confirmed(tag.clone());
+		store.insert(user_id, record);
+
+		Ok(())
+	}
+
+	/// Counts an attempt of the provided `user_id` and evaluates the
+	/// [`BlindedElement`].
+	///
+	/// # Errors
+	///
+	/// - [`Error::Vault`] if the `user_id` is unknown or its enrollment wasn't
+	///   confirmed.
+	/// - [`Error::AttemptLimit`] if all attempts were used. The
+	///   [`VaultRecord`] is removed.
+	pub fn evaluate<Cs, S>(
+		&self,
+		store: &mut S,
+		user_id: &[u8],
+		blinded_element: &BlindedElement<Cs>,
+	) -> Result<EvaluationElement<Cs>>
+	where
+		Cs: CipherSuite,
+		S: ?Sized + VaultStore<Cs>,
+	{
+		let mut record = store.get(user_id).ok_or(Error::Vault)?;
+
+		if !record.is_confirmed() {
+			return Err(Error::Vault);
+		}
+
+		if record.attempts >= self.max_attempts.get() {
+			store.remove(user_id);
+			return Err(Error::AttemptLimit);
+		}
+
+		record.attempts = record.attempts.saturating_add(1);
+		let evaluation_element = record.server.blind_evaluate(blinded_element);
+		store.insert(user_id, record);
+
+		Ok(evaluation_element)
+	}
+
+	/// Checks the [`VaultTag`] of the provided `user_id` and resets the
+	/// attempt counter if it matches.
+	///
+	/// # Errors
+	///
+	/// - [`Error::Vault`] if the `user_id` is unknown or its enrollment wasn't
+	///   confirmed.
+	/// - [`Error::Password`] if the [`VaultTag`] doesn't match.
+	/// - [`Error::AttemptLimit`] if the [`VaultTag`] doesn't match and all
+	///   attempts were used. The [`VaultRecord`] is removed.
+	pub fn confirm<Cs, S>(&self, store: &mut S, user_id: &[u8], tag: &VaultTag<Cs>) -> Result<()>
+	where
+		Cs: CipherSuite,
+		S: ?Sized + VaultStore<Cs>,
+	{
+		let mut record = store.get(user_id).ok_or(Error::Vault)?;
+
+		let VaultState::Confirmed(expected) = &record.state else {
+			return Err(Error::Vault);
+		};
+
+		if !bool::from(expected.tag.as_slice().ct_eq(tag.tag.as_slice())) {
+			if record.attempts >= self.max_attempts.get() {
+				store.remove(user_id);
+				return Err(Error::AttemptLimit);
+			}
+
+			return Err(Error::Password);
+		}
+
+		record.attempts = 0;
+		store.insert(user_id, record);
+
+		Ok(())
+	}
+}
+
+impl<Cs: CipherSuite> VaultRecord<Cs> {
+	/// Returns the [`SecretKey`] of the user.
+	#[must_use]
+	pub const fn secret_key(&self) -> &SecretKey<Cs::Group> {
+		self.server.secret_key()
+	}
+
+	/// Returns [`true`] if the enrollment was confirmed.
+	#[must_use]
+	pub const fn is_confirmed(&self) -> bool {
+		matches!(self.state, VaultState::Confirmed(_))
+	}
+
+	/// Returns the number of attempts since the last matching [`VaultTag`].
+	#[must_use]
+	pub const fn attempts(&self) -> u32 {
+		self.attempts
+	}
+
+	/// Deserializes the provided `repr` to a [`VaultRecord`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		let (secret_key, repr) = repr
+			.split_at_checked(ScalarLength::<Cs>::USIZE)
+			.ok_or(Error::FromRepr)?;
+		let (attempts, repr) = repr.split_first_chunk().ok_or(Error::FromRepr)?;
+		let (state, repr) = repr.split_first().ok_or(Error::FromRepr)?;
+
+		Ok(Self {
+			server: OprfServer::from_key(SecretKey::from_repr(secret_key)?),
+			state: match *state {
+				0 => VaultState::Pending(VaultEnrollment::from_repr(repr)?),
+				1 => VaultState::Confirmed(VaultTag::from_repr(repr)?),
+				_ => return Err(Error::FromRepr),
+			},
+			attempts: u32::from_be_bytes(*attempts),
+		})
+	}
+
+	/// Serializes this [`VaultRecord`].
+	///
+	/// # 🚨 Warning
+	///
+	/// This value contains key material.
+	///
+	/// Please treat it with the care it deserves!
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn to_repr(&self) -> Vec<u8> {
+		let (state, repr) = match &self.state {
+			VaultState::Pending(enrollment) => (0, &enrollment.secret),
+			VaultState::Confirmed(tag) => (1, &tag.tag),
+		};
+
+		self.server
+			.secret_key()
+			.to_repr()
+			.into_iter()
+			.chain(self.attempts.to_be_bytes())
+			.chain(iter::once(state))
+			.chain(repr.iter().copied())
+			.collect()
+	}
+}
+
+impl<Cs: CipherSuite> VaultEnrollment<Cs> {
+	/// Returns the representation of this [`VaultEnrollment`].
+	#[must_use]
+	pub const fn as_repr(&self) -> &Output<Cs::Hash> {
+		&self.secret
+	}
+
+	/// Deserializes the provided `repr` to a [`VaultEnrollment`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		Ok(Self {
+			secret: repr.try_into().map_err(|_| Error::FromRepr)?,
+		})
+	}
+}
+
+impl<Cs: CipherSuite> VaultTag<Cs> {
+	/// Returns the representation of this [`VaultTag`].
+	#[must_use]
+	pub const fn as_repr(&self) -> &Output<Cs::Hash> {
+		&self.tag
+	}
+
+	/// Deserializes the provided `repr` to a [`VaultTag`].
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::FromRepr`] if deserialization fails.
+	pub fn from_repr(repr: &[u8]) -> Result<Self> {
+		Ok(Self {
+			tag: repr.try_into().map_err(|_| Error::FromRepr)?,
+		})
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Cs: CipherSuite> MemoryVaultStore<Cs> {
+	/// Creates an empty [`MemoryVaultStore`].
+	#[must_use]
+	pub const fn new() -> Self {
+		Self(BTreeMap::new())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Cs: CipherSuite> VaultStore<Cs> for MemoryVaultStore<Cs> {
+	fn get(&self, user_id: &[u8]) -> Option<VaultRecord<Cs>> {
+		self.0.get(user_id).cloned()
+	}
+
+	fn insert(&mut self, user_id: &[u8], record: VaultRecord<Cs>) {
+		self.0.insert(user_id.to_vec(), record);
+	}
+
+	fn remove(&mut self, user_id: &[u8]) {
+		self.0.remove(user_id);
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for MemoryVaultStore<Cs> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for MemoryVaultStore<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("MemoryVaultStore").field(&self.0).finish()
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Default for MemoryVaultStore<Cs> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Cs: CipherSuite> Eq for MemoryVaultStore<Cs> {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for MemoryVaultStore<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0.eq(&other.0)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Cs: CipherSuite> ZeroizeOnDrop for MemoryVaultStore<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for VaultClient<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for VaultClient<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("VaultClient")
+			.field("client", &self.client)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for VaultClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for VaultClient<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.client.eq(&other.client)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for VaultClient<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for VaultRecord<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			server: self.server.clone(),
+			state: self.state.clone(),
+			attempts: self.attempts,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for VaultRecord<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("VaultRecord")
+			.field("server", &self.server)
+			.field("state", &self.state)
+			.field("attempts", &self.attempts)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> Eq for VaultRecord<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for VaultRecord<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.server.eq(&other.server)
+			&& self.state.eq(&other.state)
+			&& self.attempts.eq(&other.attempts)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for VaultRecord<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for VaultState<Cs> {
+	fn clone(&self) -> Self {
+		match self {
+			Self::Pending(enrollment) => Self::Pending(enrollment.clone()),
+			Self::Confirmed(tag) => Self::Confirmed(tag.clone()),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for VaultState<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Pending(enrollment) => f.debug_tuple("Pending").field(enrollment).finish(),
+			Self::Confirmed(tag) => f.debug_tuple("Confirmed").field(tag).finish(),
+		}
+	}
+}
+
+impl<Cs: CipherSuite> Eq for VaultState<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for VaultState<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Pending(this), Self::Pending(other)) => this.eq(other),
+			(Self::Confirmed(this), Self::Confirmed(other)) => this.eq(other),
+			_ => false,
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for VaultEnrollment<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			secret: self.secret.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for VaultEnrollment<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("VaultEnrollment")
+			.field("secret", &self.secret)
+			.finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for VaultEnrollment<Cs> {
+	fn drop(&mut self) {
+		self.secret.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for VaultEnrollment<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for VaultEnrollment<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.secret.eq(&other.secret)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for VaultEnrollment<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Clone for VaultTag<Cs> {
+	fn clone(&self) -> Self {
+		Self {
+			tag: self.tag.clone(),
+		}
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for VaultTag<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("VaultTag").field("tag", &self.tag).finish()
+	}
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Drop for VaultTag<Cs> {
+	fn drop(&mut self) {
+		self.tag.zeroize();
+	}
+}
+
+impl<Cs: CipherSuite> Eq for VaultTag<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> PartialEq for VaultTag<Cs> {
+	fn eq(&self, other: &Self) -> bool {
+		self.tag.eq(&other.tag)
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for VaultTag<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for VaultBlindResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("VaultBlindResult")
+			.field("client", &self.client)
+			.field("blinded_element", &self.blinded_element)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for VaultBlindResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for VaultEnrollResult<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("VaultEnrollResult")
+			.field("evaluation_element", &self.evaluation_element)
+			.field("enrollment", &self.enrollment)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for VaultEnrollResult<Cs> {}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<Cs: CipherSuite> Debug for VaultKeys<Cs> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("VaultKeys")
+			.field("key", &self.key)
+			.field("tag", &self.tag)
+			.finish()
+	}
+}
+
+impl<Cs: CipherSuite> ZeroizeOnDrop for VaultKeys<Cs> {}
+
+/// Derives a value from the OPRF `output` with the provided `label`.
+fn derive<Cs: CipherSuite>(output: &Output<Cs::Hash>, label: &[u8]) -> Output<Cs::Hash> {
+	Cs::Hash::default()
+		.chain(output)
+		.chain(label)
+		.chain(&*Cs::ID)
+		.finalize_fixed()
+}
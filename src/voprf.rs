@@ -20,7 +20,9 @@ use crate::cipher_suite::{CipherSuite, Element, NonIdentityElement, NonZeroScala
 use crate::common::BatchAllocBlindEvaluateResult;
 use crate::common::{
 	BatchBlindEvaluateResult, BlindEvaluateResult, BlindedElement, EvaluationElement, Mode, Proof,
+	UnblindedElement,
 };
+use crate::dleq::{self, Domain};
 use crate::error::{Error, Result};
 #[cfg(feature = "alloc")]
 use crate::internal::AllocBlindResult;
@@ -196,6 +198,31 @@ impl<Cs: CipherSuite> VoprfClient<Cs> {
 		Ok(output)
 	}
 
+	/// Completes the evaluation without hashing the [`UnblindedElement`] to an
+	/// output. [`UnblindedElement::finalize()`] with [`None`] produces the
+	/// same output as [`finalize()`](Self::finalize).
+	///
+	/// # Errors
+	///
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn finalize_element(
+		&self,
+		public_key: &PublicKey<Cs::Group>,
+		evaluation_element: &EvaluationElement<Cs>,
+		proof: &Proof<Cs>,
+	) -> Result<UnblindedElement<Cs>> {
+		let [unblinded_element] = Self::batch_finalize_element(
+			array::from_ref(self),
+			public_key,
+			array::from_ref(evaluation_element),
+			proof,
+		)?;
+		Ok(unblinded_element)
+	}
+
 	/// Batch completes evaluations with a combined [`Proof`] *without
 	/// allocation*.
 	///
@@ -223,6 +250,35 @@ impl<Cs: CipherSuite> VoprfClient<Cs> {
 		[Output<Cs::Hash>; N]:
 			AssocArraySize<Size: ArraySize<ArrayType<Output<Cs::Hash>> = [Output<Cs::Hash>; N]>>,
 	{
+		let unblinded_elements =
+			Self::batch_finalize_element(clients, public_key, evaluation_elements, proof)?;
+
+		internal::batch_finalize::<Cs, N>(
+			inputs,
+			unblinded_elements.iter().map(UnblindedElement::as_ref),
+			None,
+		)
+	}
+
+	/// Batch completes evaluations with a combined [`Proof`] without hashing
+	/// the [`UnblindedElement`]s *without allocation*.
+	///
+	/// See [`finalize_element()`](Self::finalize_element).
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of items in `clients` and
+	///   `evaluation_elements` are zero or exceed a length of [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	pub fn batch_finalize_element<const N: usize>(
+		clients: &[Self; N],
+		public_key: &PublicKey<Cs::Group>,
+		evaluation_elements: &[EvaluationElement<Cs>; N],
+		proof: &Proof<Cs>,
+	) -> Result<[UnblindedElement<Cs>; N]> {
 		if N == 0 || N > u16::MAX.into() {
 			return Err(Error::Batch);
 		}
@@ -230,16 +286,29 @@ impl<Cs: CipherSuite> VoprfClient<Cs> {
 		let c = clients.iter().map(|client| client.blinded_element.as_ref());
 		let d = evaluation_elements.iter().map(EvaluationElement::as_ref);
 
-		let composites =
-			internal::compute_composites::<_, N>(Mode::Voprf, None, public_key.as_ref(), c, d)?;
-		internal::verify_proof(Mode::Voprf, composites, public_key.as_ref(), proof)?;
+		let composites = dleq::compute_composites::<_, N>(
+			Domain::Rfc(Mode::Voprf),
+			None,
+			public_key.as_ref(),
+			c,
+			d,
+		)?;
+		dleq::verify_proof(
+			Domain::Rfc(Mode::Voprf),
+			composites,
+			public_key.as_ref(),
+			proof,
+		)?;
 
 		let blinds = clients.iter().map(|client| client.blind).collect_array();
 		let evaluation_elements = evaluation_elements
 			.iter()
 			.map(EvaluationElement::as_element);
 
-		internal::batch_finalize::<Cs, N>(inputs, blinds, evaluation_elements, None)
+		Ok(
+			internal::batch_finalize_element::<Cs, N>(blinds, evaluation_elements)
+				.map(UnblindedElement::from),
+		)
 	}
 
 	/// Batch completes evaluations with a combined [`Proof`].
@@ -269,14 +338,49 @@ impl<Cs: CipherSuite> VoprfClient<Cs> {
 		Ic: ExactSizeIterator<Item = &'clients Self>,
 		Ii: ExactSizeIterator<Item = &'inputs [&'inputs [u8]]>,
 		Iee: ExactSizeIterator<Item = &'evaluation_elements EvaluationElement<Cs>>,
+	{
+		if clients.len() != inputs.len() {
+			return Err(Error::Batch);
+		}
+
+		let unblinded_elements =
+			Self::batch_alloc_finalize_element(clients, public_key, evaluation_elements, proof)?;
+
+		internal::batch_alloc_finalize::<Cs>(
+			inputs,
+			unblinded_elements.iter().map(UnblindedElement::as_ref),
+			None,
+		)
+	}
+
+	/// Batch completes evaluations with a combined [`Proof`] without hashing
+	/// the [`UnblindedElement`]s.
+	///
+	/// See [`finalize_element()`](Self::finalize_element).
+	///
+	/// # Errors
+	///
+	/// - [`Error::Batch`] if the number of items in `clients` and
+	///   `evaluation_elements` are zero, don't match or exceed a length of
+	///   [`u16::MAX`].
+	/// - [`Error::InvalidCipherSuite`] if the [`CipherSuite`]s
+	///   [`Group`](CipherSuite::Group) and
+	///   [`ExpandMsg`](CipherSuite::ExpandMsg) are incompatible.
+	/// - [`Error::Proof`] if the [`Proof`] is invalid.
+	#[cfg(feature = "alloc")]
+	pub fn batch_alloc_finalize_element<'clients, 'evaluation_elements, Ic, Iee>(
+		clients: Ic,
+		public_key: &PublicKey<Cs::Group>,
+		evaluation_elements: Iee,
+		proof: &Proof<Cs>,
+	) -> Result<Vec<UnblindedElement<Cs>>>
+	where
+		Ic: ExactSizeIterator<Item = &'clients Self>,
+		Iee: ExactSizeIterator<Item = &'evaluation_elements EvaluationElement<Cs>>,
 	{
 		let length = clients.len();
 
-		if length == 0
-			|| length != inputs.len()
-			|| length != evaluation_elements.len()
-			|| length > u16::MAX.into()
-		{
+		if length == 0 || length != evaluation_elements.len() || length > u16::MAX.into() {
 			return Err(Error::Batch);
 		}
 
@@ -285,19 +389,39 @@ impl<Cs: CipherSuite> VoprfClient<Cs> {
 			.unzip();
 		let d: Vec<_> = evaluation_elements.map(EvaluationElement::as_ref).collect();
 
-		let composites = internal::alloc_compute_composites(
-			Mode::Voprf,
+		let composites = dleq::alloc_compute_composites(
+			Domain::Rfc(Mode::Voprf),
 			length,
 			None,
 			public_key.as_ref(),
 			c.into_iter(),
 			d.iter().copied(),
 		)?;
-		internal::verify_proof(Mode::Voprf, composites, public_key.as_ref(), proof)?;
+		dleq::verify_proof(
+			Domain::Rfc(Mode::Voprf),
+			composites,
+			public_key.as_ref(),
+			proof,
+		)?;
 
 		let evaluation_elements = d.into_iter().map(ElementWithRepr::as_element);
 
-		internal::batch_alloc_finalize::<Cs>(length, inputs, blinds, evaluation_elements, None)
+		Ok(
+			internal::batch_alloc_finalize_element::<Cs>(blinds, evaluation_elements)
+				.into_iter()
+				.map(UnblindedElement::from)
+				.collect(),
+		)
+	}
+
+	/// Returns the `blind`.
+	pub(crate) const fn blind(&self) -> NonZeroScalar<Cs> {
+		self.blind
+	}
+
+	/// Returns the [`BlindedElement`].
+	pub(crate) const fn blinded_element(&self) -> &BlindedElement<Cs> {
+		&self.blinded_element
 	}
 }
 
@@ -424,16 +548,16 @@ impl<Cs: CipherSuite> VoprfServer<Cs> {
 		let c = blinded_elements.iter().map(BlindedElement::as_ref);
 		let d = evaluation_elements.iter().map(EvaluationElement::as_ref);
 
-		let composites = internal::compute_composites::<_, N>(
-			Mode::Voprf,
+		let composites = dleq::compute_composites::<_, N>(
+			Domain::Rfc(Mode::Voprf),
 			Some(self.key_pair.secret_key().to_scalar()),
 			self.key_pair.public_key().as_ref(),
 			c.into_iter(),
 			d.into_iter(),
 		)
 		.map_err(Error::into_random::<R>)?;
-		let proof = internal::generate_proof(
-			Mode::Voprf,
+		let proof = dleq::generate_proof(
+			Domain::Rfc(Mode::Voprf),
 			rng,
 			self.key_pair.secret_key().to_scalar(),
 			composites,
@@ -483,8 +607,8 @@ impl<Cs: CipherSuite> VoprfServer<Cs> {
 		}));
 		let d = evaluation_elements.iter().map(EvaluationElement::as_ref);
 
-		let composites = internal::alloc_compute_composites(
-			Mode::Voprf,
+		let composites = dleq::alloc_compute_composites(
+			Domain::Rfc(Mode::Voprf),
 			blinded_elements_length,
 			Some(self.key_pair.secret_key().to_scalar()),
 			self.key_pair.public_key().as_ref(),
@@ -492,8 +616,8 @@ impl<Cs: CipherSuite> VoprfServer<Cs> {
 			d,
 		)
 		.map_err(Error::into_random::<R>)?;
-		let proof = internal::generate_proof(
-			Mode::Voprf,
+		let proof = dleq::generate_proof(
+			Domain::Rfc(Mode::Voprf),
 			rng,
 			self.key_pair.secret_key().to_scalar(),
 			composites,
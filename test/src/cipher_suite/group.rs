@@ -83,6 +83,10 @@ impl Group for MockCurve {
 		Ok(Scalar)
 	}
 
+	fn scalar_from_u64(_: u64) -> Self::Scalar {
+		Scalar
+	}
+
 	fn element_identity() -> Self::Element {
 		Element
 	}
@@ -122,6 +122,10 @@ impl Group for Edwards25519 {
 			.ok_or(InternalError)
 	}
 
+	fn scalar_from_u64(value: u64) -> Self::Scalar {
+		Scalar::from(value)
+	}
+
 	fn element_identity() -> Self::Element {
 		EdwardsPoint::identity()
 	}
@@ -101,6 +101,10 @@ impl Group for Edwards448 {
 		Scalar::from_repr(*repr).into_option().ok_or(InternalError)
 	}
 
+	fn scalar_from_u64(value: u64) -> Self::Scalar {
+		Scalar::from(value)
+	}
+
 	fn element_identity() -> Self::Element {
 		EdwardsPoint::IDENTITY
 	}
@@ -103,6 +103,10 @@ impl Group for Secp256k1 {
 		Scalar::from_repr(*repr).into_option().ok_or(InternalError)
 	}
 
+	fn scalar_from_u64(value: u64) -> Self::Scalar {
+		Scalar::from(value)
+	}
+
 	fn element_identity() -> Self::Element {
 		ProjectivePoint::IDENTITY
 	}
@@ -0,0 +1,119 @@
+//! Tests [`oprf::anonymous`] related functionality.
+
+#![cfg(test)]
+#![cfg(feature = "alloc")]
+#![expect(clippy::cargo_common_metadata, reason = "tests")]
+
+use oprf::Error;
+use oprf::anonymous::{self, AnonymousServer, OrProof};
+use oprf::cipher_suite::CipherSuite;
+use oprf::key::{KeyPair, PublicKey};
+use oprf::voprf::{VoprfBlindResult, VoprfClient, VoprfServer};
+use oprf_test::{INPUT, test_ciphersuites};
+
+/// Generates `count` random [`KeyPair`]s and their [`PublicKey`]s.
+fn key_set<Cs: CipherSuite>(count: usize) -> (Vec<KeyPair<Cs::Group>>, Vec<PublicKey<Cs::Group>>) {
+	let key_pairs: Vec<_> = (0..count)
+		.map(|_| KeyPair::generate(&mut rand::rng()).unwrap())
+		.collect();
+	let public_keys = key_pairs
+		.iter()
+		.map(|key_pair| key_pair.public_key().clone())
+		.collect();
+
+	(key_pairs, public_keys)
+}
+
+test_ciphersuites!(basic);
+
+/// Tests that every key of the set produces outputs matching regular VOPRF.
+fn basic<Cs: CipherSuite>() {
+	let (key_pairs, public_keys) = key_set::<Cs>(3);
+
+	for key_pair in key_pairs {
+		let server = AnonymousServer::<Cs>::new(key_pair.clone(), public_keys.clone()).unwrap();
+		let VoprfBlindResult {
+			client,
+			blinded_element,
+		} = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+		let result = server
+			.blind_evaluate(&mut rand::rng(), &blinded_element)
+			.unwrap();
+
+		let proof = OrProof::from_repr(&result.proof.to_repr()).unwrap();
+		assert_eq!(proof, result.proof);
+
+		let output = anonymous::finalize(
+			&client,
+			&public_keys,
+			INPUT,
+			&result.evaluation_element,
+			&proof,
+		)
+		.unwrap();
+		let expected = VoprfServer::<Cs>::from_key_pair(key_pair)
+			.evaluate(INPUT)
+			.unwrap();
+		assert_eq!(output, expected);
+	}
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests that [`OrProof`]s are rejected for other key sets or clients.
+fn invalid<Cs: CipherSuite>() {
+	let (mut key_pairs, public_keys) = key_set::<Cs>(3);
+	let (_, other_public_keys) = key_set::<Cs>(3);
+	let key_pair = key_pairs.pop().unwrap();
+
+	assert_eq!(
+		AnonymousServer::<Cs>::new(key_pair.clone(), other_public_keys.clone()),
+		Err(Error::KeySet)
+	);
+	assert_eq!(
+		AnonymousServer::<Cs>::new(key_pair.clone(), Vec::new()),
+		Err(Error::KeySet)
+	);
+
+	let server = AnonymousServer::<Cs>::new(key_pair, public_keys.clone()).unwrap();
+	let VoprfBlindResult {
+		client,
+		blinded_element,
+	} = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	let result = server
+		.blind_evaluate(&mut rand::rng(), &blinded_element)
+		.unwrap();
+
+	let finalize = |client: &VoprfClient<Cs>, public_keys: &[PublicKey<Cs::Group>]| {
+		anonymous::finalize_element(
+			client,
+			public_keys,
+			&result.evaluation_element,
+			&result.proof,
+		)
+	};
+
+	finalize(&client, &public_keys).unwrap();
+	assert_eq!(finalize(&client, &other_public_keys), Err(Error::Proof));
+	assert_eq!(finalize(&client, &[]), Err(Error::KeySet));
+
+	// Dropping or reordering keys invalidates the proof.
+	let (public_key, rest) = public_keys.split_last().unwrap();
+	assert_eq!(finalize(&client, rest), Err(Error::Proof));
+	let reordered: Vec<_> = [public_key.clone()]
+		.into_iter()
+		.chain(rest.iter().cloned())
+		.collect();
+	assert_eq!(finalize(&client, &reordered), Err(Error::Proof));
+
+	let VoprfBlindResult {
+		client: other_client,
+		..
+	} = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	assert_eq!(finalize(&other_client, &public_keys), Err(Error::Proof));
+
+	let repr = result.proof.to_repr();
+	let (_, repr) = repr.split_last().unwrap();
+	assert_eq!(OrProof::<Cs>::from_repr(repr), Err(Error::FromRepr));
+	assert_eq!(OrProof::<Cs>::from_repr(&[]), Err(Error::FromRepr));
+}
@@ -0,0 +1,112 @@
+//! Tests [`oprf::checkup`] related functionality.
+
+#![cfg(test)]
+#![cfg(feature = "alloc")]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use oprf::Error;
+use oprf::checkup::{
+	CheckupBuckets, CheckupClient, CheckupRequest, CheckupResponse, CheckupServer,
+};
+use oprf::cipher_suite::CipherSuite;
+use oprf_test::test_ciphersuites;
+
+/// Breached credentials known to the server.
+const BREACHED: &[&[&[u8]]] = &[
+	&[b"alice", b"password"],
+	&[b"bob", b"123456"],
+	&[b"carol", b"qwerty"],
+	&[b"dave", b"letmein"],
+];
+
+test_ciphersuites!(basic);
+
+/// Tests that breached and safe credentials are told apart.
+fn basic<Cs: CipherSuite>() {
+	let server = CheckupServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let buckets = server.buckets(BREACHED, 2).unwrap();
+	assert_eq!(buckets.prefix_bits(), 2);
+
+	let safe: [&[&[u8]]; 2] = [&[b"bob", b"654321"], &[b"eve", b"password"]];
+
+	for (credential, breached) in BREACHED
+		.iter()
+		.map(|credential| (*credential, true))
+		.chain(safe.into_iter().map(|credential| (credential, false)))
+	{
+		let result = CheckupClient::<Cs>::request(&mut rand::rng(), credential, 2).unwrap();
+		let request = CheckupRequest::from_repr(&result.request.to_repr()).unwrap();
+		assert_eq!(request, result.request);
+
+		let response = server.respond(&request, &buckets);
+		assert_eq!(response.bucket(), buckets.bucket(request.prefix()));
+		let response = CheckupResponse::from_repr(&response.to_repr()).unwrap();
+
+		assert_eq!(
+			result.client.check(credential, &response).unwrap(),
+			breached
+		);
+	}
+}
+
+test_ciphersuites!(insert);
+
+/// Tests that [`CheckupBuckets`] can be built incrementally.
+fn insert<Cs: CipherSuite>() {
+	let server = CheckupServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let mut buckets = CheckupBuckets::new(8).unwrap();
+
+	for credential in BREACHED.iter().rev() {
+		server.insert(&mut buckets, credential).unwrap();
+	}
+
+	// Duplicates are ignored.
+	server.insert(&mut buckets, BREACHED[0]).unwrap();
+
+	assert_eq!(buckets, server.buckets(BREACHED, 8).unwrap());
+
+	// A different server key doesn't find the credential.
+	let other = CheckupServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let result = CheckupClient::<Cs>::request(&mut rand::rng(), BREACHED[0], 8).unwrap();
+	let response = other.respond(&result.request, &buckets);
+
+	assert!(!result.client.check(BREACHED[0], &response).unwrap());
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests invalid prefix lengths and encodings.
+fn invalid<Cs: CipherSuite>() {
+	assert_eq!(CheckupBuckets::<Cs>::new(0), Err(Error::Prefix));
+	assert_eq!(CheckupBuckets::<Cs>::new(33), Err(Error::Prefix));
+	assert_eq!(
+		CheckupClient::<Cs>::request(&mut rand::rng(), BREACHED[0], 0).unwrap_err(),
+		Error::Prefix
+	);
+
+	let server = CheckupServer::<Cs>::new(&mut rand::rng()).unwrap();
+	assert_eq!(server.buckets(BREACHED, 33), Err(Error::Prefix));
+
+	let buckets = server.buckets(BREACHED, 1).unwrap();
+	let result = CheckupClient::<Cs>::request(&mut rand::rng(), BREACHED[0], 1).unwrap();
+
+	let repr = result.request.to_repr();
+	assert_eq!(
+		CheckupRequest::<Cs>::from_repr(&repr[..4]),
+		Err(Error::FromRepr)
+	);
+	assert_eq!(
+		CheckupRequest::<Cs>::from_repr(&repr[..repr.len() - 1]),
+		Err(Error::FromRepr)
+	);
+
+	let repr = server.respond(&result.request, &buckets).to_repr();
+	assert_eq!(
+		CheckupResponse::<Cs>::from_repr(&repr[..repr.len() - 1]),
+		Err(Error::FromRepr)
+	);
+}
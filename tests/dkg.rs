@@ -0,0 +1,274 @@
+//! Tests [`oprf::threshold::dkg`] related functionality.
+
+#![cfg(test)]
+#![cfg(feature = "alloc")]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use std::num::NonZeroU16;
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::group::Group;
+use oprf::threshold::dkg::{DkgCommitment, DkgComplaint, DkgOutput, DkgRound1, DkgShare};
+use oprf::threshold::{self, ThresholdServer};
+use oprf::voprf::VoprfClient;
+use oprf_test::{INPUT, test_ciphersuites};
+
+/// Runs the DKG in-process for all participants.
+///
+/// `tamper` can modify all [`DkgCommitment`]s and [`DkgShare`]s before they
+/// are delivered, `false_complaints` are broadcast in addition to the
+/// [`DkgComplaint`]s of all participants and `justify` decides if accused
+/// dealers answer [`DkgComplaint`]s.
+fn run<G, T>(
+	threshold: u16,
+	participants: u16,
+	tamper: T,
+	false_complaints: &[DkgComplaint],
+	justify: bool,
+) -> Vec<DkgOutput<G>>
+where
+	G: Group,
+	T: Fn(&mut Vec<DkgCommitment<G>>, &mut Vec<DkgShare<G>>),
+{
+	let rounds_1: Vec<_> = (1..=participants)
+		.map(|index| {
+			DkgRound1::<G>::new(
+				&mut rand::rng(),
+				NonZeroU16::new(index).unwrap(),
+				threshold,
+				participants,
+			)
+			.unwrap()
+		})
+		.collect();
+
+	let mut commitments: Vec<_> = rounds_1
+		.iter()
+		.map(|round| round.commitment().clone())
+		.collect();
+	let mut shares: Vec<_> = rounds_1.iter().flat_map(DkgRound1::shares).collect();
+	tamper(&mut commitments, &mut shares);
+
+	let rounds_2: Vec<_> = rounds_1
+		.into_iter()
+		.enumerate()
+		.map(|(position, round)| {
+			let index = NonZeroU16::new(u16::try_from(position).unwrap() + 1).unwrap();
+			let shares: Vec<_> = shares
+				.iter()
+				.filter(|share| share.recipient() == index)
+				.cloned()
+				.collect();
+
+			round.receive(&commitments, &shares)
+		})
+		.collect();
+
+	let complaints: Vec<DkgComplaint> = rounds_2
+		.iter()
+		.flat_map(|round| round.complaints().iter().copied())
+		.chain(false_complaints.iter().copied())
+		.collect();
+	let justifications: Vec<_> = if justify {
+		rounds_2
+			.iter()
+			.flat_map(|round| round.justify(&complaints))
+			.collect()
+	} else {
+		Vec::new()
+	};
+
+	rounds_2
+		.into_iter()
+		.map(|round| round.finish(&complaints, &justifications).unwrap())
+		.collect()
+}
+
+/// Asserts that all participants agree on the outcome and that their
+/// [`KeyShare`](threshold::KeyShare)s jointly evaluate under the group
+/// [`PublicKey`](oprf::key::PublicKey).
+fn assert_outputs<Cs: CipherSuite>(threshold: u16, outputs: &[DkgOutput<Cs::Group>]) {
+	for output in outputs {
+		assert_eq!(output.public_key(), outputs[0].public_key());
+		assert_eq!(output.disqualified(), outputs[0].disqualified());
+		assert_eq!(output.false_complainers(), outputs[0].false_complainers());
+		assert_eq!(
+			output.key_share().public_key_share(),
+			outputs[0]
+				.public_key_share(output.key_share().index())
+				.unwrap()
+		);
+	}
+
+	let public_key_shares: Vec<_> = outputs
+		.iter()
+		.map(|output| output.key_share().public_key_share())
+		.collect();
+	let blind_result = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+
+	let mut results = outputs.windows(threshold.into()).map(|outputs| {
+		let shares: Vec<_> = outputs
+			.iter()
+			.map(|output| {
+				ThresholdServer::<Cs>::from_key_share(output.key_share().clone())
+					.verifiable_blind_evaluate(&mut rand::rng(), &blind_result.blinded_element)
+					.unwrap()
+			})
+			.collect();
+
+		threshold::verifiable_finalize(
+			&blind_result.client,
+			threshold,
+			&public_key_shares,
+			INPUT,
+			&shares,
+		)
+		.unwrap()
+	});
+
+	let expected = results.next().unwrap();
+	assert!(results.all(|output| output == expected));
+}
+
+test_ciphersuites!(dkg);
+
+/// Tests that an honest DKG produces consistent [`KeyShare`](threshold::KeyShare)s.
+fn dkg<Cs: CipherSuite>() {
+	let outputs = run::<Cs::Group, _>(3, 5, |_, _| (), &[], true);
+
+	assert!(outputs[0].disqualified().is_empty());
+	assert!(outputs[0].false_complainers().is_empty());
+	assert_eq!(outputs[0].commitments().len(), 3);
+	assert_outputs::<Cs>(3, &outputs);
+}
+
+/// Returns a copy of the provided [`DkgShare`] with an invalid share.
+fn invalid<G: Group>(share: &DkgShare<G>) -> DkgShare<G> {
+	let mut repr = share.to_repr();
+	repr[4..].copy_from_slice(&G::scalar_to_repr(&G::scalar_from_u64(1)));
+	DkgShare::from_repr(&repr).unwrap()
+}
+
+/// Replaces the [`DkgShare`] of dealer `2` for participant `1` with an invalid
+/// one.
+fn tamper<G: Group>(shares: &mut [DkgShare<G>]) {
+	for share in shares {
+		if share.dealer().get() == 2 && share.recipient().get() == 1 {
+			*share = invalid(share);
+		}
+	}
+}
+
+test_ciphersuites!(justified);
+
+/// Tests that a justified [`DkgComplaint`] doesn't disqualify the dealer.
+fn justified<Cs: CipherSuite>() {
+	let outputs = run::<Cs::Group, _>(2, 4, |_, shares| tamper(shares), &[], true);
+
+	assert!(outputs[0].disqualified().is_empty());
+	assert_eq!(
+		outputs[0].false_complainers(),
+		[NonZeroU16::new(1).unwrap()]
+	);
+	assert_outputs::<Cs>(2, &outputs);
+}
+
+test_ciphersuites!(blame);
+
+/// Tests that a dealer failing to justify a [`DkgComplaint`] is disqualified.
+fn blame<Cs: CipherSuite>() {
+	let outputs = run::<Cs::Group, _>(2, 4, |_, shares| tamper(shares), &[], false);
+
+	assert_eq!(outputs[0].disqualified(), [NonZeroU16::new(2).unwrap()]);
+	assert!(outputs[0].false_complainers().is_empty());
+	assert_outputs::<Cs>(2, &outputs);
+}
+
+test_ciphersuites!(equivocate_commitment);
+
+/// Tests that a dealer broadcasting conflicting [`DkgCommitment`]s is
+/// disqualified while all other participants finish.
+fn equivocate_commitment<Cs: CipherSuite>() {
+	let mut outputs = run::<Cs::Group, _>(
+		2,
+		4,
+		|commitments, _| {
+			let round =
+				DkgRound1::new(&mut rand::rng(), NonZeroU16::new(3).unwrap(), 2, 4).unwrap();
+			commitments.push(round.commitment().clone());
+		},
+		&[],
+		true,
+	);
+
+	// The equivocating dealer doesn't learn about its own disqualification.
+	outputs.remove(2);
+
+	assert_eq!(outputs[0].disqualified(), [NonZeroU16::new(3).unwrap()]);
+	assert_outputs::<Cs>(2, &outputs);
+}
+
+test_ciphersuites!(equivocate_share);
+
+/// Tests that a dealer sending conflicting [`DkgShare`]s to a participant has
+/// to justify the resulting [`DkgComplaint`].
+fn equivocate_share<Cs: CipherSuite>() {
+	/// Adds an invalid [`DkgShare`] of dealer `2` for participant `1`.
+	fn equivocate<G: Group>(shares: &mut Vec<DkgShare<G>>) {
+		let share = invalid(
+			shares
+				.iter()
+				.find(|share| share.dealer().get() == 2 && share.recipient().get() == 1)
+				.unwrap(),
+		);
+		shares.push(share);
+	}
+
+	let outputs = run::<Cs::Group, _>(2, 4, |_, shares| equivocate(shares), &[], true);
+
+	assert!(outputs[0].disqualified().is_empty());
+	assert_eq!(
+		outputs[0].false_complainers(),
+		[NonZeroU16::new(1).unwrap()]
+	);
+	assert_outputs::<Cs>(2, &outputs);
+
+	let outputs = run::<Cs::Group, _>(2, 4, |_, shares| equivocate(shares), &[], false);
+
+	assert_eq!(outputs[0].disqualified(), [NonZeroU16::new(2).unwrap()]);
+	assert_outputs::<Cs>(2, &outputs);
+}
+
+test_ciphersuites!(false_complaint);
+
+/// Tests that a false [`DkgComplaint`] is recorded without disqualifying the
+/// accused dealer.
+fn false_complaint<Cs: CipherSuite>() {
+	let complaint = DkgComplaint::from_repr(&[0, 3, 0, 1]).unwrap();
+	let outputs = run::<Cs::Group, _>(2, 4, |_, _| (), &[complaint], true);
+
+	assert!(outputs[0].disqualified().is_empty());
+	assert_eq!(
+		outputs[0].false_complainers(),
+		[NonZeroU16::new(3).unwrap()]
+	);
+	assert_outputs::<Cs>(2, &outputs);
+}
+
+test_ciphersuites!(parameters);
+
+/// Tests invalid DKG parameters.
+fn parameters<Cs: CipherSuite>() {
+	let index = NonZeroU16::new(1).unwrap();
+
+	for (index, threshold, participants) in [(index, 0, 3), (index, 4, 3), (NonZeroU16::MAX, 2, 3)]
+	{
+		let result = DkgRound1::<Cs::Group>::new(&mut rand::rng(), index, threshold, participants);
+		assert_eq!(result.unwrap_err(), Error::Threshold);
+	}
+}
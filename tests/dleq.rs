@@ -0,0 +1,119 @@
+//! Tests [`oprf::dleq`] related functionality.
+
+#![cfg(test)]
+#![expect(clippy::cargo_common_metadata, reason = "tests")]
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::dleq;
+use oprf::group::Group;
+use oprf::key::{KeyPair, SecretKey};
+use oprf_test::test_ciphersuites;
+
+/// Domain separation tag used in tests.
+const DST: &[u8] = b"DLEQ-Test";
+
+test_ciphersuites!(basic);
+
+/// Tests that [`Proof`](oprf::Proof)s verify only for the proven statement.
+fn basic<Cs: CipherSuite>() {
+	let secret_key = SecretKey::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let k = *secret_key.as_scalar();
+	let a = element::<Cs>();
+	let b = k * &a;
+	let c = element::<Cs>();
+	let d = k * &c;
+
+	let proof = dleq::prove::<Cs, _>(&mut rand::rng(), DST, &secret_key, &a, &b, &c, &d).unwrap();
+	dleq::verify(DST, &a, &b, &c, &d, &proof).unwrap();
+
+	assert_eq!(
+		dleq::verify(b"DLEQ-Other", &a, &b, &c, &d, &proof),
+		Err(Error::Proof)
+	);
+	assert_eq!(dleq::verify(DST, &c, &b, &a, &d, &proof), Err(Error::Proof));
+	assert_eq!(dleq::verify(DST, &a, &d, &c, &b, &proof), Err(Error::Proof));
+
+	// A wrong discrete logarithm doesn't produce a valid `Proof`.
+	let other = SecretKey::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let proof = dleq::prove::<Cs, _>(&mut rand::rng(), DST, &other, &a, &b, &c, &d).unwrap();
+	assert_eq!(dleq::verify(DST, &a, &b, &c, &d, &proof), Err(Error::Proof));
+}
+
+test_ciphersuites!(batch);
+
+/// Tests batched [`Proof`]s.
+fn batch<Cs: CipherSuite>() {
+	let secret_key = SecretKey::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let k = *secret_key.as_scalar();
+	let a = element::<Cs>();
+	let b = k * &a;
+	let c = [element::<Cs>(), element::<Cs>()];
+	let d = c.map(|c| k * &c);
+
+	let proof =
+		dleq::batch_prove::<Cs, _, 2>(&mut rand::rng(), DST, &secret_key, &a, &b, &c, &d).unwrap();
+	dleq::batch_verify(DST, &a, &b, &c, &d, &proof).unwrap();
+
+	let [d0, d1] = d;
+	assert_eq!(
+		dleq::batch_verify(DST, &a, &b, &c, &[d1, d0], &proof),
+		Err(Error::Proof)
+	);
+
+	#[cfg(feature = "alloc")]
+	{
+		dleq::batch_alloc_verify(DST, &a, &b, &c, &d, &proof).unwrap();
+
+		let proof =
+			dleq::batch_alloc_prove::<Cs, _>(&mut rand::rng(), DST, &secret_key, &a, &b, &c, &d)
+				.unwrap();
+		dleq::batch_verify(DST, &a, &b, &c, &d, &proof).unwrap();
+
+		assert_eq!(
+			dleq::batch_alloc_verify(DST, &a, &b, &c, &d[..1], &proof),
+			Err(Error::Batch)
+		);
+		assert_eq!(
+			dleq::batch_alloc_verify(DST, &a, &b, &[], &[], &proof),
+			Err(Error::Batch)
+		);
+	}
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests invalid DSTs and batch sizes.
+fn invalid<Cs: CipherSuite>() {
+	let secret_key = SecretKey::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let k = *secret_key.as_scalar();
+	let a = element::<Cs>();
+	let b = k * &a;
+
+	let prove =
+		|dst: &[u8]| dleq::prove::<Cs, _>(&mut rand::rng(), dst, &secret_key, &a, &b, &a, &b);
+
+	let proof = prove(DST).unwrap();
+	assert_eq!(prove(&[]), Err(Error::Dst));
+	assert_eq!(prove(&[0; 256]), Err(Error::Dst));
+	prove(&[0; 255]).unwrap();
+
+	assert_eq!(dleq::verify(&[], &a, &b, &a, &b, &proof), Err(Error::Dst));
+
+	assert_eq!(
+		dleq::batch_prove::<Cs, _, 0>(&mut rand::rng(), DST, &secret_key, &a, &b, &[], &[]),
+		Err(Error::Batch)
+	);
+	assert_eq!(
+		dleq::batch_verify::<Cs, 0>(DST, &a, &b, &[], &[], &proof),
+		Err(Error::Batch)
+	);
+}
+
+/// Returns a random [`NonIdentityElement`](Group::NonIdentityElement).
+fn element<Cs: CipherSuite>() -> <Cs::Group as Group>::NonIdentityElement {
+	*KeyPair::<Cs::Group>::generate(&mut rand::rng())
+		.unwrap()
+		.public_key()
+		.as_element()
+}
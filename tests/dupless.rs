@@ -0,0 +1,180 @@
+//! Tests [`oprf::dupless`] related functionality.
+
+#![cfg(test)]
+#![cfg(feature = "alloc")]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use digest::Output;
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::dupless::{DuplessClient, DuplessServer, MemoryRateLimiter, RateLimiter};
+use oprf_test::test_ciphersuites;
+
+/// `client_id` to use.
+const CLIENT_ID: &[u8] = b"client";
+/// Files to use.
+const FILES: [&[u8]; 3] = [b"file 1", b"file 2", b"file 3"];
+
+/// Derives the content keys of the provided `contents` with the
+/// [`DuplessServer`].
+fn derive<Cs: CipherSuite>(
+	server: &DuplessServer<Cs>,
+	limiter: &mut MemoryRateLimiter,
+	client_id: &[u8],
+	contents: &[&[u8]],
+) -> Result<Vec<Output<Cs::Hash>>, Error> {
+	let blind_result = DuplessClient::<Cs>::blind(&mut rand::rng(), contents).unwrap();
+	let evaluate_result = server.evaluate(
+		&mut rand::rng(),
+		limiter,
+		client_id,
+		&blind_result.blinded_elements,
+	)?;
+
+	blind_result.client.finalize(
+		server.public_key(),
+		&evaluate_result.evaluation_elements,
+		&evaluate_result.proof,
+	)
+}
+
+test_ciphersuites!(basic);
+
+/// Tests that equal files derive equal content keys.
+fn basic<Cs: CipherSuite>() {
+	let server = DuplessServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let mut limiter = MemoryRateLimiter::new(usize::MAX);
+
+	let keys = derive(&server, &mut limiter, CLIENT_ID, &FILES).unwrap();
+	assert_eq!(keys.len(), FILES.len());
+	assert_ne!(keys[0], keys[1]);
+	assert_ne!(keys[1], keys[2]);
+
+	// Keys are independent of the client and the batch.
+	for (file, key) in FILES.iter().zip(&keys) {
+		let other_keys = derive(&server, &mut limiter, b"other", &[*file]).unwrap();
+		assert_eq!(other_keys, [key.clone()]);
+	}
+
+	let reversed: Vec<_> = FILES.iter().rev().copied().collect();
+	let mut reversed_keys = derive(&server, &mut limiter, CLIENT_ID, &reversed).unwrap();
+	reversed_keys.reverse();
+	assert_eq!(reversed_keys, keys);
+
+	// Keys depend on the key server.
+	let other_server = DuplessServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let other_keys = derive(&other_server, &mut limiter, CLIENT_ID, &FILES).unwrap();
+	assert_ne!(other_keys, keys);
+
+	let server = DuplessServer::<Cs>::from_key_pair(server.key_pair().clone());
+	assert_eq!(
+		derive(&server, &mut limiter, CLIENT_ID, &FILES).unwrap(),
+		keys
+	);
+}
+
+test_ciphersuites!(limit);
+
+/// Tests that the [`MemoryRateLimiter`] limits content keys per client.
+fn limit<Cs: CipherSuite>() {
+	let server = DuplessServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let mut limiter = MemoryRateLimiter::new(4);
+
+	derive(&server, &mut limiter, CLIENT_ID, &FILES).unwrap();
+	assert_eq!(limiter.remaining(CLIENT_ID), 1);
+
+	// Rejected requests aren't counted.
+	let result = derive(&server, &mut limiter, CLIENT_ID, &FILES[..2]);
+	assert_eq!(result.unwrap_err(), Error::RateLimit);
+	assert_eq!(limiter.remaining(CLIENT_ID), 1);
+
+	derive(&server, &mut limiter, CLIENT_ID, &FILES[..1]).unwrap();
+	assert_eq!(limiter.remaining(CLIENT_ID), 0);
+
+	let result = derive(&server, &mut limiter, CLIENT_ID, &FILES[..1]);
+	assert_eq!(result.unwrap_err(), Error::RateLimit);
+
+	// Other clients are unaffected.
+	assert_eq!(limiter.remaining(b"other"), limiter.limit());
+	derive(&server, &mut limiter, b"other", &FILES).unwrap();
+
+	// Reset starts a new period.
+	limiter.reset();
+	assert_eq!(limiter.remaining(CLIENT_ID), 4);
+	assert!(limiter.allow(CLIENT_ID, 4));
+	assert!(!limiter.allow(CLIENT_ID, 1));
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests that invalid requests and responses are rejected.
+fn invalid<Cs: CipherSuite>() {
+	let server = DuplessServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let other_server = DuplessServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let mut limiter = MemoryRateLimiter::new(usize::MAX);
+
+	let blind_result = DuplessClient::<Cs>::blind(&mut rand::rng(), &FILES).unwrap();
+	let evaluate_result = server
+		.evaluate(
+			&mut rand::rng(),
+			&mut limiter,
+			CLIENT_ID,
+			&blind_result.blinded_elements,
+		)
+		.unwrap();
+
+	// Failure on wrong key server.
+	let result = blind_result.client.finalize(
+		other_server.public_key(),
+		&evaluate_result.evaluation_elements,
+		&evaluate_result.proof,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on inconsistent key.
+	let mut evaluation_elements = evaluate_result.evaluation_elements.clone();
+	evaluation_elements[1] = other_server
+		.evaluate(
+			&mut rand::rng(),
+			&mut limiter,
+			CLIENT_ID,
+			&blind_result.blinded_elements,
+		)
+		.unwrap()
+		.evaluation_elements[1]
+		.clone();
+	let result = blind_result.client.finalize(
+		server.public_key(),
+		&evaluation_elements,
+		&evaluate_result.proof,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on response to a different request.
+	let other_blind_result = DuplessClient::<Cs>::blind(&mut rand::rng(), &FILES).unwrap();
+	let result = other_blind_result.client.finalize(
+		server.public_key(),
+		&evaluate_result.evaluation_elements,
+		&evaluate_result.proof,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on wrong number of evaluation elements.
+	let result = blind_result.client.finalize(
+		server.public_key(),
+		&evaluate_result.evaluation_elements[..2],
+		&evaluate_result.proof,
+	);
+	assert_eq!(result.unwrap_err(), Error::Batch);
+
+	// Failure on no files.
+	let result = DuplessClient::<Cs>::blind(&mut rand::rng(), &[]);
+	assert_eq!(result.unwrap_err(), Error::Batch);
+
+	let result = server.evaluate(&mut rand::rng(), &mut limiter, CLIENT_ID, &[]);
+	assert_eq!(result.unwrap_err(), Error::Batch);
+}
@@ -3,14 +3,28 @@
 #![cfg(test)]
 #![expect(clippy::cargo_common_metadata, reason = "tests")]
 
-use oprf::cipher_suite::CipherSuite;
+use core::marker::PhantomData;
+use oprf::Error;
+
+use oprf::cipher_suite::{CipherSuite, Id};
 use oprf::common::Mode;
-use oprf::key::{KeyPair, PublicKey, SecretKey};
+use oprf::key::{KeyPair, PossessionProof, PublicKey, SecretKey};
 use oprf::oprf::OprfServer;
 use oprf::poprf::PoprfServer;
 use oprf::voprf::VoprfServer;
 use oprf_test::{INFO, test_ciphersuites};
 
+/// [`CipherSuite`] that only differs from `Cs` by its [`CipherSuite::ID`].
+struct OtherCs<Cs>(PhantomData<Cs>);
+
+impl<Cs: CipherSuite> CipherSuite for OtherCs<Cs> {
+	const ID: Id = Id::new(b"other").unwrap();
+
+	type Group = Cs::Group;
+	type Hash = Cs::Hash;
+	type ExpandMsg = Cs::ExpandMsg;
+}
+
 test_ciphersuites!(basic);
 
 // Tests basic key functionality.
@@ -65,6 +79,56 @@ fn derive<Cs: CipherSuite>(mode: Mode) {
 	);
 }
 
+test_ciphersuites!(possession, Mode);
+
+// Tests proof of possession.
+fn possession<Cs: CipherSuite>(mode: Mode) {
+	let key_pair = KeyPair::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let proof = key_pair
+		.prove_possession::<Cs, _>(&mut rand::rng(), mode, INFO)
+		.unwrap();
+
+	key_pair
+		.public_key()
+		.verify_possession(mode, &proof, INFO)
+		.unwrap();
+
+	// Check de/serialization.
+	assert_eq!(
+		PossessionProof::<Cs>::from_repr(&proof.to_repr()).unwrap(),
+		proof
+	);
+
+	// Failure on different context.
+	let result = key_pair.public_key().verify_possession(mode, &proof, &[]);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on different mode.
+	let other_mode = if mode == Mode::Oprf {
+		Mode::Voprf
+	} else {
+		Mode::Oprf
+	};
+	let result = key_pair
+		.public_key()
+		.verify_possession(other_mode, &proof, INFO);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on different cipher suite.
+	let other_proof = PossessionProof::<OtherCs<Cs>>::from_repr(&proof.to_repr()).unwrap();
+	let result = key_pair
+		.public_key()
+		.verify_possession(mode, &other_proof, INFO);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on different public key.
+	let other_key_pair = KeyPair::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let result = other_key_pair
+		.public_key()
+		.verify_possession(mode, &proof, INFO);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+}
+
 test_ciphersuites!(oprf_from_seed);
 
 fn oprf_from_seed<Cs: CipherSuite>() {
@@ -0,0 +1,456 @@
+//! Tests [`oprf::opaque`] related functionality.
+
+#![cfg(test)]
+#![cfg(all(
+	feature = "opaque",
+	feature = "p256-ciphersuite",
+	feature = "ristretto255-ciphersuite"
+))]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use hex_literal::hex;
+use oprf::key::KeyPair;
+use oprf::opaque::{
+	ClientLogin, ClientRegistration, Identifiers, IdentityKsf, Ke1, Ke2, Ke3, OpaqueCipherSuite,
+	OpaqueServer, RegistrationRecord, RegistrationRequest, RegistrationResponse,
+};
+use oprf::{Error, NistP256, Ristretto255};
+use rand::{CryptoRng, RngCore};
+
+/// `password` used in every test vector.
+const PASSWORD: &[u8] = b"CorrectHorseBatteryStaple";
+/// `credential_identifier` used in every test vector.
+const CREDENTIAL_IDENTIFIER: &[u8] = b"1234";
+/// `context` used in every test vector.
+const CONTEXT: &[u8] = b"OPAQUE-POC";
+
+/// [RFC 9807 Appendix C.1.1](https://www.rfc-editor.org/rfc/rfc9807.html#appendix-C.1.1).
+const RISTRETTO255: Vector = Vector {
+	oprf_seed: &hex!(
+		"f433d0227b0b9dd54f7c4422b600e764e47fb503f1f9a0f0a47c6606b054a7fd"
+		"c65347f1a08f277e22358bbabe26f823fca82c7848e9a75661f4ec5d5c1989ef"
+	),
+	envelope_nonce: &hex!("ac13171b2f17bc2c74997f0fce1e1f35bec6b91fe2e12dbd323d23ba7a38dfec"),
+	masking_nonce: &hex!("38fe59af0df2c79f57b8780278f5ae47355fe1f817119041951c80f612fdfc6d"),
+	server_private_key: &hex!("47451a85372f8b3537e249d7b54188091fb18edde78094b43e2ba42b5eb89f0d"),
+	server_public_key: &hex!("b2fe7af9f48cc502d016729d2fe25cdd433f2c4bc904660b2a382c9b79df1a78"),
+	server_nonce: &hex!("71cd9960ecef2fe0d0f7494986fa3d8b2bb01963537e60efb13981e138e3d4a1"),
+	client_nonce: &hex!("da7e07376d6d6f034cfa9bb537d11b8c6b4238c334333d1f0aebb380cae6a6cc"),
+	client_keyshare_seed: &hex!("82850a697b42a505f5b68fcdafce8c31f0af2b581f063cf1091933541936304b"),
+	server_keyshare_seed: &hex!("05a4f54206eef1ba2f615bc0aa285cb22f26d1153b5b40a1e85ff80da12f982f"),
+	blind_registration: &hex!("76cfbfe758db884bebb33582331ba9f159720ca8784a2a070a265d9c2d6abe01"),
+	blind_login: &hex!("6ecc102d2e7a7cf49617aad7bbe188556792d4acd60a1a8a8d2b65d4b0790308"),
+	registration_request: &hex!("5059ff249eb1551b7ce4991f3336205bde44a105a032e747d21bf382e75f7a71"),
+	registration_response: &hex!(
+		"7408a268083e03abc7097fc05b587834539065e86fb0c7b6342fcf5e01e5b019"
+		"b2fe7af9f48cc502d016729d2fe25cdd433f2c4bc904660b2a382c9b79df1a78"
+	),
+	registration_upload: &hex!(
+		"76a845464c68a5d2f7e442436bb1424953b17d3e2e289ccbaccafb57ac5c3675"
+		"1ac5844383c7708077dea41cbefe2fa15724f449e535dd7dd562e66f5ecfb958"
+		"64eadddec9db5874959905117dad40a4524111849799281fefe3c51fa82785c5"
+		"ac13171b2f17bc2c74997f0fce1e1f35bec6b91fe2e12dbd323d23ba7a38dfec"
+		"634b0f5b96109c198a8027da51854c35bee90d1e1c781806d07d49b76de6a28b"
+		"8d9e9b6c93b9f8b64d16dddd9c5bfb5fea48ee8fd2f75012a8b308605cdd8ba5"
+	),
+	ke1: &hex!(
+		"c4dedb0ba6ed5d965d6f250fbe554cd45cba5dfcce3ce836e4aee778aa3cd44d"
+		"da7e07376d6d6f034cfa9bb537d11b8c6b4238c334333d1f0aebb380cae6a6cc"
+		"6e29bee50701498605b2c085d7b241ca15ba5c32027dd21ba420b94ce60da326"
+	),
+	ke2: &hex!(
+		"7e308140890bcde30cbcea28b01ea1ecfbd077cff62c4def8efa075aabcbb471"
+		"38fe59af0df2c79f57b8780278f5ae47355fe1f817119041951c80f612fdfc6d"
+		"d6ec60bcdb26dc455ddf3e718f1020490c192d70dfc7e403981179d8073d1146"
+		"a4f9aa1ced4e4cd984c657eb3b54ced3848326f70331953d91b02535af44d9fe"
+		"dc80188ca46743c52786e0382f95ad85c08f6afcd1ccfbff95e2bdeb015b166c"
+		"6b20b92f832cc6df01e0b86a7efd92c1c804ff865781fa93f2f20b446c8371b6"
+		"71cd9960ecef2fe0d0f7494986fa3d8b2bb01963537e60efb13981e138e3d4a1"
+		"c4f62198a9d6fa9170c42c3c71f1971b29eb1d5d0bd733e40816c91f7912cc4a"
+		"660c48dae03e57aaa38f3d0cffcfc21852ebc8b405d15bd6744945ba1a93438a"
+		"162b6111699d98a16bb55b7bdddfe0fc5608b23da246e7bd73b47369169c5c90"
+	),
+	ke3: &hex!(
+		"4455df4f810ac31a6748835888564b536e6da5d9944dfea9e34defb9575fe5e2"
+		"661ef61d2ae3929bcf57e53d464113d364365eb7d1a57b629707ca48da18e442"
+	),
+	export_key: &hex!(
+		"1ef15b4fa99e8a852412450ab78713aad30d21fa6966c9b8c9fb3262a970dc62"
+		"950d4dd4ed62598229b1b72794fc0335199d9f7fcc6eaedde92cc04870e63f16"
+	),
+	session_key: &hex!(
+		"42afde6f5aca0cfa5c163763fbad55e73a41db6b41bc87b8e7b62214a8eedc67"
+		"31fa3cb857d657ab9b3764b89a84e91ebcb4785166fbb02cedfcbdfda215b96f"
+	),
+};
+
+/// [RFC 9807 Appendix C.1.3](https://www.rfc-editor.org/rfc/rfc9807.html#appendix-C.1.3).
+const P256: Vector = Vector {
+	oprf_seed: &hex!("62f60b286d20ce4fd1d64809b0021dad6ed5d52a2c8cf27ae6582543a0a8dce2"),
+	envelope_nonce: &hex!("a921f2a014513bd8a90e477a629794e89fec12d12206dde662ebdcf65670e51f"),
+	masking_nonce: &hex!("38fe59af0df2c79f57b8780278f5ae47355fe1f817119041951c80f612fdfc6d"),
+	server_private_key: &hex!("c36139381df63bfc91c850db0b9cfbec7a62e86d80040a41aa7725bf0e79d5e5"),
+	server_public_key: &hex!("035f40ff9cf88aa1f5cd4fe5fd3da9ea65a4923a5594f84fd9f2092d6067784874"),
+	server_nonce: &hex!("71cd9960ecef2fe0d0f7494986fa3d8b2bb01963537e60efb13981e138e3d4a1"),
+	client_nonce: &hex!("ab3d33bde0e93eda72392346a7a73051110674bbf6b1b7ffab8be4f91fdaeeb1"),
+	client_keyshare_seed: &hex!("633b875d74d1556d2a2789309972b06db21dfcc4f5ad51d7e74d783b7cfab8dc"),
+	server_keyshare_seed: &hex!("05a4f54206eef1ba2f615bc0aa285cb22f26d1153b5b40a1e85ff80da12f982f"),
+	blind_registration: &hex!("411bf1a62d119afe30df682b91a0a33d777972d4f2daa4b34ca527d597078153"),
+	blind_login: &hex!("c497fddf6056d241e6cf9fb7ac37c384f49b357a221eb0a802c989b9942256c1"),
+	registration_request: &hex!(
+		"029e949a29cfa0bf7c1287333d2fb3dc586c41aa652f5070d26a5315a1b50229f8"
+	),
+	registration_response: &hex!(
+		"0350d3694c00978f00a5ce7cd08a00547e4ab5fb5fc2b2f6717cdaa6c89136efef"
+		"035f40ff9cf88aa1f5cd4fe5fd3da9ea65a4923a5594f84fd9f2092d6067784874"
+	),
+	registration_upload: &hex!(
+		"03b218507d978c3db570ca994aaf36695a731ddb2db272c817f79746fc37ae5214"
+		"7f0ed53532d3ae8e505ecc70d42d2b814b6b0e48156def71ea029148b2803aaf"
+		"a921f2a014513bd8a90e477a629794e89fec12d12206dde662ebdcf65670e51f"
+		"ad30bbcfc1f8eda0211553ab9aaf26345ad59a128e80188f035fe4924fad67b8"
+	),
+	ke1: &hex!(
+		"037342f0bcb3ecea754c1e67576c86aa90c1de3875f390ad599a26686cdfee6e07"
+		"ab3d33bde0e93eda72392346a7a73051110674bbf6b1b7ffab8be4f91fdaeeb1"
+		"022ed3f32f318f81bab80da321fecab3cd9b6eea11a95666dfa6beeaab321280b6"
+	),
+	ke2: &hex!(
+		"0246da9fe4d41d5ba69faa6c509a1d5bafd49a48615a47a8dd4b0823cc14764811"
+		"38fe59af0df2c79f57b8780278f5ae47355fe1f817119041951c80f612fdfc6d"
+		"2f0c547f70deaeca54d878c14c1aa5e1ab405dec833777132eea905c2fbb1250"
+		"4a67dcbe0e66740c76b62c13b04a38a77926e19072953319ec65e41f9bfd2ae2"
+		"6837b6ce688bf9af2542f04eec9ab96a1b9328812dc2f5c89182ed47fead61f0"
+		"9f71cd9960ecef2fe0d0f7494986fa3d8b2bb01963537e60efb13981e138e3d4"
+		"a103c1701353219b53acf337bf6456a83cefed8f563f1040b65afbf3b65d3bc9"
+		"a19b50a73b145bc87a157e8c58c0342e2047ee22ae37b63db17e0a82a30fcc4e"
+		"cf7b"
+	),
+	ke3: &hex!("e97cab4433aa39d598e76f13e768bba61c682947bdcf9936035e8a3a3ebfb66e"),
+	export_key: &hex!("c3c9a1b0e33ac84dd83d0b7e8af6794e17e7a3caadff289fbd9dc769a853c64b"),
+	session_key: &hex!("484ad345715ccce138ca49e4ea362c6183f0949aaaa1125dc3bc3f80876e7cd1"),
+};
+
+/// OPAQUE-3DH with ristretto255 and SHA-512.
+enum Ristretto255Sha512 {}
+
+impl OpaqueCipherSuite for Ristretto255Sha512 {
+	type Oprf = Ristretto255;
+	type Ksf = IdentityKsf;
+}
+
+/// OPAQUE-3DH with P-256 and SHA-256.
+enum P256Sha256 {}
+
+impl OpaqueCipherSuite for P256Sha256 {
+	type Oprf = NistP256;
+	type Ksf = IdentityKsf;
+}
+
+/// RFC 9807 test vector without identities.
+struct Vector {
+	/// `oprf_seed`.
+	oprf_seed: &'static [u8],
+	/// `envelope_nonce`.
+	envelope_nonce: &'static [u8],
+	/// `masking_nonce`.
+	masking_nonce: &'static [u8],
+	/// `server_private_key`.
+	server_private_key: &'static [u8],
+	/// `server_public_key`.
+	server_public_key: &'static [u8],
+	/// `server_nonce`.
+	server_nonce: &'static [u8],
+	/// `client_nonce`.
+	client_nonce: &'static [u8],
+	/// `client_keyshare_seed`.
+	client_keyshare_seed: &'static [u8],
+	/// `server_keyshare_seed`.
+	server_keyshare_seed: &'static [u8],
+	/// `blind_registration`.
+	blind_registration: &'static [u8],
+	/// `blind_login`.
+	blind_login: &'static [u8],
+	/// `registration_request`.
+	registration_request: &'static [u8],
+	/// `registration_response`.
+	registration_response: &'static [u8],
+	/// `registration_upload`.
+	registration_upload: &'static [u8],
+	/// `KE1`.
+	ke1: &'static [u8],
+	/// `KE2`.
+	ke2: &'static [u8],
+	/// `KE3`.
+	ke3: &'static [u8],
+	/// `export_key`.
+	export_key: &'static [u8],
+	/// `session_key`.
+	session_key: &'static [u8],
+}
+
+/// RNG returning the provided bytes. Panics when they are exhausted.
+struct MockRng<'bytes>(&'bytes [u8]);
+
+impl RngCore for MockRng<'_> {
+	fn next_u32(&mut self) -> u32 {
+		let mut bytes = [0; size_of::<u32>()];
+		self.fill_bytes(&mut bytes);
+		u32::from_be_bytes(bytes)
+	}
+
+	fn next_u64(&mut self) -> u64 {
+		let mut bytes = [0; size_of::<u64>()];
+		self.fill_bytes(&mut bytes);
+		u64::from_be_bytes(bytes)
+	}
+
+	fn fill_bytes(&mut self, dst: &mut [u8]) {
+		dst.copy_from_slice(&self.0[..dst.len()]);
+		self.0 = &self.0[dst.len()..];
+	}
+}
+
+impl CryptoRng for MockRng<'_> {}
+
+/// Tests the ristretto255 test vector.
+#[test]
+fn vector_ristretto255() {
+	vector::<Ristretto255Sha512>(&RISTRETTO255);
+}
+
+/// Tests the P-256 test vector.
+#[test]
+fn vector_p256() {
+	vector::<P256Sha256>(&P256);
+}
+
+/// Tests registration and login with identities and a `context`.
+#[test]
+fn basic_ristretto255() {
+	basic::<Ristretto255Sha512>();
+}
+
+/// Tests registration and login with identities and a `context`.
+#[test]
+fn basic_p256() {
+	basic::<P256Sha256>();
+}
+
+/// Tests that failed logins are detected.
+#[test]
+fn invalid_ristretto255() {
+	invalid::<Ristretto255Sha512>();
+}
+
+/// Tests that failed logins are detected.
+#[test]
+fn invalid_p256() {
+	invalid::<P256Sha256>();
+}
+
+/// Runs the provided [`Vector`] through all wire encodings.
+fn vector<Cs: OpaqueCipherSuite>(vector: &Vector) {
+	let server = OpaqueServer::<Cs>::from_key_pair(
+		KeyPair::from_repr(vector.server_private_key).unwrap(),
+		vector.oprf_seed.try_into().unwrap(),
+	);
+	assert_eq!(
+		server.key_pair().public_key().as_repr().as_slice(),
+		vector.server_public_key
+	);
+
+	// Registration.
+	let registration =
+		ClientRegistration::<Cs>::start(&mut MockRng(vector.blind_registration), PASSWORD).unwrap();
+	let registration_request = registration.registration_request.to_repr();
+	assert_eq!(registration_request, vector.registration_request);
+	let registration_request = RegistrationRequest::from_repr(&registration_request).unwrap();
+
+	let registration_response = server
+		.registration(&registration_request, CREDENTIAL_IDENTIFIER)
+		.unwrap()
+		.to_repr();
+	assert_eq!(registration_response, vector.registration_response);
+	let registration_response = RegistrationResponse::from_repr(&registration_response).unwrap();
+
+	let registration = registration
+		.client
+		.finish(
+			&mut MockRng(vector.envelope_nonce),
+			PASSWORD,
+			&registration_response,
+			Identifiers::default(),
+		)
+		.unwrap();
+	let registration_record = registration.registration_record.to_repr();
+	assert_eq!(registration_record, vector.registration_upload);
+	assert_eq!(registration.export_key.as_slice(), vector.export_key);
+	let registration_record = RegistrationRecord::from_repr(&registration_record).unwrap();
+
+	// Login.
+	let login = ClientLogin::<Cs>::start(
+		&mut MockRng(
+			&[
+				vector.blind_login,
+				vector.client_nonce,
+				vector.client_keyshare_seed,
+			]
+			.concat(),
+		),
+		PASSWORD,
+	)
+	.unwrap();
+	let ke1 = login.ke1.to_repr();
+	assert_eq!(ke1, vector.ke1);
+	let ke1 = Ke1::from_repr(&ke1).unwrap();
+
+	let server_login = server
+		.login(
+			&mut MockRng(
+				&[
+					vector.masking_nonce,
+					vector.server_nonce,
+					vector.server_keyshare_seed,
+				]
+				.concat(),
+			),
+			&registration_record,
+			CREDENTIAL_IDENTIFIER,
+			&ke1,
+			Identifiers::default(),
+			CONTEXT,
+		)
+		.unwrap();
+	let ke2 = server_login.ke2.to_repr();
+	assert_eq!(ke2, vector.ke2);
+	let ke2 = Ke2::from_repr(&ke2).unwrap();
+
+	let login = login
+		.client
+		.finish(PASSWORD, &ke2, Identifiers::default(), CONTEXT)
+		.unwrap();
+	let ke3 = login.ke3.to_repr();
+	assert_eq!(ke3, vector.ke3);
+	assert_eq!(login.session_key.as_slice(), vector.session_key);
+	assert_eq!(login.export_key.as_slice(), vector.export_key);
+	assert_eq!(&login.server_public_key, server.key_pair().public_key());
+	let ke3 = Ke3::from_repr(&ke3).unwrap();
+
+	let session_key = server_login.server.finish(&ke3).unwrap();
+	assert_eq!(session_key.as_slice(), vector.session_key);
+}
+
+/// Registers [`PASSWORD`] with the provided [`Identifiers`].
+fn register<Cs: OpaqueCipherSuite>(
+	server: &OpaqueServer<Cs>,
+	identifiers: Identifiers<'_>,
+) -> RegistrationRecord<Cs> {
+	let registration = ClientRegistration::<Cs>::start(&mut rand::rng(), PASSWORD).unwrap();
+	let registration_response = server
+		.registration(&registration.registration_request, CREDENTIAL_IDENTIFIER)
+		.unwrap();
+	let registration = registration
+		.client
+		.finish(
+			&mut rand::rng(),
+			PASSWORD,
+			&registration_response,
+			identifiers,
+		)
+		.unwrap();
+
+	registration.registration_record
+}
+
+/// Tests registration and login with identities and a `context`.
+fn basic<Cs: OpaqueCipherSuite>() {
+	let server = OpaqueServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let identifiers = Identifiers {
+		client: Some(b"alice".as_slice()),
+		server: Some(b"bob".as_slice()),
+	};
+	let registration_record = register(&server, identifiers);
+
+	let login = ClientLogin::<Cs>::start(&mut rand::rng(), PASSWORD).unwrap();
+	let server_login = server
+		.login(
+			&mut rand::rng(),
+			&registration_record,
+			CREDENTIAL_IDENTIFIER,
+			&login.ke1,
+			identifiers,
+			b"test context",
+		)
+		.unwrap();
+	let client_login = login
+		.client
+		.finish(PASSWORD, &server_login.ke2, identifiers, b"test context")
+		.unwrap();
+	let session_key = server_login.server.finish(&client_login.ke3).unwrap();
+
+	assert_eq!(session_key, client_login.session_key);
+	assert_eq!(
+		&client_login.server_public_key,
+		server.key_pair().public_key()
+	);
+}
+
+/// Tests that failed logins are detected.
+fn invalid<Cs: OpaqueCipherSuite>() {
+	let server = OpaqueServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let registration_record = register(&server, Identifiers::default());
+
+	let login =
+		|password: &[u8], registration_record: &RegistrationRecord<Cs>, server_context: &[u8]| {
+			let login = ClientLogin::<Cs>::start(&mut rand::rng(), password).unwrap();
+			let server_login = server
+				.login(
+					&mut rand::rng(),
+					registration_record,
+					CREDENTIAL_IDENTIFIER,
+					&login.ke1,
+					Identifiers::default(),
+					server_context,
+				)
+				.unwrap();
+			let result =
+				login
+					.client
+					.finish(password, &server_login.ke2, Identifiers::default(), CONTEXT);
+
+			(result, server_login.server)
+		};
+
+	// Wrong password.
+	let (result, _) = login(b"wrong password", &registration_record, CONTEXT);
+	assert_eq!(result.unwrap_err(), Error::Envelope);
+
+	// Unknown credential identifier.
+	let fake = RegistrationRecord::fake(&mut rand::rng()).unwrap();
+	let (result, _) = login(PASSWORD, &fake, CONTEXT);
+	assert_eq!(result.unwrap_err(), Error::Envelope);
+
+	// Mismatched `context`.
+	let (result, _) = login(PASSWORD, &registration_record, b"wrong context");
+	assert_eq!(result.unwrap_err(), Error::Authentication);
+
+	// Invalid client MAC.
+	let (result, server_login) = login(PASSWORD, &registration_record, CONTEXT);
+	let mut ke3 = result.unwrap().ke3.to_repr();
+	ke3[0] ^= 1;
+	let ke3 = Ke3::from_repr(&ke3).unwrap();
+	assert_eq!(server_login.finish(&ke3), Err(Error::Authentication));
+
+	// Invalid lengths.
+	let repr = registration_record.to_repr();
+	assert_eq!(
+		RegistrationRecord::<Cs>::from_repr(&repr[1..]),
+		Err(Error::FromRepr)
+	);
+	assert_eq!(Ke3::<Cs>::from_repr(&[0; 3]), Err(Error::FromRepr));
+}
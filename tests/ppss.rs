@@ -0,0 +1,180 @@
+//! Tests [`oprf::ppss`] related functionality.
+
+#![cfg(test)]
+#![cfg(feature = "alloc")]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use oprf::cipher_suite::CipherSuite;
+use oprf::ppss::{self, PpssClient, PpssRecord};
+use oprf::threshold::{EvaluationShare, ThresholdServer};
+use oprf::{BlindedElement, Error};
+use oprf_test::test_ciphersuites;
+
+/// Password to use.
+const PASSWORD: &[u8] = b"password";
+
+test_ciphersuites!(basic);
+
+/// Tests that any `threshold` servers recover the secret.
+fn basic<Cs: CipherSuite>() {
+	let setup = ppss::setup::<Cs, _>(&mut rand::rng(), PASSWORD, 3, 5).unwrap();
+	let servers: Vec<_> = setup
+		.key_shares
+		.iter()
+		.cloned()
+		.map(ThresholdServer::<Cs>::from_key_share)
+		.collect();
+
+	for subset in [[0, 1, 2].as_slice(), &[1, 3, 4], &[0, 1, 2, 3, 4]] {
+		let blind_result = PpssClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+		let shares = evaluate(&servers, subset, &blind_result.blinded_element);
+		let result = blind_result
+			.client
+			.finalize(&setup.record, PASSWORD, &shares)
+			.unwrap();
+
+		assert_eq!(result.secret, setup.secret);
+		assert!(result.invalid_shares.is_empty());
+	}
+
+	// Check de/serialization.
+	assert_eq!(
+		PpssRecord::<Cs>::from_repr(&setup.record.to_repr()).unwrap(),
+		setup.record
+	);
+}
+
+test_ciphersuites!(malicious);
+
+/// Tests that malicious servers are skipped and distinguished from a wrong
+/// password.
+fn malicious<Cs: CipherSuite>() {
+	let setup = ppss::setup::<Cs, _>(&mut rand::rng(), PASSWORD, 2, 3).unwrap();
+	let other_setup = ppss::setup::<Cs, _>(&mut rand::rng(), PASSWORD, 2, 3).unwrap();
+	let servers: Vec<_> = setup
+		.key_shares
+		.iter()
+		.cloned()
+		.map(ThresholdServer::<Cs>::from_key_share)
+		.collect();
+	let malicious = ThresholdServer::<Cs>::from_key_share(other_setup.key_shares[0].clone());
+
+	// Malicious server is skipped.
+	let blind_result = PpssClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+	let mut shares = evaluate(&servers, &[1, 2], &blind_result.blinded_element);
+	shares.push(
+		malicious
+			.verifiable_blind_evaluate(&mut rand::rng(), &blind_result.blinded_element)
+			.unwrap(),
+	);
+	let result = blind_result
+		.client
+		.finalize(&setup.record, PASSWORD, &shares)
+		.unwrap();
+
+	assert_eq!(result.secret, setup.secret);
+	assert_eq!(result.invalid_shares, [malicious.key_share().index()]);
+
+	// Wrong password is still reported as such when a malicious server is skipped.
+	let wrong_blind_result = PpssClient::<Cs>::blind(&mut rand::rng(), b"wrong").unwrap();
+	let mut wrong_shares = evaluate(&servers, &[1, 2], &wrong_blind_result.blinded_element);
+	wrong_shares.push(
+		malicious
+			.verifiable_blind_evaluate(&mut rand::rng(), &wrong_blind_result.blinded_element)
+			.unwrap(),
+	);
+	let result = wrong_blind_result
+		.client
+		.finalize(&setup.record, b"wrong", &wrong_shares);
+	assert_eq!(result.unwrap_err(), Error::Password);
+
+	// Failure on not enough valid shares.
+	shares.remove(0);
+	let result = blind_result
+		.client
+		.finalize(&setup.record, PASSWORD, &shares);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on missing proof.
+	let shares = [
+		servers[0].blind_evaluate(&blind_result.blinded_element),
+		servers[1].blind_evaluate(&blind_result.blinded_element),
+	];
+	let result = blind_result
+		.client
+		.finalize(&setup.record, PASSWORD, &shares);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests that invalid parameters and a wrong password are rejected.
+fn invalid<Cs: CipherSuite>() {
+	let setup = ppss::setup::<Cs, _>(&mut rand::rng(), PASSWORD, 2, 3).unwrap();
+	let servers: Vec<_> = setup
+		.key_shares
+		.iter()
+		.cloned()
+		.map(ThresholdServer::<Cs>::from_key_share)
+		.collect();
+
+	// Failure on wrong password.
+	let blind_result = PpssClient::<Cs>::blind(&mut rand::rng(), b"wrong").unwrap();
+	let shares = evaluate(&servers, &[0, 1, 2], &blind_result.blinded_element);
+	let result = blind_result
+		.client
+		.finalize(&setup.record, b"wrong", &shares);
+	assert_eq!(result.unwrap_err(), Error::Password);
+
+	// Failure on record of a different setup.
+	let other_setup = ppss::setup::<Cs, _>(&mut rand::rng(), PASSWORD, 2, 3).unwrap();
+	let blind_result = PpssClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+	let shares = evaluate(&servers, &[0, 1], &blind_result.blinded_element);
+	let result = blind_result
+		.client
+		.finalize(&other_setup.record, PASSWORD, &shares);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on not enough shares.
+	let result = blind_result
+		.client
+		.finalize(&setup.record, PASSWORD, &shares[..1]);
+	assert_eq!(result.unwrap_err(), Error::Threshold);
+
+	// Failure on invalid threshold.
+	let result = ppss::setup::<Cs, _>(&mut rand::rng(), PASSWORD, 0, 3);
+	assert_eq!(result.unwrap_err(), Error::Threshold);
+	let result = ppss::setup::<Cs, _>(&mut rand::rng(), PASSWORD, 4, 3);
+	assert_eq!(result.unwrap_err(), Error::Threshold);
+
+	// Failure on not enough bytes.
+	let result = PpssRecord::<Cs>::from_repr(&[]);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+
+	// Failure on threshold larger than the number of servers.
+	let mut repr = setup.record.to_repr();
+	repr[..2].copy_from_slice(&4_u16.to_be_bytes());
+	let result = PpssRecord::<Cs>::from_repr(&repr);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+}
+
+/// Evaluates the [`BlindedElement`] with the servers at the provided
+/// `indices`.
+fn evaluate<Cs: CipherSuite>(
+	servers: &[ThresholdServer<Cs>],
+	indices: &[usize],
+	blinded_element: &BlindedElement<Cs>,
+) -> Vec<EvaluationShare<Cs>> {
+	indices
+		.iter()
+		.map(|&index| {
+			servers[index]
+				.verifiable_blind_evaluate(&mut rand::rng(), blinded_element)
+				.unwrap()
+		})
+		.collect()
+}
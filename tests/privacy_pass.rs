@@ -0,0 +1,263 @@
+//! Tests [`oprf::privacy_pass`] related functionality.
+
+#![cfg(test)]
+#![cfg(all(feature = "alloc", feature = "privacy-pass"))]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use oprf::Error;
+use oprf::key::KeyPair;
+use oprf::privacy_pass::{
+	BatchTokenRequest, BatchTokenResponse, MemoryNonceStore, PrivacyPassBatchClient,
+	PrivacyPassClient, PrivacyPassIssuer, TOKEN_TYPE, Token, TokenRequest, TokenResponse,
+};
+use sha2::{Digest, Sha256};
+
+/// Serialized `TokenChallenge` used in tests.
+const CHALLENGE: &[u8] = b"test challenge";
+
+/// Creates a new [`PrivacyPassIssuer`].
+fn issuer() -> PrivacyPassIssuer {
+	PrivacyPassIssuer::from_key_pair(KeyPair::generate(&mut rand::rng()).unwrap())
+}
+
+/// Runs the issuance protocol through its wire encodings.
+fn issue(issuer: &PrivacyPassIssuer) -> Token {
+	let request =
+		PrivacyPassClient::request(&mut rand::rng(), issuer.public_key(), CHALLENGE).unwrap();
+
+	let token_request = TokenRequest::from_repr(&request.token_request.to_repr()).unwrap();
+	assert_eq!(token_request, request.token_request);
+	assert_eq!(
+		token_request.truncated_token_key_id(),
+		issuer.token_key_id()[31]
+	);
+
+	let token_response = issuer.issue(&mut rand::rng(), &token_request).unwrap();
+	let token_response = TokenResponse::from_repr(&token_response.to_repr()).unwrap();
+
+	let token = request.client.finalize(&token_response).unwrap();
+	let repr = token.to_repr();
+	assert_eq!(repr[..2], TOKEN_TYPE.to_be_bytes());
+	assert_eq!(Token::from_repr(&repr).unwrap(), token);
+
+	token
+}
+
+/// Tests issuance and redemption.
+#[test]
+fn basic() {
+	let issuer = issuer();
+	let mut store = MemoryNonceStore::new();
+
+	let token = issue(&issuer);
+	assert_eq!(token.token_key_id(), issuer.token_key_id());
+	issuer.redeem(&token, CHALLENGE, &mut store).unwrap();
+
+	let token = issue(&issuer);
+	issuer.redeem(&token, CHALLENGE, &mut store).unwrap();
+}
+
+/// Tests the wire encodings against the layouts of RFC 9578 § 5.
+#[test]
+fn encoding() {
+	let issuer = issuer();
+	let request =
+		PrivacyPassClient::request(&mut rand::rng(), issuer.public_key(), CHALLENGE).unwrap();
+
+	// `token_key_id` is the SHA-256 digest of the serialized public key.
+	let token_key_id: [u8; 32] = Sha256::digest(issuer.public_key().as_repr()).into();
+	assert_eq!(issuer.token_key_id(), &token_key_id);
+
+	// `token_type` || `truncated_token_key_id` || `blinded_msg`.
+	let repr = request.token_request.to_repr();
+	assert_eq!(repr[..2], 0x0001_u16.to_be_bytes());
+	assert_eq!(repr[2], token_key_id[31]);
+	assert_eq!(
+		&repr[3..],
+		request.token_request.blinded_element().as_repr().as_slice()
+	);
+
+	// `evaluate_msg` || `evaluate_proof`, with a compressed P-384 element.
+	let token_response = issuer
+		.issue(&mut rand::rng(), &request.token_request)
+		.unwrap();
+	let repr = token_response.to_repr();
+	let (evaluate_msg, evaluate_proof) = repr.split_at(49);
+	assert_eq!(
+		evaluate_msg,
+		token_response.evaluation_element().as_repr().as_slice()
+	);
+	assert_eq!(evaluate_proof, token_response.proof().to_repr().as_slice());
+
+	// `token_type` || `nonce` || `challenge_digest` || `token_key_id` ||
+	// `authenticator`.
+	let token = request.client.finalize(&token_response).unwrap();
+	let repr = token.to_repr();
+	assert_eq!(repr[..2], 0x0001_u16.to_be_bytes());
+	assert_eq!(repr[2..34], *token.nonce());
+	assert_eq!(&repr[34..66], Sha256::digest(CHALLENGE).as_slice());
+	assert_eq!(repr[66..98], token_key_id);
+	assert_eq!(&repr[98..], token.authenticator().as_slice());
+}
+
+/// Tests that a [`Token`] can't be redeemed twice.
+#[test]
+fn double_spend() {
+	let issuer = issuer();
+	let mut store = MemoryNonceStore::new();
+
+	let token = issue(&issuer);
+	issuer.redeem(&token, CHALLENGE, &mut store).unwrap();
+
+	let result = issuer.redeem(&token, CHALLENGE, &mut store);
+	assert_eq!(result, Err(Error::DoubleSpend));
+}
+
+/// Tests that invalid [`Token`]s are rejected.
+#[test]
+fn invalid() {
+	let issuer = issuer();
+	let mut store = MemoryNonceStore::new();
+	let token = issue(&issuer);
+
+	// Wrong challenge.
+	let result = issuer.redeem(&token, b"wrong challenge", &mut store);
+	assert_eq!(result, Err(Error::Token));
+
+	// Wrong issuer.
+	let result = self::issuer().redeem(&token, CHALLENGE, &mut store);
+	assert_eq!(result, Err(Error::Token));
+
+	// Wrong authenticator.
+	let mut repr = token.to_repr();
+	*repr.last_mut().unwrap() ^= 1;
+	let forged = Token::from_repr(&repr).unwrap();
+	let result = issuer.redeem(&forged, CHALLENGE, &mut store);
+	assert_eq!(result, Err(Error::Token));
+
+	// Rejected tokens don't spend the nonce.
+	issuer.redeem(&token, CHALLENGE, &mut store).unwrap();
+}
+
+/// Tests that [`TokenRequest`]s for a different issuer are rejected.
+#[test]
+fn wrong_key() {
+	let issuer = issuer();
+	let mut other = self::issuer();
+
+	while other.token_key_id()[31] == issuer.token_key_id()[31] {
+		other = self::issuer();
+	}
+
+	let request =
+		PrivacyPassClient::request(&mut rand::rng(), other.public_key(), CHALLENGE).unwrap();
+	let result = issuer.issue(&mut rand::rng(), &request.token_request);
+	assert_eq!(result.unwrap_err(), Error::Token);
+}
+
+/// Tests that unknown token types are rejected.
+#[test]
+fn token_type() {
+	let token = issue(&issuer());
+	let mut repr = token.to_repr();
+	repr[..2].copy_from_slice(&0x0002_u16.to_be_bytes());
+
+	assert_eq!(Token::from_repr(&repr), Err(Error::FromRepr));
+}
+
+/// Tests batched issuance and redemption.
+#[test]
+fn batch() {
+	let issuer = issuer();
+	let mut store = MemoryNonceStore::new();
+
+	let request =
+		PrivacyPassBatchClient::request(&mut rand::rng(), issuer.public_key(), CHALLENGE, 32)
+			.unwrap();
+
+	let repr = request.batch_token_request.to_repr().unwrap();
+	assert_eq!(repr[..2], TOKEN_TYPE.to_be_bytes());
+	assert_eq!(repr[3..5], 32_u16.to_be_bytes());
+	let batch_token_request = BatchTokenRequest::from_repr(&repr).unwrap();
+	assert_eq!(batch_token_request, request.batch_token_request);
+	assert_eq!(batch_token_request.blinded_elements().len(), 32);
+
+	let batch_token_response = issuer
+		.batch_issue(&mut rand::rng(), &batch_token_request)
+		.unwrap();
+	let repr = batch_token_response.to_repr().unwrap();
+	assert_eq!(repr[..2], 32_u16.to_be_bytes());
+	let batch_token_response = BatchTokenResponse::from_repr(&repr).unwrap();
+
+	let tokens = request.client.finalize(&batch_token_response).unwrap();
+	assert_eq!(tokens.len(), 32);
+
+	for token in &tokens {
+		issuer.redeem(token, CHALLENGE, &mut store).unwrap();
+	}
+
+	let result = issuer.redeem(&tokens[0], CHALLENGE, &mut store);
+	assert_eq!(result, Err(Error::DoubleSpend));
+}
+
+/// Tests that invalid batches are rejected.
+#[test]
+fn batch_invalid() {
+	let issuer = issuer();
+
+	// Empty batch.
+	let result =
+		PrivacyPassBatchClient::request(&mut rand::rng(), issuer.public_key(), CHALLENGE, 0);
+	assert_eq!(result.unwrap_err(), Error::Batch);
+
+	// Mismatched number of `EvaluationElement`s.
+	let request =
+		PrivacyPassBatchClient::request(&mut rand::rng(), issuer.public_key(), CHALLENGE, 2)
+			.unwrap();
+	let other =
+		PrivacyPassBatchClient::request(&mut rand::rng(), issuer.public_key(), CHALLENGE, 1)
+			.unwrap();
+	let batch_token_response = issuer
+		.batch_issue(&mut rand::rng(), &other.batch_token_request)
+		.unwrap();
+	let result = request.client.finalize(&batch_token_response);
+	assert_eq!(result, Err(Error::Batch));
+
+	// Invalid `Proof`.
+	let batch_token_response = issuer
+		.batch_issue(&mut rand::rng(), &request.batch_token_request)
+		.unwrap();
+	let mut repr = batch_token_response.to_repr().unwrap();
+	*repr.last_mut().unwrap() ^= 1;
+	let batch_token_response = BatchTokenResponse::from_repr(&repr).unwrap();
+	let result = request.client.finalize(&batch_token_response);
+	assert_eq!(result, Err(Error::Proof));
+
+	// Invalid count prefix.
+	let mut repr = request.batch_token_request.to_repr().unwrap();
+	repr[3..5].copy_from_slice(&3_u16.to_be_bytes());
+	assert_eq!(BatchTokenRequest::from_repr(&repr), Err(Error::FromRepr));
+	repr[3..5].copy_from_slice(&0_u16.to_be_bytes());
+	assert_eq!(BatchTokenRequest::from_repr(&repr), Err(Error::FromRepr));
+}
+
+/// Tests that [`BatchTokenRequest`]s for a different issuer are rejected.
+#[test]
+fn batch_wrong_key() {
+	let issuer = issuer();
+	let mut other = self::issuer();
+
+	while other.token_key_id()[31] == issuer.token_key_id()[31] {
+		other = self::issuer();
+	}
+
+	let request =
+		PrivacyPassBatchClient::request(&mut rand::rng(), other.public_key(), CHALLENGE, 2)
+			.unwrap();
+	let result = issuer.batch_issue(&mut rand::rng(), &request.batch_token_request);
+	assert_eq!(result.unwrap_err(), Error::Token);
+}
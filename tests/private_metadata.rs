@@ -0,0 +1,151 @@
+//! Tests [`oprf::private_metadata`] related functionality.
+
+#![cfg(test)]
+#![expect(clippy::cargo_common_metadata, reason = "tests")]
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::private_metadata::{PmbClient, PmbProof, PmbPublicKey, PmbServer, PmbToken};
+use oprf_test::test_ciphersuites;
+
+test_ciphersuites!(basic);
+
+/// Tests that the private metadata bit is recovered on redemption.
+fn basic<Cs: CipherSuite>() {
+	let server = PmbServer::<Cs>::new(&mut rand::rng()).unwrap();
+
+	for bit in [false, true] {
+		let blind_result = PmbClient::<Cs>::blind(&mut rand::rng()).unwrap();
+		let evaluate_result = server
+			.blind_evaluate(&mut rand::rng(), &blind_result.blinded_element, bit)
+			.unwrap();
+		let token = blind_result
+			.client
+			.finalize(
+				server.public_key(),
+				&evaluate_result.nonce,
+				&evaluate_result.evaluation_element,
+				&evaluate_result.proof,
+			)
+			.unwrap();
+
+		assert_eq!(server.redeem(&token).unwrap(), bit);
+
+		// Check de/serialization.
+		#[cfg(feature = "alloc")]
+		{
+			assert_eq!(PmbToken::<Cs>::from_repr(&token.to_repr()).unwrap(), token);
+			assert_eq!(
+				PmbProof::<Cs>::from_repr(&evaluate_result.proof.to_repr()).unwrap(),
+				evaluate_result.proof
+			);
+			assert_eq!(
+				&PmbPublicKey::<Cs>::from_repr(&server.public_key().to_repr()).unwrap(),
+				server.public_key()
+			);
+		}
+	}
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests that invalid [`PmbProof`]s and [`PmbToken`]s are rejected.
+fn invalid<Cs: CipherSuite>() {
+	let server = PmbServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let other_server = PmbServer::<Cs>::new(&mut rand::rng()).unwrap();
+
+	let blind_result = PmbClient::<Cs>::blind(&mut rand::rng()).unwrap();
+	let evaluate_result = server
+		.blind_evaluate(&mut rand::rng(), &blind_result.blinded_element, true)
+		.unwrap();
+
+	// Failure on wrong public key.
+	let result = blind_result.client.finalize(
+		other_server.public_key(),
+		&evaluate_result.nonce,
+		&evaluate_result.evaluation_element,
+		&evaluate_result.proof,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on wrong nonce.
+	let result = blind_result.client.finalize(
+		server.public_key(),
+		&[0; 32],
+		&evaluate_result.evaluation_element,
+		&evaluate_result.proof,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on proof by a different server.
+	let other_result = other_server
+		.blind_evaluate(&mut rand::rng(), &blind_result.blinded_element, true)
+		.unwrap();
+	let result = blind_result.client.finalize(
+		server.public_key(),
+		&other_result.nonce,
+		&other_result.evaluation_element,
+		&other_result.proof,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on redemption with a different server.
+	let token = blind_result
+		.client
+		.finalize(
+			server.public_key(),
+			&evaluate_result.nonce,
+			&evaluate_result.evaluation_element,
+			&evaluate_result.proof,
+		)
+		.unwrap();
+	let result = other_server.redeem(&token);
+	assert_eq!(result.unwrap_err(), Error::Token);
+
+	// Failure on not enough bytes.
+	let result = PmbProof::<Cs>::from_repr(&[]);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+	let result = PmbToken::<Cs>::from_repr(&[]);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+	let result = PmbPublicKey::<Cs>::from_repr(&[]);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+}
+
+test_ciphersuites!(hidden);
+
+/// Tests that a client holding a [`PmbToken`] for each bit can't tell them
+/// apart: both [`PmbProof`]s verify against the same [`PmbPublicKey`] and
+/// only the [`PmbServer`] recovers the bits.
+fn hidden<Cs: CipherSuite>() {
+	let server = PmbServer::<Cs>::new(&mut rand::rng()).unwrap();
+
+	let [(token_0, proof_0), (token_1, proof_1)] = [false, true].map(|bit| {
+		let blind_result = PmbClient::<Cs>::blind(&mut rand::rng()).unwrap();
+		let evaluate_result = server
+			.blind_evaluate(&mut rand::rng(), &blind_result.blinded_element, bit)
+			.unwrap();
+		let token = blind_result
+			.client
+			.finalize(
+				server.public_key(),
+				&evaluate_result.nonce,
+				&evaluate_result.evaluation_element,
+				&evaluate_result.proof,
+			)
+			.unwrap();
+
+		(token, evaluate_result.proof)
+	});
+
+	assert_ne!(token_0, token_1);
+	assert_ne!(proof_0, proof_1);
+
+	#[cfg(feature = "alloc")]
+	{
+		assert_eq!(token_0.to_repr().len(), token_1.to_repr().len());
+		assert_eq!(proof_0.to_repr().len(), proof_1.to_repr().len());
+	}
+
+	assert!(!server.redeem(&token_0).unwrap());
+	assert!(server.redeem(&token_1).unwrap());
+}
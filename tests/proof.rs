@@ -1,4 +1,4 @@
-//! Tests for invalid [`Proof`](oprf::Proof)s.
+//! Tests for [`Proof`](oprf::Proof) verification.
 
 #![cfg(test)]
 #![expect(clippy::cargo_common_metadata, reason = "tests")]
@@ -8,7 +8,7 @@ use std::iter;
 
 use oprf::Error;
 use oprf::cipher_suite::CipherSuite;
-use oprf::common::Mode;
+use oprf::common::{BlindedElement, EvaluationElement, Mode};
 use oprf_test::{CommonClient, CommonServer, INFO, INPUT, test_ciphersuites};
 
 test_ciphersuites!(basic, Voprf);
@@ -182,3 +182,94 @@ fn batch_alloc<Cs: CipherSuite>(mode: Mode) {
 		assert_eq!(result.unwrap_err(), Error::Proof);
 	}
 }
+
+test_ciphersuites!(verify, Voprf);
+test_ciphersuites!(verify, Poprf);
+
+/// Tests verifying [`Proof`]s without the clients blinds.
+fn verify<Cs: CipherSuite>(mode: Mode) {
+	let info = (mode == Mode::Poprf).then_some(INFO);
+
+	let client = CommonClient::<Cs>::blind(mode);
+	let server = CommonServer::<Cs>::blind_evaluate(&client);
+	let public_key = server.public_key().unwrap();
+	let proof = server.proof().unwrap();
+
+	proof
+		.verify(
+			public_key,
+			client.blinded_element(),
+			server.evaluation_element(),
+			info,
+		)
+		.unwrap();
+
+	// Failure on swapped elements.
+	let result = proof.verify(
+		public_key,
+		&BlindedElement::from_repr(server.evaluation_element().as_repr()).unwrap(),
+		&EvaluationElement::from_repr(client.blinded_element().as_repr()).unwrap(),
+		info,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on wrong info.
+	let wrong_info = if mode == Mode::Poprf {
+		None
+	} else {
+		Some(INFO)
+	};
+	let result = proof.verify(
+		public_key,
+		client.blinded_element(),
+		server.evaluation_element(),
+		wrong_info,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	let clients = CommonClient::<Cs>::batch::<2>(mode);
+	let server = CommonServer::<Cs>::batch::<2>(&clients);
+	let public_key = server.public_key().unwrap();
+	let proof = server.proof().unwrap();
+	let blinded_elements: &[_; 2] = clients.blinded_elements().try_into().unwrap();
+	let evaluation_elements: &[_; 2] = server.evaluation_elements().try_into().unwrap();
+
+	proof
+		.batch_verify(public_key, blinded_elements, evaluation_elements, info)
+		.unwrap();
+
+	// Failure on reordered elements.
+	let [evaluation_element_1, evaluation_element_2] = evaluation_elements.clone();
+	let result = proof.batch_verify(
+		public_key,
+		blinded_elements,
+		&[evaluation_element_2, evaluation_element_1],
+		info,
+	);
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// Failure on no items.
+	let result = proof.batch_verify::<0>(public_key, &[], &[], info);
+	assert_eq!(result.unwrap_err(), Error::Batch);
+
+	#[cfg(feature = "alloc")]
+	{
+		proof
+			.batch_alloc_verify(
+				public_key,
+				blinded_elements.iter(),
+				evaluation_elements.iter(),
+				info,
+			)
+			.unwrap();
+
+		// Failure on unequal items.
+		let result = proof.batch_alloc_verify(
+			public_key,
+			blinded_elements.iter(),
+			evaluation_elements.iter().take(1),
+			info,
+		);
+		assert_eq!(result.unwrap_err(), Error::Batch);
+	}
+}
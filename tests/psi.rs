@@ -0,0 +1,270 @@
+//! Tests [`oprf::psi`] related functionality.
+
+#![cfg(test)]
+#![cfg(feature = "alloc")]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::psi::cuckoo::{CuckooFilter, CuckooFilterDelta};
+use oprf::psi::{PsiCardinalityClient, PsiCardinalityServer, PsiClient, PsiServer, PsiSet};
+use oprf_test::test_ciphersuites;
+use rand::RngCore;
+
+/// Items of the server.
+const SERVER_ITEMS: &[&[u8]] = &[b"alice", b"bob", b"carol", b"dave", b"eve"];
+/// Items of the client.
+const CLIENT_ITEMS: &[&[u8]] = &[b"mallory", b"bob", b"trent", b"eve", b"peggy", b"alice"];
+
+test_ciphersuites!(intersection);
+
+/// Tests that the client learns exactly the intersection.
+fn intersection<Cs: CipherSuite>() {
+	let server = PsiServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let set = server.set(SERVER_ITEMS).unwrap();
+	assert_eq!(set.len(), SERVER_ITEMS.len());
+
+	// Publishing the set doesn't depend on the order of items.
+	let mut reversed = SERVER_ITEMS.to_vec();
+	reversed.reverse();
+	assert_eq!(server.set(&reversed).unwrap(), set);
+	let set = PsiSet::from_outputs(set.as_outputs().to_vec());
+
+	let blind_result = PsiClient::<Cs>::blind(&mut rand::rng(), CLIENT_ITEMS).unwrap();
+	let evaluation_elements = server.blind_evaluate(&blind_result.blinded_elements);
+	let intersection = blind_result
+		.client
+		.intersection(CLIENT_ITEMS, &evaluation_elements, &set)
+		.unwrap();
+
+	assert_eq!(intersection, [b"bob".as_slice(), b"eve", b"alice"]);
+
+	// A different server key yields an empty intersection.
+	let other = PsiServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let evaluation_elements = other.blind_evaluate(&blind_result.blinded_elements);
+	let intersection = blind_result
+		.client
+		.intersection(CLIENT_ITEMS, &evaluation_elements, &set)
+		.unwrap();
+
+	assert!(intersection.is_empty());
+}
+
+test_ciphersuites!(cardinality);
+
+/// Tests that the client learns the size of the intersection.
+fn cardinality<Cs: CipherSuite>() {
+	let server = PsiCardinalityServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let set = server.set(SERVER_ITEMS).unwrap();
+
+	let blind_result = PsiCardinalityClient::<Cs>::blind(&mut rand::rng(), CLIENT_ITEMS).unwrap();
+	let evaluation_elements = server
+		.blind_evaluate(&mut rand::rng(), &blind_result.blinded_elements)
+		.unwrap();
+	let cardinality = blind_result
+		.client
+		.cardinality(&evaluation_elements, &set)
+		.unwrap();
+
+	assert_eq!(cardinality, 3);
+}
+
+test_ciphersuites!(cardinality_unlinkable);
+
+/// Tests that evaluations of a [`PsiCardinalityServer`] can't be matched
+/// against the [`PsiSet`] of a [`PsiServer`] and vice versa.
+fn cardinality_unlinkable<Cs: CipherSuite>() {
+	let server = PsiServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let set = server.set(SERVER_ITEMS).unwrap();
+	let cardinality_server = PsiCardinalityServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let cardinality_set = cardinality_server.set(SERVER_ITEMS).unwrap();
+
+	// Single items aren't affected by the shuffle.
+	for &item in CLIENT_ITEMS {
+		let blind_result = PsiClient::<Cs>::blind(&mut rand::rng(), &[item]).unwrap();
+		let evaluation_elements = cardinality_server
+			.blind_evaluate(&mut rand::rng(), &blind_result.blinded_elements)
+			.unwrap();
+		let intersection = blind_result
+			.client
+			.intersection(&[item], &evaluation_elements, &set)
+			.unwrap();
+
+		assert!(intersection.is_empty());
+	}
+
+	let blind_result = PsiCardinalityClient::<Cs>::blind(&mut rand::rng(), CLIENT_ITEMS).unwrap();
+	let evaluation_elements = server.blind_evaluate(&blind_result.blinded_elements);
+	let cardinality = blind_result
+		.client
+		.cardinality(&evaluation_elements, &cardinality_set)
+		.unwrap();
+
+	assert_eq!(cardinality, 0);
+}
+
+test_ciphersuites!(batch);
+
+/// Tests that mismatched numbers of items are rejected.
+fn batch<Cs: CipherSuite>() {
+	let server = PsiServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let set = server.set(SERVER_ITEMS).unwrap();
+
+	let blind_result = PsiClient::<Cs>::blind(&mut rand::rng(), CLIENT_ITEMS).unwrap();
+	let evaluation_elements = server.blind_evaluate(&blind_result.blinded_elements);
+	let result = blind_result
+		.client
+		.intersection(CLIENT_ITEMS, &evaluation_elements[1..], &set);
+	assert_eq!(result, Err(Error::Batch));
+	let result = blind_result
+		.client
+		.intersection(&CLIENT_ITEMS[1..], &evaluation_elements, &set);
+	assert_eq!(result, Err(Error::Batch));
+
+	let server = PsiCardinalityServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let set = server.set(SERVER_ITEMS).unwrap();
+	let blind_result = PsiCardinalityClient::<Cs>::blind(&mut rand::rng(), CLIENT_ITEMS).unwrap();
+	let evaluation_elements = server
+		.blind_evaluate(&mut rand::rng(), &blind_result.blinded_elements)
+		.unwrap();
+	let result = blind_result
+		.client
+		.cardinality(&evaluation_elements[1..], &set);
+	assert_eq!(result, Err(Error::Batch));
+}
+
+test_ciphersuites!(filter);
+
+/// Tests that the client learns the intersection from a [`CuckooFilter`].
+fn filter<Cs: CipherSuite>() {
+	let server = PsiServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let filter = server.filter(SERVER_ITEMS, 4).unwrap();
+	assert_eq!(CuckooFilter::from_repr(&filter.to_repr()).unwrap(), filter);
+
+	let blind_result = PsiClient::<Cs>::blind(&mut rand::rng(), CLIENT_ITEMS).unwrap();
+	let evaluation_elements = server.blind_evaluate(&blind_result.blinded_elements);
+	let intersection = blind_result
+		.client
+		.filter_intersection(CLIENT_ITEMS, &evaluation_elements, &filter)
+		.unwrap();
+
+	assert_eq!(intersection, [b"bob".as_slice(), b"eve", b"alice"]);
+}
+
+test_ciphersuites!(filter_delta);
+
+/// Tests that [`CuckooFilterDelta`]s keep the client up to date.
+fn filter_delta<Cs: CipherSuite>() {
+	let server = PsiServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let mut filter = server.filter(SERVER_ITEMS, 4).unwrap();
+	let mut client_filter = CuckooFilter::from_repr(&filter.to_repr()).unwrap();
+	assert!(filter.delta().is_empty());
+
+	server
+		.filter_insert(&mut filter, &[b"mallory", b"trent"])
+		.unwrap();
+	server.filter_remove(&mut filter, &[b"bob"]).unwrap();
+
+	let delta = filter.delta();
+	assert!(!delta.is_empty());
+	assert!(filter.delta().is_empty());
+
+	let delta = CuckooFilterDelta::from_repr(&delta.to_repr()).unwrap();
+	client_filter.apply(&delta).unwrap();
+	assert_eq!(client_filter, filter);
+
+	let blind_result = PsiClient::<Cs>::blind(&mut rand::rng(), CLIENT_ITEMS).unwrap();
+	let evaluation_elements = server.blind_evaluate(&blind_result.blinded_elements);
+	let intersection = blind_result
+		.client
+		.filter_intersection(CLIENT_ITEMS, &evaluation_elements, &client_filter)
+		.unwrap();
+
+	assert_eq!(
+		intersection,
+		[b"mallory".as_slice(), b"trent", b"eve", b"alice"]
+	);
+}
+
+/// Tests the false-positive rate and capacity of a [`CuckooFilter`].
+#[test]
+fn filter_false_positives() {
+	let mut filter = CuckooFilter::new(10_000, 2).unwrap();
+	let mut output = [0; 32];
+
+	for _ in 0..10_000 {
+		rand::rng().fill_bytes(&mut output);
+		filter.insert(&output).unwrap();
+		assert!(filter.contains(&output));
+	}
+
+	// The expected number of false positives is about 12.
+	let false_positives = (0..100_000)
+		.filter(|_| {
+			rand::rng().fill_bytes(&mut output);
+			filter.contains(&output)
+		})
+		.count();
+	assert!(false_positives < 50);
+
+	// Filling the filter eventually fails without losing any outputs.
+	let mut filter = CuckooFilter::new(4, 1).unwrap();
+	let mut outputs = Vec::new();
+
+	loop {
+		rand::rng().fill_bytes(&mut output);
+
+		if filter.insert(&output) == Err(Error::Filter) {
+			break;
+		}
+
+		outputs.push(output);
+	}
+
+	assert!(outputs.len() <= filter.capacity());
+	assert!(outputs.iter().all(|output| filter.contains(output)));
+
+	for output in &outputs {
+		assert!(filter.remove(output));
+	}
+
+	assert_eq!(filter, CuckooFilter::new(4, 1).unwrap());
+}
+
+/// Tests invalid [`CuckooFilter`] parameters and encodings.
+#[test]
+fn filter_invalid() {
+	assert_eq!(CuckooFilter::new(1, 0), Err(Error::Filter));
+	assert_eq!(CuckooFilter::new(1, 9), Err(Error::Filter));
+	assert_eq!(CuckooFilter::new(usize::MAX, 4), Err(Error::Filter));
+
+	let mut filter = CuckooFilter::new(100, 2).unwrap();
+	let repr = filter.to_repr();
+	assert_eq!(CuckooFilter::from_repr(&[]), Err(Error::FromRepr));
+	assert_eq!(
+		CuckooFilter::from_repr(&repr[..repr.len() - 1]),
+		Err(Error::FromRepr)
+	);
+
+	let mut other = CuckooFilter::new(100, 3).unwrap();
+	other.insert(&[1; 32]).unwrap();
+	let delta = other.delta();
+	assert_eq!(filter.apply(&delta), Err(Error::Filter));
+
+	let mut other = CuckooFilter::new(10_000, 2).unwrap();
+	other.insert(&[u8::MAX; 32]).unwrap();
+	let delta = other.delta();
+	assert_eq!(filter.apply(&delta), Err(Error::Filter));
+	assert_eq!(filter, CuckooFilter::new(100, 2).unwrap());
+
+	let repr = delta.to_repr();
+	assert_eq!(CuckooFilterDelta::from_repr(&[]), Err(Error::FromRepr));
+	assert_eq!(
+		CuckooFilterDelta::from_repr(&repr[..repr.len() - 1]),
+		Err(Error::FromRepr)
+	);
+}
@@ -0,0 +1,140 @@
+//! Tests [`oprf::pythia`] related functionality.
+
+#![cfg(test)]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::pythia::{HardenedPassword, PythiaClient, PythiaServer, UpdateToken};
+use oprf_test::test_ciphersuites;
+
+/// Password of the user.
+const PASSWORD: &[u8] = b"password";
+/// Tenant of the user.
+const TENANT: &[u8] = b"tenant";
+
+test_ciphersuites!(basic);
+
+/// Tests that hardening is deterministic per tenant and verifiable.
+fn basic<Cs: CipherSuite>() {
+	let server = PythiaServer::<Cs>::new(&mut rand::rng()).unwrap();
+
+	let harden = |tenant: &[u8]| {
+		let blind_result = PythiaClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+		let result = server
+			.harden(&mut rand::rng(), tenant, &blind_result.blinded_element)
+			.unwrap();
+		blind_result
+			.client
+			.finalize(
+				server.public_key(),
+				tenant,
+				&result.evaluation_element,
+				&result.proof,
+			)
+			.unwrap()
+	};
+
+	let hardened = harden(TENANT);
+	assert_eq!(hardened, harden(TENANT));
+	assert_ne!(hardened, harden(b"other"));
+
+	let repr = HardenedPassword::<Cs>::from_repr(hardened.as_repr()).unwrap();
+	assert_eq!(repr, hardened);
+}
+
+test_ciphersuites!(ratchet);
+
+/// Tests that [`UpdateToken`]s re-key [`HardenedPassword`]s.
+fn ratchet<Cs: CipherSuite>() {
+	let mut server = PythiaServer::<Cs>::new(&mut rand::rng()).unwrap();
+
+	let harden = |server: &PythiaServer<Cs>| {
+		let blind_result = PythiaClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+		let result = server
+			.harden(&mut rand::rng(), TENANT, &blind_result.blinded_element)
+			.unwrap();
+		blind_result
+			.client
+			.finalize(
+				server.public_key(),
+				TENANT,
+				&result.evaluation_element,
+				&result.proof,
+			)
+			.unwrap()
+	};
+
+	let mut hardened = harden(&server);
+	let mut other = hardened.clone();
+	let old_public_key = server.public_key().clone();
+
+	let ratchet = server.ratchet(&mut rand::rng()).unwrap();
+	assert_eq!(ratchet.public_key(), server.public_key());
+	assert_ne!(server.public_key(), &old_public_key);
+
+	let update_token = ratchet.update_token(TENANT).unwrap();
+	let update_token = UpdateToken::from_repr(&update_token.to_repr()).unwrap();
+	let fresh = harden(&server);
+	assert_ne!(hardened, fresh);
+
+	hardened.update(&update_token);
+	assert_eq!(hardened, fresh);
+
+	// Tokens are tenant specific.
+	other.update(&ratchet.update_token(b"other").unwrap());
+	assert_ne!(other, fresh);
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests that [`Proof`](oprf::Proof)s for different tenants or keys are
+/// rejected.
+fn invalid<Cs: CipherSuite>() {
+	let server = PythiaServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let other = PythiaServer::<Cs>::new(&mut rand::rng()).unwrap();
+
+	let blind_result = PythiaClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+	let result = server
+		.harden(&mut rand::rng(), TENANT, &blind_result.blinded_element)
+		.unwrap();
+
+	assert_eq!(
+		blind_result.client.finalize(
+			server.public_key(),
+			b"other",
+			&result.evaluation_element,
+			&result.proof,
+		),
+		Err(Error::Proof)
+	);
+	assert_eq!(
+		blind_result.client.finalize(
+			other.public_key(),
+			TENANT,
+			&result.evaluation_element,
+			&result.proof,
+		),
+		Err(Error::Proof)
+	);
+
+	let token = server
+		.clone()
+		.ratchet(&mut rand::rng())
+		.unwrap()
+		.update_token(TENANT)
+		.unwrap()
+		.to_repr();
+	assert_eq!(
+		UpdateToken::<Cs>::from_repr(&token[1..]),
+		Err(Error::FromRepr)
+	);
+	assert_eq!(
+		UpdateToken::<Cs>::from_repr(&[0; 128][..token.len()]),
+		Err(Error::FromRepr)
+	);
+}
@@ -0,0 +1,188 @@
+//! Tests [`oprf::threshold::refresh`] related functionality.
+
+#![cfg(test)]
+#![cfg(feature = "alloc")]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use std::num::NonZeroU16;
+
+use oprf::cipher_suite::CipherSuite;
+use oprf::group::Group;
+use oprf::key::KeyPair;
+use oprf::threshold::dkg::{DkgComplaint, DkgShare};
+use oprf::threshold::refresh::{RefreshOutput, RefreshRound1};
+use oprf::threshold::{self, KeyShare, ThresholdServer};
+use oprf::voprf::{VoprfClient, VoprfServer};
+use oprf_test::{INPUT, test_ciphersuites};
+
+/// Runs the refresh in-process for all provided [`KeyShare`]s.
+///
+/// `tamper` can modify all [`DkgShare`]s before they are delivered and
+/// `justify` decides if accused dealers answer [`DkgComplaint`]s.
+fn run<G, T>(
+	threshold: u16,
+	key_shares: &[KeyShare<G>],
+	tamper: T,
+	justify: bool,
+) -> Vec<RefreshOutput<G>>
+where
+	G: Group,
+	T: Fn(DkgShare<G>) -> DkgShare<G>,
+{
+	let participants = u16::try_from(key_shares.len()).unwrap();
+	let rounds_1: Vec<_> = key_shares
+		.iter()
+		.map(|key_share| {
+			RefreshRound1::new(&mut rand::rng(), key_share.clone(), threshold, participants)
+				.unwrap()
+		})
+		.collect();
+
+	let commitments: Vec<_> = rounds_1
+		.iter()
+		.map(|round| round.commitment().clone())
+		.collect();
+	let shares: Vec<_> = rounds_1
+		.iter()
+		.flat_map(RefreshRound1::shares)
+		.map(tamper)
+		.collect();
+
+	let rounds_2: Vec<_> = rounds_1
+		.into_iter()
+		.zip(key_shares)
+		.map(|(round, key_share)| {
+			let shares: Vec<_> = shares
+				.iter()
+				.filter(|share| share.recipient() == key_share.index())
+				.cloned()
+				.collect();
+
+			round.receive(&commitments, &shares)
+		})
+		.collect();
+
+	let complaints: Vec<DkgComplaint> = rounds_2
+		.iter()
+		.flat_map(|round| round.complaints().iter().copied())
+		.collect();
+	let justifications: Vec<_> = if justify {
+		rounds_2
+			.iter()
+			.flat_map(|round| round.justify(&complaints))
+			.collect()
+	} else {
+		Vec::new()
+	};
+
+	rounds_2
+		.into_iter()
+		.map(|round| round.finish(&complaints, &justifications).unwrap())
+		.collect()
+}
+
+/// Asserts that the refreshed [`KeyShare`]s still evaluate under the same
+/// [`KeyPair`].
+fn assert_outputs<Cs: CipherSuite>(
+	key_pair: KeyPair<Cs::Group>,
+	old: &[KeyShare<Cs::Group>],
+	outputs: &[RefreshOutput<Cs::Group>],
+) {
+	let public_key_shares: Vec<_> = old
+		.iter()
+		.zip(outputs)
+		.map(|(old, output)| {
+			let public_key_share = output.key_share().public_key_share();
+
+			assert_ne!(output.key_share(), old);
+			assert_eq!(
+				outputs[0]
+					.public_key_share(&old.public_key_share())
+					.unwrap(),
+				public_key_share
+			);
+
+			public_key_share
+		})
+		.collect();
+
+	let blind_result = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	let expected = VoprfServer::<Cs>::from_key_pair(key_pair)
+		.evaluate(INPUT)
+		.unwrap();
+
+	let shares: Vec<_> = outputs[1..]
+		.iter()
+		.map(|output| {
+			ThresholdServer::<Cs>::from_key_share(output.key_share().clone())
+				.verifiable_blind_evaluate(&mut rand::rng(), &blind_result.blinded_element)
+				.unwrap()
+		})
+		.collect();
+	let output =
+		threshold::verifiable_finalize(&blind_result.client, 2, &public_key_shares, INPUT, &shares)
+			.unwrap();
+
+	assert_eq!(output, expected);
+
+	// Old and refreshed shares can't be combined.
+	let shares = [
+		ThresholdServer::<Cs>::from_key_share(old[0].clone())
+			.blind_evaluate(&blind_result.blinded_element),
+		ThresholdServer::<Cs>::from_key_share(outputs[1].key_share().clone())
+			.blind_evaluate(&blind_result.blinded_element),
+	];
+	let evaluation_element = threshold::combine(2, &shares).unwrap();
+	let output = blind_result
+		.client
+		.finalize(INPUT, &evaluation_element)
+		.unwrap();
+
+	assert_ne!(output, expected);
+}
+
+test_ciphersuites!(refresh);
+
+/// Tests that refreshed [`KeyShare`]s evaluate under the same [`KeyPair`].
+fn refresh<Cs: CipherSuite>() {
+	let key_pair = KeyPair::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let key_shares = threshold::split(&mut rand::rng(), key_pair.secret_key(), 2, 3).unwrap();
+
+	let outputs = run(2, &key_shares, |share| share, true);
+
+	assert!(outputs[0].disqualified().is_empty());
+	assert_eq!(outputs[0].commitments().len(), 1);
+	assert_outputs::<Cs>(key_pair, &key_shares, &outputs);
+}
+
+test_ciphersuites!(blame);
+
+/// Tests that a dealer with an invalid sharing of zero is disqualified.
+fn blame<Cs: CipherSuite>() {
+	let key_pair = KeyPair::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let key_shares = threshold::split(&mut rand::rng(), key_pair.secret_key(), 2, 3).unwrap();
+
+	let outputs = run(
+		2,
+		&key_shares,
+		|share: DkgShare<Cs::Group>| {
+			if share.dealer().get() == 2 && share.recipient().get() == 1 {
+				let mut repr = share.to_repr();
+				repr[4..].copy_from_slice(&<Cs::Group as Group>::scalar_to_repr(
+					&<Cs::Group as Group>::scalar_from_u64(1),
+				));
+				DkgShare::from_repr(&repr).unwrap()
+			} else {
+				share
+			}
+		},
+		false,
+	);
+
+	assert_eq!(outputs[0].disqualified(), [NonZeroU16::new(2).unwrap()]);
+	assert_outputs::<Cs>(key_pair, &key_shares, &outputs);
+}
@@ -0,0 +1,165 @@
+//! Tests [`oprf::relay`] related functionality.
+
+#![cfg(test)]
+#![expect(clippy::cargo_common_metadata, reason = "tests")]
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::common::{BatchBlindEvaluateResult, BlindedElement};
+use oprf::relay::{self, Relay, RelayBlindResult, RelayResponse};
+use oprf::voprf::{VoprfBlindResult, VoprfClient, VoprfServer};
+use oprf_test::{INPUT, test_ciphersuites};
+
+test_ciphersuites!(basic);
+
+/// Tests that relayed evaluation matches direct evaluation.
+fn basic<Cs: CipherSuite>() {
+	let server = VoprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let VoprfBlindResult {
+		client,
+		blinded_element,
+	} = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+
+	let RelayBlindResult {
+		relay,
+		blinded_element: relayed_blinded_element,
+	} = Relay::blind(&mut rand::rng(), &blinded_element).unwrap();
+	assert_ne!(relayed_blinded_element, blinded_element);
+
+	let result = server
+		.blind_evaluate(&mut rand::rng(), &relayed_blinded_element)
+		.unwrap();
+	let response = relay
+		.finalize(
+			&mut rand::rng(),
+			server.public_key(),
+			&result.evaluation_element,
+			&result.proof,
+		)
+		.unwrap();
+
+	let output = relay::finalize(&client, server.public_key(), INPUT, &response).unwrap();
+	assert_eq!(output, server.evaluate(INPUT).unwrap());
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests that invalid [`Proof`](oprf::Proof)s are rejected by the
+/// [`Relay`] and the client.
+fn invalid<Cs: CipherSuite>() {
+	let server = VoprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let other = VoprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let VoprfBlindResult {
+		client,
+		blinded_element,
+	} = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+
+	let RelayBlindResult {
+		relay,
+		blinded_element: relayed_blinded_element,
+	} = Relay::blind(&mut rand::rng(), &blinded_element).unwrap();
+	let result = server
+		.blind_evaluate(&mut rand::rng(), &relayed_blinded_element)
+		.unwrap();
+
+	assert_eq!(
+		relay
+			.finalize(
+				&mut rand::rng(),
+				other.public_key(),
+				&result.evaluation_element,
+				&result.proof,
+			)
+			.unwrap_err(),
+		Error::Proof
+	);
+
+	let response = relay
+		.finalize(
+			&mut rand::rng(),
+			server.public_key(),
+			&result.evaluation_element,
+			&result.proof,
+		)
+		.unwrap();
+	assert_eq!(
+		relay::finalize(&client, other.public_key(), INPUT, &response),
+		Err(Error::Proof)
+	);
+
+	// A relay that returns a wrong `EvaluationElement` is caught by the client.
+	let mut tampered = relay
+		.finalize(
+			&mut rand::rng(),
+			server.public_key(),
+			&result.evaluation_element,
+			&result.proof,
+		)
+		.unwrap();
+	tampered.evaluation_element = result.evaluation_element.clone();
+	assert_eq!(
+		relay::finalize(&client, server.public_key(), INPUT, &tampered),
+		Err(Error::Proof)
+	);
+
+	// A `RelayResponse` for a different client is rejected.
+	let VoprfBlindResult {
+		client: other_client,
+		..
+	} = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	assert_eq!(
+		relay::finalize(&other_client, server.public_key(), INPUT, &response),
+		Err(Error::Proof)
+	);
+}
+
+test_ciphersuites!(server_proof);
+
+/// Tests that a [VOPRF](oprf::voprf) [`Proof`](oprf::Proof) over the same
+/// elements isn't accepted as the [`Relay`]s [`Proof`](oprf::Proof).
+fn server_proof<Cs: CipherSuite>() {
+	let server = VoprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let relay_server = VoprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let VoprfBlindResult {
+		client,
+		blinded_element,
+	} = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+
+	// The relay re-blinds with the key of a `VoprfServer` and proves it with a
+	// VOPRF `Proof` instead of a relay `Proof`.
+	let evaluation_element = server
+		.blind_evaluate(&mut rand::rng(), &blinded_element)
+		.unwrap()
+		.evaluation_element;
+	let BatchBlindEvaluateResult {
+		evaluation_elements: [relayed_blinded_element, relayed_evaluation_element],
+		proof,
+	} = relay_server
+		.batch_blind_evaluate(
+			&mut rand::rng(),
+			&[
+				blinded_element,
+				BlindedElement::from_repr(evaluation_element.as_repr()).unwrap(),
+			],
+		)
+		.unwrap();
+	let relayed_blinded_element =
+		BlindedElement::from_repr(relayed_blinded_element.as_repr()).unwrap();
+	let result = server
+		.blind_evaluate(&mut rand::rng(), &relayed_blinded_element)
+		.unwrap();
+	assert_eq!(result.evaluation_element, relayed_evaluation_element);
+
+	let response = RelayResponse {
+		relayed_blinded_element,
+		relayed_evaluation_element,
+		relayed_proof: result.proof,
+		relay_key: relay_server.public_key().clone(),
+		evaluation_element,
+		proof,
+	};
+	assert_eq!(
+		relay::finalize(&client, server.public_key(), INPUT, &response),
+		Err(Error::Proof)
+	);
+}
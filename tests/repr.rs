@@ -5,7 +5,7 @@
 
 use oprf::Error;
 use oprf::cipher_suite::CipherSuite;
-use oprf::common::{BlindedElement, EvaluationElement, Proof};
+use oprf::common::{BlindedElement, EvaluationElement, Proof, UnblindedElement};
 use oprf::key::{KeyPair, PublicKey, SecretKey};
 use oprf_test::test_ciphersuites;
 
@@ -47,6 +47,25 @@ fn evaluation_element<Cs: CipherSuite>() {
 	EvaluationElement::<Cs>::from_repr(&oprf_test::element::<Cs>()).unwrap();
 }
 
+test_ciphersuites!(unblinded_element);
+
+fn unblinded_element<Cs: CipherSuite>() {
+	// Failure on non-reduced element.
+	let result = UnblindedElement::<Cs>::from_repr(&oprf_test::invalid_element::<Cs>());
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+
+	// Failure on identity element.
+	let result = UnblindedElement::<Cs>::from_repr(&oprf_test::identity_element::<Cs>());
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+
+	// Failure on not enough bytes.
+	let result = UnblindedElement::<Cs>::from_repr(&[]);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+
+	// Success.
+	UnblindedElement::<Cs>::from_repr(&oprf_test::element::<Cs>()).unwrap();
+}
+
 test_ciphersuites!(proof);
 
 fn proof<Cs: CipherSuite>() {
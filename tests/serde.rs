@@ -9,8 +9,8 @@ use std::iter;
 
 use hybrid_array::{Array, ArraySize};
 use oprf::cipher_suite::CipherSuite;
-use oprf::common::{BlindedElement, EvaluationElement, Mode, Proof};
-use oprf::key::{KeyPair, PublicKey, SecretKey};
+use oprf::common::{BlindedElement, EvaluationElement, Mode, Proof, UnblindedElement};
+use oprf::key::{KeyPair, PossessionProof, PublicKey, SecretKey};
 use oprf::oprf::{OprfClient, OprfServer};
 use oprf::poprf::{PoprfClient, PoprfServer};
 use oprf::voprf::{VoprfClient, VoprfServer};
@@ -26,10 +26,12 @@ fn common<Cs: CipherSuite<Group: ScalarRepr> + ScalarRepr>()
 where
 	BlindedElement<Cs>: for<'de> Deserialize<'de> + Serialize,
 	EvaluationElement<Cs>: for<'de> Deserialize<'de> + Serialize,
+	UnblindedElement<Cs>: for<'de> Deserialize<'de> + Serialize,
 	Proof<Cs>: for<'de> Deserialize<'de> + Serialize,
 	KeyPair<Cs::Group>: for<'de> Deserialize<'de> + Serialize,
 	SecretKey<Cs::Group>: for<'de> Deserialize<'de> + Serialize,
 	PublicKey<Cs::Group>: for<'de> Deserialize<'de> + Serialize,
+	PossessionProof<Cs>: for<'de> Deserialize<'de> + Serialize,
 {
 	let scalar1 = leak(oprf_test::scalar::<Cs>());
 	let scalar2 = leak(oprf_test::scalar::<Cs>());
@@ -56,6 +58,14 @@ where
 		[invalid_element, identity_element],
 	);
 
+	let unblinded_element = UnblindedElement::from_repr(element).unwrap();
+	newtype_struct(
+		&unblinded_element,
+		"UnblindedElement",
+		element,
+		[invalid_element, identity_element],
+	);
+
 	let proof_bytes = [scalar1, scalar2].concat();
 	let proof = Proof::<Cs>::from_repr(&proof_bytes).unwrap();
 	struct_2(
@@ -87,6 +97,18 @@ where
 		element,
 		[invalid_element, identity_element],
 	);
+
+	let possession_proof = PossessionProof::<Cs>::from_repr(&proof_bytes).unwrap();
+	struct_2(
+		&possession_proof,
+		"PossessionProof",
+		"c",
+		scalar1,
+		[invalid_scalar],
+		"s",
+		scalar2,
+		[invalid_scalar],
+	);
 }
 
 test_ciphersuites!(mode, Mode, [MockCs as mock]);
@@ -0,0 +1,141 @@
+//! Tests [`oprf::threshold`] related functionality.
+
+#![cfg(test)]
+#![cfg(feature = "alloc")]
+#![expect(
+	clippy::cargo_common_metadata,
+	clippy::indexing_slicing,
+	reason = "tests"
+)]
+
+use std::slice;
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::key::{KeyPair, SecretKey};
+use oprf::oprf::{OprfClient, OprfServer};
+use oprf::threshold::{self, EvaluationShare, KeyShare, ThresholdServer};
+use oprf::voprf::{VoprfClient, VoprfServer};
+use oprf_test::{INPUT, test_ciphersuites};
+
+test_ciphersuites!(oprf);
+
+/// Tests that any `threshold` shares produce the same output as a single
+/// [`OprfServer`].
+fn oprf<Cs: CipherSuite>() {
+	let secret_key = SecretKey::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let key_shares = threshold::split(&mut rand::rng(), &secret_key, 3, 5).unwrap();
+	let servers: Vec<_> = key_shares
+		.into_iter()
+		.map(ThresholdServer::<Cs>::from_key_share)
+		.collect();
+
+	let blind_result = OprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	let expected = OprfServer::<Cs>::from_key(secret_key).evaluate(INPUT).unwrap();
+
+	for subset in [[0, 1, 2].as_slice(), &[1, 3, 4], &[0, 1, 2, 3, 4]] {
+		let shares: Vec<_> = subset
+			.iter()
+			.map(|&index| servers[index].blind_evaluate(&blind_result.blinded_element))
+			.collect();
+		let evaluation_element = threshold::combine(3, &shares).unwrap();
+		let output = blind_result
+			.client
+			.finalize(INPUT, &evaluation_element)
+			.unwrap();
+
+		assert_eq!(output, expected);
+	}
+}
+
+test_ciphersuites!(voprf);
+
+/// Tests that verified shares produce the same output as a single
+/// [`VoprfServer`].
+fn voprf<Cs: CipherSuite>() {
+	let key_pair = KeyPair::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+	let key_shares = threshold::split(&mut rand::rng(), key_pair.secret_key(), 2, 3).unwrap();
+	let public_key_shares: Vec<_> = key_shares
+		.iter()
+		.map(KeyShare::public_key_share)
+		.collect();
+	let servers: Vec<_> = key_shares
+		.into_iter()
+		.map(ThresholdServer::<Cs>::from_key_share)
+		.collect();
+
+	let blind_result = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	let expected = VoprfServer::<Cs>::from_key_pair(key_pair)
+		.evaluate(INPUT)
+		.unwrap();
+
+	let shares: Vec<_> = servers[1..]
+		.iter()
+		.map(|server| {
+			server
+				.verifiable_blind_evaluate(&mut rand::rng(), &blind_result.blinded_element)
+				.unwrap()
+		})
+		.collect();
+	let output = threshold::verifiable_finalize(
+		&blind_result.client,
+		2,
+		&public_key_shares,
+		INPUT,
+		&shares,
+	)
+	.unwrap();
+
+	assert_eq!(output, expected);
+
+	// Shares without a proof are rejected.
+	let shares: Vec<_> = servers[1..]
+		.iter()
+		.map(|server| server.blind_evaluate(&blind_result.blinded_element))
+		.collect();
+	let result = threshold::verifiable_finalize(
+		&blind_result.client,
+		2,
+		&public_key_shares,
+		INPUT,
+		&shares,
+	);
+
+	assert_eq!(result.unwrap_err(), Error::Proof);
+
+	// A share verified against the wrong `PublicKeyShare` is rejected.
+	let share = &shares[0];
+	let result = share.verify(&public_key_shares[0], &blind_result.blinded_element);
+
+	assert_eq!(result.unwrap_err(), Error::Threshold);
+}
+
+test_ciphersuites!(insufficient);
+
+/// Tests failure on insufficient or duplicate shares.
+fn insufficient<Cs: CipherSuite>() {
+	let secret_key = SecretKey::<Cs::Group>::generate(&mut rand::rng()).unwrap();
+
+	assert_eq!(
+		threshold::split(&mut rand::rng(), &secret_key, 0, 1).unwrap_err(),
+		Error::Threshold
+	);
+	assert_eq!(
+		threshold::split(&mut rand::rng(), &secret_key, 3, 2).unwrap_err(),
+		Error::Threshold
+	);
+
+	let key_shares = threshold::split(&mut rand::rng(), &secret_key, 2, 2).unwrap();
+	let server = ThresholdServer::<Cs>::from_key_share(key_shares[0].clone());
+	let blind_result = OprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	let share: EvaluationShare<Cs> = server.blind_evaluate(&blind_result.blinded_element);
+
+	// Too few shares.
+	let result = threshold::combine(2, slice::from_ref(&share));
+	assert_eq!(result.unwrap_err(), Error::Threshold);
+
+	// Duplicate shares.
+	let duplicate = server.blind_evaluate(&blind_result.blinded_element);
+	let result = threshold::combine(2, &[share, duplicate]);
+	assert_eq!(result.unwrap_err(), Error::Threshold);
+}
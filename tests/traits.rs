@@ -12,14 +12,31 @@ use std::hash::Hash;
 use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::{error, io};
 
+#[cfg(feature = "alloc")]
+use oprf::anonymous::{AnonymousBlindEvaluateResult, AnonymousServer, OrProof};
+#[cfg(feature = "alloc")]
+use oprf::checkup::{
+	CheckupBuckets, CheckupClient, CheckupRequest, CheckupRequestResult, CheckupResponse,
+	CheckupServer,
+};
 use oprf::cipher_suite::{CipherSuite, Id};
 #[cfg(feature = "alloc")]
 use oprf::common::BatchAllocBlindEvaluateResult;
 use oprf::common::{
 	BatchBlindEvaluateResult, BlindEvaluateResult, BlindedElement, EvaluationElement, Mode, Proof,
+	UnblindedElement,
 };
+#[cfg(feature = "alloc")]
+use oprf::dupless::{DuplessBlindResult, DuplessClient, DuplessServer, MemoryRateLimiter};
 use oprf::group::ristretto255::Ristretto255;
-use oprf::key::{KeyPair, PublicKey, SecretKey};
+use oprf::key::{KeyPair, PossessionProof, PublicKey, SecretKey};
+#[cfg(feature = "opaque")]
+use oprf::opaque::{
+	ClientLogin, ClientLoginFinishResult, ClientLoginStartResult, ClientRegistration,
+	ClientRegistrationFinishResult, ClientRegistrationStartResult, Identifiers, IdentityKsf, Ke1,
+	Ke2, Ke3, OpaqueCipherSuite, OpaqueServer, RegistrationRecord, RegistrationRequest,
+	RegistrationResponse, ServerLogin, ServerLoginResult,
+};
 #[cfg(feature = "alloc")]
 use oprf::oprf::OprfBatchAllocBlindResult;
 use oprf::oprf::{OprfBatchBlindResult, OprfBlindResult, OprfClient, OprfServer};
@@ -27,6 +44,48 @@ use oprf::oprf::{OprfBatchBlindResult, OprfBlindResult, OprfClient, OprfServer};
 use oprf::poprf::PoprfBatchAllocBlindResult;
 use oprf::poprf::{PoprfBatchBlindResult, PoprfBlindResult, PoprfClient, PoprfServer};
 #[cfg(feature = "alloc")]
+use oprf::ppss::{PpssBlindResult, PpssClient, PpssRecord, PpssRecoverResult, PpssSetup};
+#[cfg(feature = "alloc")]
+use oprf::privacy_pass::{
+	BatchTokenRequest, BatchTokenResponse, MemoryNonceStore, PrivacyPassBatchClient,
+	PrivacyPassBatchRequestResult,
+};
+use oprf::privacy_pass::{
+	PrivacyPassClient, PrivacyPassIssuer, PrivacyPassRequestResult, Token, TokenRequest,
+	TokenResponse,
+};
+use oprf::private_metadata::{
+	PmbBlindEvaluateResult, PmbBlindResult, PmbClient, PmbProof, PmbPublicKey, PmbServer, PmbToken,
+};
+#[cfg(feature = "alloc")]
+use oprf::psi::cuckoo::{CuckooFilter, CuckooFilterDelta};
+#[cfg(feature = "alloc")]
+use oprf::psi::{
+	PsiBlindResult, PsiCardinalityBlindResult, PsiCardinalityClient, PsiCardinalityServer,
+	PsiClient, PsiServer, PsiSet,
+};
+use oprf::pythia::{
+	HardenedPassword, PythiaBlindResult, PythiaClient, PythiaRatchet, PythiaServer, UpdateToken,
+};
+use oprf::relay::{Relay, RelayBlindResult, RelayResponse};
+#[cfg(feature = "alloc")]
+use oprf::threshold::dkg::{
+	DkgCommitment, DkgComplaint, DkgOutput, DkgRound1, DkgRound2, DkgShare,
+};
+#[cfg(feature = "alloc")]
+use oprf::threshold::refresh::{RefreshOutput, RefreshRound1, RefreshRound2};
+#[cfg(feature = "alloc")]
+use oprf::threshold::{EvaluationShare, KeyShare, PublicKeyShare, ThresholdServer};
+use oprf::updatable::{
+	RotationToken, UpdatableBlindResult, UpdatableClient, UpdatableElement, UpdatableServer,
+};
+#[cfg(feature = "alloc")]
+use oprf::vault::MemoryVaultStore;
+use oprf::vault::{
+	VaultBlindResult, VaultClient, VaultEnrollResult, VaultEnrollment, VaultKeys, VaultRecord,
+	VaultServer, VaultTag,
+};
+#[cfg(feature = "alloc")]
 use oprf::voprf::VoprfBatchAllocBlindResult;
 use oprf::voprf::{VoprfBatchBlindResult, VoprfBlindResult, VoprfClient, VoprfServer};
 use oprf::{Decaf448, Error, NistP256, NistP384, NistP521};
@@ -47,6 +106,7 @@ macro_rules! test_ciphersuite {
 				api!(BlindedElement<$cs>);
 				api!(EvaluationElement<$cs>);
 				api!(Proof<$cs>);
+				api!(UnblindedElement<$cs>);
 				result!(BlindEvaluateResult<$cs>);
 				result!(BatchBlindEvaluateResult<$cs, 1>);
 				#[cfg(feature = "alloc")]
@@ -55,6 +115,7 @@ macro_rules! test_ciphersuite {
 				api!(KeyPair<<$cs as CipherSuite>::Group>);
 				api!(SecretKey<<$cs as CipherSuite>::Group>);
 				api!(PublicKey<<$cs as CipherSuite>::Group>);
+				api!(PossessionProof<$cs>);
 
 				api!(OprfClient<$cs>);
 				api!(OprfServer<$cs>);
@@ -76,6 +137,123 @@ macro_rules! test_ciphersuite {
 				result!(PoprfBatchBlindResult<$cs, 1>);
 				#[cfg(feature = "alloc")]
 				result!(PoprfBatchAllocBlindResult<$cs>);
+
+				common!(PmbClient<$cs>);
+				assert_impl_all!(PmbClient<$cs>: ZeroizeOnDrop);
+				common!(PmbServer<$cs>);
+				assert_impl_all!(PmbServer<$cs>: ZeroizeOnDrop);
+				common!(PmbPublicKey<$cs>);
+				assert_impl_all!(PmbPublicKey<$cs>: ZeroizeOnDrop);
+				common!(PmbProof<$cs>);
+				assert_impl_all!(PmbProof<$cs>: ZeroizeOnDrop);
+				common!(PmbToken<$cs>);
+				assert_impl_all!(PmbToken<$cs>: ZeroizeOnDrop);
+				result!(PmbBlindResult<$cs>);
+				result!(PmbBlindEvaluateResult<$cs>);
+
+				common!(PythiaClient<$cs>);
+				assert_impl_all!(PythiaClient<$cs>: ZeroizeOnDrop);
+				common!(PythiaServer<$cs>);
+				assert_impl_all!(PythiaServer<$cs>: ZeroizeOnDrop);
+				common!(PythiaRatchet<$cs>);
+				assert_impl_all!(PythiaRatchet<$cs>: ZeroizeOnDrop);
+				common!(HardenedPassword<$cs>);
+				assert_impl_all!(HardenedPassword<$cs>: ZeroizeOnDrop);
+				common!(UpdateToken<$cs>);
+				assert_impl_all!(UpdateToken<$cs>: ZeroizeOnDrop);
+				result!(PythiaBlindResult<$cs>);
+
+				common!(Relay<$cs>);
+				assert_impl_all!(Relay<$cs>: ZeroizeOnDrop);
+				result!(RelayBlindResult<$cs>);
+				result!(RelayResponse<$cs>);
+
+				common!(UpdatableClient<$cs>);
+				assert_impl_all!(UpdatableClient<$cs>: ZeroizeOnDrop);
+				common!(UpdatableServer<$cs>);
+				assert_impl_all!(UpdatableServer<$cs>: ZeroizeOnDrop);
+				common!(UpdatableElement<$cs>);
+				assert_impl_all!(UpdatableElement<$cs>: ZeroizeOnDrop);
+				common!(RotationToken<$cs>);
+				assert_impl_all!(RotationToken<$cs>: ZeroizeOnDrop);
+				result!(UpdatableBlindResult<$cs>);
+
+				common!(VaultClient<$cs>);
+				assert_impl_all!(VaultClient<$cs>: ZeroizeOnDrop);
+				common!(VaultRecord<$cs>);
+				assert_impl_all!(VaultRecord<$cs>: ZeroizeOnDrop);
+				common!(VaultEnrollment<$cs>);
+				assert_impl_all!(VaultEnrollment<$cs>: ZeroizeOnDrop);
+				common!(VaultTag<$cs>);
+				assert_impl_all!(VaultTag<$cs>: ZeroizeOnDrop);
+				result!(VaultBlindResult<$cs>);
+				result!(VaultEnrollResult<$cs>);
+				result!(VaultKeys<$cs>);
+
+				#[cfg(feature = "alloc")]
+				{
+					api!(KeyShare<<$cs as CipherSuite>::Group>);
+					common!(PublicKeyShare<<$cs as CipherSuite>::Group>);
+					#[cfg(feature = "serde")]
+					assert_impl_all!(PublicKeyShare<<$cs as CipherSuite>::Group>: Deserialize<'static>, Serialize);
+					api!(ThresholdServer<$cs>);
+					assert_impl_all!(EvaluationShare<$cs>: Debug);
+
+					result!(DkgRound1<<$cs as CipherSuite>::Group>);
+					result!(DkgRound2<<$cs as CipherSuite>::Group>);
+					common!(DkgCommitment<<$cs as CipherSuite>::Group>);
+					common!(DkgShare<<$cs as CipherSuite>::Group>);
+					assert_impl_all!(DkgShare<<$cs as CipherSuite>::Group>: ZeroizeOnDrop);
+					common!(DkgOutput<<$cs as CipherSuite>::Group>);
+
+					result!(RefreshRound1<<$cs as CipherSuite>::Group>);
+					result!(RefreshRound2<<$cs as CipherSuite>::Group>);
+					common!(RefreshOutput<<$cs as CipherSuite>::Group>);
+
+					common!(PsiClient<$cs>);
+					assert_impl_all!(PsiClient<$cs>: ZeroizeOnDrop);
+					common!(PsiCardinalityClient<$cs>);
+					assert_impl_all!(PsiCardinalityClient<$cs>: ZeroizeOnDrop);
+					common!(PsiServer<$cs>);
+					assert_impl_all!(PsiServer<$cs>: ZeroizeOnDrop);
+					common!(PsiCardinalityServer<$cs>);
+					assert_impl_all!(PsiCardinalityServer<$cs>: ZeroizeOnDrop);
+					common!(PsiSet<$cs>);
+					result!(PsiBlindResult<$cs>);
+					result!(PsiCardinalityBlindResult<$cs>);
+
+					common!(CheckupClient<$cs>);
+					assert_impl_all!(CheckupClient<$cs>: ZeroizeOnDrop);
+					common!(CheckupServer<$cs>);
+					assert_impl_all!(CheckupServer<$cs>: ZeroizeOnDrop);
+					common!(CheckupBuckets<$cs>);
+					result!(CheckupRequestResult<$cs>);
+					common!(CheckupRequest<$cs>);
+					assert_impl_all!(CheckupRequest<$cs>: ZeroizeOnDrop);
+					common!(CheckupResponse<$cs>);
+
+					common!(AnonymousServer<$cs>);
+					assert_impl_all!(AnonymousServer<$cs>: ZeroizeOnDrop);
+					common!(OrProof<$cs>);
+					assert_impl_all!(OrProof<$cs>: ZeroizeOnDrop);
+					result!(AnonymousBlindEvaluateResult<$cs>);
+
+					common!(DuplessClient<$cs>);
+					assert_impl_all!(DuplessClient<$cs>: ZeroizeOnDrop);
+					common!(DuplessServer<$cs>);
+					assert_impl_all!(DuplessServer<$cs>: ZeroizeOnDrop);
+					result!(DuplessBlindResult<$cs>);
+
+					common!(PpssClient<$cs>);
+					assert_impl_all!(PpssClient<$cs>: ZeroizeOnDrop);
+					common!(PpssRecord<$cs>);
+					result!(PpssSetup<$cs>);
+					result!(PpssBlindResult<$cs>);
+					result!(PpssRecoverResult<$cs>);
+
+					common!(MemoryVaultStore<$cs>);
+					assert_impl_all!(MemoryVaultStore<$cs>: Default, ZeroizeOnDrop);
+				}
 			}
 		}
 	};
@@ -98,6 +276,118 @@ assert_impl_all!(Error<()>: Copy, Hash);
 
 assert_impl_all!(Error<io::Error>: Display, error::Error);
 
+#[cfg(feature = "alloc")]
+common!(DkgComplaint);
+#[cfg(feature = "alloc")]
+assert_impl_all!(DkgComplaint: Copy, Hash);
+
+#[cfg(feature = "alloc")]
+common!(MemoryRateLimiter);
+
+common!(VaultServer);
+assert_impl_all!(VaultServer: Copy, Hash);
+
+common!(PrivacyPassClient);
+assert_impl_all!(PrivacyPassClient: ZeroizeOnDrop);
+common!(PrivacyPassIssuer);
+assert_impl_all!(PrivacyPassIssuer: ZeroizeOnDrop);
+result!(PrivacyPassRequestResult);
+common!(TokenRequest);
+assert_impl_all!(TokenRequest: ZeroizeOnDrop);
+common!(TokenResponse);
+assert_impl_all!(TokenResponse: ZeroizeOnDrop);
+common!(Token);
+assert_impl_all!(Token: ZeroizeOnDrop);
+#[cfg(feature = "alloc")]
+common!(PrivacyPassBatchClient);
+#[cfg(feature = "alloc")]
+assert_impl_all!(PrivacyPassBatchClient: ZeroizeOnDrop);
+#[cfg(feature = "alloc")]
+result!(PrivacyPassBatchRequestResult);
+#[cfg(feature = "alloc")]
+common!(BatchTokenRequest);
+#[cfg(feature = "alloc")]
+assert_impl_all!(BatchTokenRequest: ZeroizeOnDrop);
+#[cfg(feature = "alloc")]
+common!(BatchTokenResponse);
+#[cfg(feature = "alloc")]
+assert_impl_all!(BatchTokenResponse: ZeroizeOnDrop);
+#[cfg(feature = "alloc")]
+common!(MemoryNonceStore);
+#[cfg(feature = "alloc")]
+common!(CuckooFilter);
+#[cfg(feature = "alloc")]
+common!(CuckooFilterDelta);
+
+/// [`OpaqueCipherSuite`] used to check OPAQUE types.
+#[cfg(feature = "opaque")]
+enum Opaque {}
+
+#[cfg(feature = "opaque")]
+impl OpaqueCipherSuite for Opaque {
+	type Oprf = Ristretto255;
+	type Ksf = IdentityKsf;
+}
+
+#[cfg(feature = "opaque")]
+common!(OpaqueServer<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(OpaqueServer<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+common!(ServerLogin<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(ServerLogin<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+result!(ServerLoginResult<Opaque>);
+#[cfg(feature = "opaque")]
+common!(ClientRegistration<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(ClientRegistration<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+result!(ClientRegistrationStartResult<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(ClientRegistrationFinishResult<Opaque>: Debug);
+#[cfg(feature = "opaque")]
+common!(ClientLogin<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(ClientLogin<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+result!(ClientLoginStartResult<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(ClientLoginFinishResult<Opaque>: Debug);
+#[cfg(feature = "opaque")]
+common!(RegistrationRequest<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(RegistrationRequest<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+common!(RegistrationResponse<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(RegistrationResponse<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+common!(RegistrationRecord<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(RegistrationRecord<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+common!(Ke1<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(Ke1<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+common!(Ke2<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(Ke2<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+common!(Ke3<Opaque>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(Ke3<Opaque>: ZeroizeOnDrop);
+#[cfg(feature = "opaque")]
+common!(IdentityKsf);
+#[cfg(feature = "opaque")]
+assert_impl_all!(IdentityKsf: Copy, Default, Hash);
+#[cfg(feature = "opaque")]
+common!(Identifiers<'static>);
+#[cfg(feature = "opaque")]
+assert_impl_all!(Identifiers<'static>: Copy, Default, Hash);
+
 common!(Id);
 assert_impl_all!(Mode: Copy, Hash);
 
@@ -0,0 +1,169 @@
+//! Tests for [`UnblindedElement`].
+
+#![cfg(test)]
+#![expect(clippy::cargo_common_metadata, reason = "tests")]
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::common::UnblindedElement;
+use oprf::oprf::{OprfBatchBlindResult, OprfBlindResult, OprfClient, OprfServer};
+use oprf::poprf::{PoprfBlindResult, PoprfClient, PoprfServer};
+use oprf::voprf::{VoprfBlindResult, VoprfClient, VoprfServer};
+use oprf_test::{INFO, INPUT, test_ciphersuites};
+
+test_ciphersuites!(finalize_oprf);
+
+/// Tests that [`UnblindedElement::finalize()`] matches
+/// [`OprfClient::finalize()`].
+fn finalize_oprf<Cs: CipherSuite>() {
+	let server = OprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let OprfBlindResult {
+		client,
+		blinded_element,
+	} = OprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	let evaluation_element = server.blind_evaluate(&blinded_element);
+
+	let output = client.finalize(INPUT, &evaluation_element).unwrap();
+	let unblinded_element = client.finalize_element(&evaluation_element);
+	assert_eq!(unblinded_element.finalize(INPUT, None).unwrap(), output);
+	assert_ne!(
+		unblinded_element.finalize(INPUT, Some(INFO)).unwrap(),
+		output
+	);
+	assert_eq!(server.evaluate(INPUT).unwrap(), output);
+
+	assert_eq!(
+		UnblindedElement::from_repr(unblinded_element.as_repr()).unwrap(),
+		unblinded_element
+	);
+}
+
+test_ciphersuites!(finalize_oprf_batch);
+
+/// Tests that [`OprfClient::batch_finalize_element()`] matches
+/// [`OprfClient::finalize_element()`].
+fn finalize_oprf_batch<Cs: CipherSuite>() {
+	let server = OprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let OprfBatchBlindResult {
+		clients,
+		blinded_elements,
+	} = OprfClient::<Cs>::batch_blind(&mut rand::rng(), &[INPUT, &[b"other".as_slice()]]).unwrap();
+	let evaluation_elements = server.batch_blind_evaluate(&blinded_elements);
+
+	let unblinded_elements = OprfClient::batch_finalize_element(&clients, &evaluation_elements);
+
+	for ((client, evaluation_element), unblinded_element) in clients
+		.iter()
+		.zip(&evaluation_elements)
+		.zip(&unblinded_elements)
+	{
+		assert_eq!(
+			&client.finalize_element(evaluation_element),
+			unblinded_element
+		);
+	}
+
+	#[cfg(feature = "alloc")]
+	{
+		let alloc_unblinded_elements =
+			OprfClient::batch_alloc_finalize_element(clients.iter(), evaluation_elements.iter())
+				.unwrap();
+		assert_eq!(alloc_unblinded_elements, unblinded_elements);
+
+		let result = OprfClient::batch_alloc_finalize_element(
+			clients.iter(),
+			evaluation_elements.iter().skip(1),
+		);
+		assert_eq!(result, Err(Error::Batch));
+	}
+}
+
+test_ciphersuites!(finalize_voprf);
+
+/// Tests that [`UnblindedElement::finalize()`] matches
+/// [`VoprfClient::finalize()`].
+fn finalize_voprf<Cs: CipherSuite>() {
+	let server = VoprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let VoprfBlindResult {
+		client,
+		blinded_element,
+	} = VoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	let result = server
+		.blind_evaluate(&mut rand::rng(), &blinded_element)
+		.unwrap();
+
+	let output = client
+		.finalize(
+			server.public_key(),
+			INPUT,
+			&result.evaluation_element,
+			&result.proof,
+		)
+		.unwrap();
+	let unblinded_element = client
+		.finalize_element(
+			server.public_key(),
+			&result.evaluation_element,
+			&result.proof,
+		)
+		.unwrap();
+	assert_eq!(unblinded_element.finalize(INPUT, None).unwrap(), output);
+
+	let other = VoprfServer::<Cs>::new(&mut rand::rng()).unwrap();
+	assert_eq!(
+		client.finalize_element(
+			other.public_key(),
+			&result.evaluation_element,
+			&result.proof,
+		),
+		Err(Error::Proof)
+	);
+}
+
+test_ciphersuites!(finalize_poprf);
+
+/// Tests that [`UnblindedElement::finalize()`] matches
+/// [`PoprfClient::finalize()`].
+fn finalize_poprf<Cs: CipherSuite>() {
+	let server = PoprfServer::<Cs>::new(&mut rand::rng(), INFO).unwrap();
+	let PoprfBlindResult {
+		client,
+		blinded_element,
+	} = PoprfClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+	let result = server
+		.blind_evaluate(&mut rand::rng(), &blinded_element)
+		.unwrap();
+
+	let output = client
+		.finalize(
+			server.public_key(),
+			INPUT,
+			&result.evaluation_element,
+			&result.proof,
+			INFO,
+		)
+		.unwrap();
+	let unblinded_element = client
+		.finalize_element(
+			server.public_key(),
+			&result.evaluation_element,
+			&result.proof,
+			INFO,
+		)
+		.unwrap();
+	assert_eq!(
+		unblinded_element.finalize(INPUT, Some(INFO)).unwrap(),
+		output
+	);
+	assert_ne!(unblinded_element.finalize(INPUT, None).unwrap(), output);
+
+	assert_eq!(
+		client.finalize_element(
+			server.public_key(),
+			&result.evaluation_element,
+			&result.proof,
+			b"wrong",
+		),
+		Err(Error::Proof)
+	);
+}
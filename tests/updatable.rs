@@ -0,0 +1,115 @@
+//! Tests [`oprf::updatable`] related functionality.
+
+#![cfg(test)]
+#![expect(clippy::cargo_common_metadata, reason = "tests")]
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::updatable::{RotationToken, UpdatableClient, UpdatableElement, UpdatableServer};
+use oprf_test::test_ciphersuites;
+
+/// Input of the client.
+const INPUT: &[&[u8]] = &[b"input"];
+
+test_ciphersuites!(rotate);
+
+/// Tests that [`RotationToken`]s re-key [`UpdatableElement`]s.
+fn rotate<Cs: CipherSuite>() {
+	let mut server = UpdatableServer::<Cs>::new(&mut rand::rng()).unwrap();
+
+	let finalize = |server: &UpdatableServer<Cs>| {
+		let blind_result = UpdatableClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+		let evaluation_element = server.blind_evaluate(&blind_result.blinded_element);
+		blind_result.client.finalize(&evaluation_element)
+	};
+
+	let mut element = finalize(&server);
+	assert_eq!(element, finalize(&server));
+	assert_eq!(
+		UpdatableElement::from_repr(element.as_repr()).unwrap(),
+		element
+	);
+
+	let old_public_key = server.public_key().clone();
+	let rotation_token = server.rotate(&mut rand::rng()).unwrap();
+	rotation_token
+		.verify(&old_public_key, server.public_key())
+		.unwrap();
+
+	let fresh = finalize(&server);
+	assert_ne!(element, fresh);
+
+	element.apply_rotation(&rotation_token);
+	assert_eq!(element, fresh);
+
+	assert_eq!(
+		RotationToken::from_repr(&rotation_token.to_repr()).unwrap(),
+		rotation_token
+	);
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests that [`RotationToken`]s for different keys are rejected.
+fn invalid<Cs: CipherSuite>() {
+	let mut server = UpdatableServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let old_public_key = server.public_key().clone();
+	let rotation_token = server.rotate(&mut rand::rng()).unwrap();
+
+	assert_eq!(
+		rotation_token.verify(server.public_key(), &old_public_key),
+		Err(Error::Proof)
+	);
+	assert_eq!(
+		rotation_token.verify(&old_public_key, &old_public_key),
+		Err(Error::Proof)
+	);
+
+	let other = server.rotate(&mut rand::rng()).unwrap();
+	assert_eq!(
+		other.verify(&old_public_key, server.public_key()),
+		Err(Error::Proof)
+	);
+
+	let repr = rotation_token.to_repr();
+	let (_, repr) = repr.split_last().unwrap();
+	assert_eq!(RotationToken::<Cs>::from_repr(repr), Err(Error::FromRepr));
+}
+
+test_ciphersuites!(wrong_key);
+
+/// Tests that a [`RotationToken`] from a different old key is rejected and
+/// doesn't re-key [`UpdatableElement`]s.
+fn wrong_key<Cs: CipherSuite>() {
+	let mut server = UpdatableServer::<Cs>::new(&mut rand::rng()).unwrap();
+	let mut other = UpdatableServer::<Cs>::new(&mut rand::rng()).unwrap();
+
+	let finalize = |server: &UpdatableServer<Cs>| {
+		let blind_result = UpdatableClient::<Cs>::blind(&mut rand::rng(), INPUT).unwrap();
+		let evaluation_element = server.blind_evaluate(&blind_result.blinded_element);
+		blind_result.client.finalize(&evaluation_element)
+	};
+
+	let mut element = finalize(&server);
+	let old_public_key = server.public_key().clone();
+	let other_old_public_key = other.public_key().clone();
+
+	server.rotate(&mut rand::rng()).unwrap();
+	let rotation_token = other.rotate(&mut rand::rng()).unwrap();
+	rotation_token
+		.verify(&other_old_public_key, other.public_key())
+		.unwrap();
+
+	assert_eq!(
+		rotation_token.verify(&old_public_key, other.public_key()),
+		Err(Error::Proof)
+	);
+	assert_eq!(
+		rotation_token.verify(&old_public_key, server.public_key()),
+		Err(Error::Proof)
+	);
+
+	element.apply_rotation(&rotation_token);
+	assert_ne!(element, finalize(&server));
+	assert_ne!(element, finalize(&other));
+}
@@ -0,0 +1,219 @@
+//! Tests [`oprf::vault`] related functionality.
+
+#![cfg(test)]
+#![cfg(all(feature = "alloc", feature = "vault"))]
+#![expect(clippy::cargo_common_metadata, reason = "tests")]
+
+use std::num::NonZeroU32;
+
+use oprf::Error;
+use oprf::cipher_suite::CipherSuite;
+use oprf::vault::{
+	MemoryVaultStore, VaultClient, VaultEnrollment, VaultKeys, VaultRecord, VaultServer,
+	VaultStore, VaultTag,
+};
+use oprf_test::test_ciphersuites;
+
+/// `user_id` to use.
+const USER_ID: &[u8] = b"user";
+/// Password to use.
+const PASSWORD: &[u8] = b"password";
+/// Maximum number of attempts to use.
+const MAX_ATTEMPTS: NonZeroU32 = NonZeroU32::new(3).unwrap();
+
+/// Enrolls [`USER_ID`] with [`PASSWORD`].
+fn enroll<Cs: CipherSuite>(store: &mut MemoryVaultStore<Cs>) -> VaultKeys<Cs> {
+	let blind_result = VaultClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+	let enroll_result = VaultServer::enroll(
+		&mut rand::rng(),
+		store,
+		USER_ID,
+		&blind_result.blinded_element,
+	)
+	.unwrap();
+	let keys = blind_result
+		.client
+		.finalize(PASSWORD, &enroll_result.evaluation_element)
+		.unwrap();
+	VaultServer::confirm_enrollment(store, USER_ID, &enroll_result.enrollment, &keys.tag).unwrap();
+
+	keys
+}
+
+/// Runs a restore attempt of [`USER_ID`] with the provided `password`.
+fn restore<Cs: CipherSuite>(
+	server: &VaultServer,
+	store: &mut MemoryVaultStore<Cs>,
+	password: &[u8],
+) -> Result<VaultKeys<Cs>, Error> {
+	let blind_result = VaultClient::<Cs>::blind(&mut rand::rng(), password).unwrap();
+	let evaluation_element = server.evaluate(store, USER_ID, &blind_result.blinded_element)?;
+	let keys = blind_result
+		.client
+		.finalize(password, &evaluation_element)
+		.unwrap();
+	server.confirm(store, USER_ID, &keys.tag)?;
+
+	Ok(keys)
+}
+
+test_ciphersuites!(basic);
+
+/// Tests that the vault key is recovered with the correct password.
+fn basic<Cs: CipherSuite>() {
+	let server = VaultServer::new(MAX_ATTEMPTS);
+	let mut store = MemoryVaultStore::<Cs>::new();
+	let keys = enroll(&mut store);
+
+	for _ in 0..5 {
+		let restored = restore(&server, &mut store, PASSWORD).unwrap();
+
+		assert_eq!(restored.key, keys.key);
+		assert_eq!(restored.tag, keys.tag);
+		assert_eq!(store.get(USER_ID).unwrap().attempts(), 0);
+	}
+
+	// Check de/serialization.
+	let record = store.get(USER_ID).unwrap();
+	assert!(record.is_confirmed());
+	assert_eq!(
+		VaultRecord::<Cs>::from_repr(&record.to_repr()).unwrap(),
+		record
+	);
+	assert_eq!(
+		VaultTag::<Cs>::from_repr(keys.tag.as_repr()).unwrap(),
+		keys.tag
+	);
+
+	// A new enrollment after removing the record changes the vault key.
+	store.remove(USER_ID);
+	let new_keys = enroll(&mut store);
+	assert_ne!(new_keys.key, keys.key);
+}
+
+test_ciphersuites!(limit);
+
+/// Tests that the [`VaultRecord`] is wiped after too many wrong attempts.
+fn limit<Cs: CipherSuite>() {
+	let server = VaultServer::new(MAX_ATTEMPTS);
+	let mut store = MemoryVaultStore::<Cs>::new();
+	enroll(&mut store);
+
+	// A correct password resets the attempt counter.
+	for _ in 1..MAX_ATTEMPTS.get() {
+		let result = restore(&server, &mut store, b"wrong");
+		assert_eq!(result.unwrap_err(), Error::Password);
+	}
+
+	assert_eq!(
+		store.get(USER_ID).unwrap().attempts(),
+		MAX_ATTEMPTS.get() - 1
+	);
+	restore(&server, &mut store, PASSWORD).unwrap();
+
+	// Last wrong attempt wipes the record.
+	for _ in 1..MAX_ATTEMPTS.get() {
+		let result = restore(&server, &mut store, b"wrong");
+		assert_eq!(result.unwrap_err(), Error::Password);
+	}
+
+	let result = restore(&server, &mut store, b"wrong");
+	assert_eq!(result.unwrap_err(), Error::AttemptLimit);
+	assert!(store.get(USER_ID).is_none());
+
+	let result = restore(&server, &mut store, PASSWORD);
+	assert_eq!(result.unwrap_err(), Error::Vault);
+
+	// Unconfirmed attempts are counted as well.
+	enroll(&mut store);
+	let blind_result = VaultClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+
+	for _ in 0..MAX_ATTEMPTS.get() {
+		server
+			.evaluate(&mut store, USER_ID, &blind_result.blinded_element)
+			.unwrap();
+	}
+
+	let result = server.evaluate(&mut store, USER_ID, &blind_result.blinded_element);
+	assert_eq!(result.unwrap_err(), Error::AttemptLimit);
+	assert!(store.get(USER_ID).is_none());
+}
+
+test_ciphersuites!(invalid);
+
+/// Tests that unknown and unconfirmed users are rejected.
+fn invalid<Cs: CipherSuite>() {
+	let server = VaultServer::new(MAX_ATTEMPTS);
+	let mut store = MemoryVaultStore::<Cs>::new();
+	let blind_result = VaultClient::<Cs>::blind(&mut rand::rng(), PASSWORD).unwrap();
+
+	// Failure on unknown user.
+	let result = server.evaluate(&mut store, USER_ID, &blind_result.blinded_element);
+	assert_eq!(result.unwrap_err(), Error::Vault);
+
+	// Failure on unconfirmed enrollment.
+	let enroll_result = VaultServer::enroll(
+		&mut rand::rng(),
+		&mut store,
+		USER_ID,
+		&blind_result.blinded_element,
+	)
+	.unwrap();
+	let record = store.get(USER_ID).unwrap();
+	assert!(!record.is_confirmed());
+	assert_eq!(
+		VaultRecord::<Cs>::from_repr(&record.to_repr()).unwrap(),
+		record
+	);
+	assert_eq!(
+		VaultEnrollment::<Cs>::from_repr(enroll_result.enrollment.as_repr()).unwrap(),
+		enroll_result.enrollment
+	);
+
+	let result = server.evaluate(&mut store, USER_ID, &blind_result.blinded_element);
+	assert_eq!(result.unwrap_err(), Error::Vault);
+
+	let keys = blind_result
+		.client
+		.finalize(PASSWORD, &enroll_result.evaluation_element)
+		.unwrap();
+	let result = server.confirm(&mut store, USER_ID, &keys.tag);
+	assert_eq!(result.unwrap_err(), Error::Vault);
+
+	// Failure on confirmation of unknown user.
+	let result =
+		VaultServer::confirm_enrollment(&mut store, b"other", &enroll_result.enrollment, &keys.tag);
+	assert_eq!(result.unwrap_err(), Error::Vault);
+
+	// Failure on confirmation with a different enrollment secret.
+	let other_enrollment =
+		VaultEnrollment::<Cs>::from_repr(&vec![0; enroll_result.enrollment.as_repr().len()])
+			.unwrap();
+	let result = VaultServer::confirm_enrollment(&mut store, USER_ID, &other_enrollment, &keys.tag);
+	assert_eq!(result.unwrap_err(), Error::Vault);
+	assert!(!store.get(USER_ID).unwrap().is_confirmed());
+
+	// Failure on confirming or enrolling over a confirmed record.
+	VaultServer::confirm_enrollment(&mut store, USER_ID, &enroll_result.enrollment, &keys.tag)
+		.unwrap();
+	let record = store.get(USER_ID).unwrap();
+	let result =
+		VaultServer::confirm_enrollment(&mut store, USER_ID, &enroll_result.enrollment, &keys.tag);
+	assert_eq!(result.unwrap_err(), Error::AlreadyEnrolled);
+	let result = VaultServer::enroll(
+		&mut rand::rng(),
+		&mut store,
+		USER_ID,
+		&blind_result.blinded_element,
+	);
+	assert_eq!(result.unwrap_err(), Error::AlreadyEnrolled);
+	assert_eq!(store.get(USER_ID).unwrap(), record);
+
+	// Failure on not enough bytes.
+	let result = VaultRecord::<Cs>::from_repr(&[]);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+	let result = VaultTag::<Cs>::from_repr(&[]);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+	let result = VaultEnrollment::<Cs>::from_repr(&[]);
+	assert_eq!(result.unwrap_err(), Error::FromRepr);
+}